@@ -1,7 +1,7 @@
-use day_01::process_part2;
-use std::fs;
+use aoc_utils::inputs;
+use day_2022_01::process_part2;
 
 fn main() {
-    let file = fs::read_to_string("./input.txt").unwrap();
-    println!("{}", process_part2(file));
+    let input = inputs::input(2022, 1).unwrap_or_else(|err| panic!("{err}"));
+    println!("{}", process_part2(input));
 }
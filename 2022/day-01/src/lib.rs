@@ -1,54 +1,44 @@
-pub fn process_part1(input: String) -> u32 {
-    input
-        .split("\n\n")
-        .map(|elf| {
-            elf.lines()
-                .map(|line| line.parse::<u32>().unwrap())
-                .sum::<u32>()
-        })
-        .max()
-        .unwrap()
+use aoc_utils::{
+    parse::{blocks, integer, lines, parse_all},
+    Error,
+};
+
+fn calories_per_elf(input: &str) -> Result<Vec<u32>, Error> {
+    let elves: Vec<Vec<u32>> = parse_all(blocks(lines(integer)), input)?;
+    Ok(elves.iter().map(|elf| elf.iter().sum()).collect())
 }
 
-pub fn process_part2(input: String) -> u32 {
-    let mut sums = input
-        .split("\n\n")
-        .map(|elf| {
-            elf.lines()
-                .map(|line| line.parse::<u32>().unwrap())
-                .sum::<u32>()
-        })
-        .collect::<Vec<u32>>();
+/// The calories carried by the three elves carrying the most.
+fn top_three(calories: &[u32]) -> u32 {
+    let mut sums = calories.to_vec();
     sums.sort_by(|a, b| b.cmp(a));
-    return sums.iter().take(3).sum::<u32>();
+    sums.iter().take(3).sum()
+}
+
+aoc_utils::solution! {
+    Day01: 2022 day 1;
+    type Parsed<'a> = Vec<u32>;
+    parse: |input| calories_per_elf(input);
+    part1: |calories| Ok(*calories.iter().max().unwrap());
+    part2: |calories| Ok(top_three(calories));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
 
-    const EXAMPLE_INPUT: &str = "1000
-2000
-3000
-
-4000
-
-5000
-6000
-
-7000
-8000
-9000
-
-10000";
+    fn example() -> &'static str {
+        inputs::example(2022, 1, 1).unwrap()
+    }
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT.to_string()), 24000);
+        assert_eq!(process_part1(example()), "24000");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT.to_string()), 45000);
+        assert_eq!(process_part2(example()), "45000");
     }
 }
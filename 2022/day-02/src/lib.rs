@@ -1,7 +1,9 @@
-use std::{cmp::Ordering, str::FromStr, sync::RwLock};
+use std::{cmp::Ordering, str::FromStr};
+
+use aoc_utils::{parse::map_lines, Error};
 
 #[derive(PartialEq, Copy, Clone)]
-enum Move {
+pub enum Move {
     Rock = 1,
     Paper = 2,
     Scissors = 3,
@@ -39,7 +41,7 @@ impl Move {
 }
 
 #[derive(PartialEq, Copy, Clone)]
-enum Outcome {
+pub enum Outcome {
     Draw = 3,
     Win = 6,
     Loss = 0,
@@ -73,9 +75,20 @@ impl FromStr for Outcome {
     }
 }
 
+/// Every line of the strategy guide: the opponent's move, then the second
+/// column read both as my move and as the outcome I need.
+fn parse_guide(input: &str) -> Result<Vec<(Move, Move, Outcome)>, Error> {
+    map_lines(input, |game| {
+        let (their_move, second) = game
+            .split_once(' ')
+            .ok_or_else(|| format!("expected two columns in {game:?}"))?;
+        Ok::<_, String>((their_move.parse()?, second.parse()?, second.parse()?))
+    })
+}
+
 fn calculate_my_move(opponent: &Move, desired_outcome: &Outcome) -> Move {
     match desired_outcome {
-        Outcome::Draw => opponent.clone(),
+        Outcome::Draw => *opponent,
         Outcome::Win => match opponent {
             Move::Scissors => Move::Rock,
             Move::Rock => Move::Paper,
@@ -89,46 +102,46 @@ fn calculate_my_move(opponent: &Move, desired_outcome: &Outcome) -> Move {
     }
 }
 
-pub fn process_part1(input: String) -> u32 {
-    input
-        .lines()
-        .map(|game| {
-            let (their_move, my_move) = game.split_once(" ").unwrap();
-            let them = their_move.parse::<Move>().unwrap();
-            let me = my_move.parse::<Move>().unwrap();
-            me.points() + Outcome::from_moves(&me, &them).unwrap().points()
-        })
-        .sum::<u32>()
+/// My total score when the second column is the move I play.
+fn score_by_move(guide: &[(Move, Move, Outcome)]) -> u32 {
+    guide
+        .iter()
+        .map(|(them, me, _)| me.points() + Outcome::from_moves(me, them).unwrap().points())
+        .sum()
 }
 
-pub fn process_part2(input: String) -> u32 {
-    input
-        .lines()
-        .map(|game| {
-            let (their_move, desired_outcome) = game.split_once(" ").unwrap();
-            let them = their_move.parse::<Move>().unwrap();
-            let outcome = desired_outcome.parse::<Outcome>().unwrap();
-            let me = calculate_my_move(&them, &outcome);
-            outcome.points() + me.points()
-        })
-        .sum::<u32>()
+/// My total score when the second column is the outcome I need.
+fn score_by_outcome(guide: &[(Move, Move, Outcome)]) -> u32 {
+    guide
+        .iter()
+        .map(|(them, _, outcome)| outcome.points() + calculate_my_move(them, outcome).points())
+        .sum()
+}
+
+aoc_utils::solution! {
+    Day02: 2022 day 2;
+    type Parsed<'a> = Vec<(Move, Move, Outcome)>;
+    parse: |input| parse_guide(input);
+    part1: |guide| Ok(score_by_move(guide));
+    part2: |guide| Ok(score_by_outcome(guide));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
 
-    const EXAMPLE_INPUT: &str = "A Y
-B X
-C Z";
+    fn example() -> &'static str {
+        inputs::example(2022, 2, 1).unwrap()
+    }
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT.to_string()), 15);
+        assert_eq!(process_part1(example()), "15");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT.to_string()), 12);
+        assert_eq!(process_part2(example()), "12");
     }
 }
@@ -1,4 +1,4 @@
-use itertools::Itertools;
+use aoc_utils::{parse::map_lines, Error};
 
 fn to_priority(c: char) -> u32 {
     match c {
@@ -8,53 +8,72 @@ fn to_priority(c: char) -> u32 {
     }
 }
 
-pub fn process_part1(input: String) -> u32 {
-    input
-        .lines()
+fn parse_rucksacks(input: &str) -> Result<Vec<&str>, Error> {
+    map_lines(input, |rucksack| {
+        match rucksack.chars().find(|c| !c.is_ascii_alphabetic()) {
+            Some(c) => Err(format!("unacceptable character '{c}'")),
+            None => Ok(rucksack),
+        }
+    })
+}
+
+fn common_item(rucksack: &str, others: &[&str]) -> Result<char, Error> {
+    rucksack
+        .chars()
+        .find(|c| others.iter().all(|other| other.contains(*c)))
+        .ok_or_else(|| Error::invalid(format!("{rucksack} has nothing in common with {others:?}")))
+}
+
+/// Sum of the priorities of the item in both compartments of each rucksack.
+fn misplaced_priorities(rucksacks: &[&str]) -> Result<u32, Error> {
+    rucksacks
+        .iter()
         .map(|rucksack| {
             let (left, right) = rucksack.split_at(rucksack.len() / 2);
-            let common_char = left.chars().find(|c| right.contains(*c)).unwrap();
-            to_priority(common_char)
+            common_item(left, &[right]).map(to_priority)
         })
         .sum()
 }
 
-pub fn process_part2(input: String) -> u32 {
-    input
-        .lines()
+/// Sum of the priorities of the badge that each team of three elves carries.
+fn badge_priorities(rucksacks: &[&str]) -> Result<u32, Error> {
+    rucksacks
         .chunks(3)
-        .into_iter()
         .map(|elf_team| {
-            let [a, b, c] = elf_team.collect::<Vec<&str>>()[..] else {
-                panic!("unexpected elf team size")
+            let [a, b, c] = elf_team else {
+                return Err(Error::invalid(
+                    "the elves can't be split into teams of three",
+                ));
             };
-            let common_char = a
-                .chars()
-                .find(|ch| b.contains(*ch) && c.contains(*ch))
-                .unwrap();
-            to_priority(common_char)
+            common_item(a, &[b, c]).map(to_priority)
         })
-        .sum::<u32>()
+        .sum()
+}
+
+aoc_utils::solution! {
+    Day03: 2022 day 3;
+    type Parsed<'a> = Vec<&'a str>;
+    parse: |input| parse_rucksacks(input);
+    part1: |rucksacks| misplaced_priorities(rucksacks);
+    part2: |rucksacks| badge_priorities(rucksacks);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
 
-    const EXAMPLE_INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
-jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-PmmdzqPrVvPwwTWBwg
-wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-ttgJtRGJQctTZtZT
-CrZsJsPPZsGzwwsLwLmpwMDw";
+    fn example() -> &'static str {
+        inputs::example(2022, 3, 1).unwrap()
+    }
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT.to_string()), 157);
+        assert_eq!(process_part1(example()), "157");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT.to_string()), 70);
+        assert_eq!(process_part2(example()), "70");
     }
 }
@@ -1,7 +1,7 @@
-use day_04::process_part1;
-use std::fs;
+use aoc_utils::inputs;
+use day_2022_04::process_part1;
 
 fn main() {
-    let file = fs::read_to_string("./input.txt").unwrap();
-    println!("{}", process_part1(file));
+    let input = inputs::input(2022, 4).unwrap_or_else(|err| panic!("{err}"));
+    println!("{}", process_part1(input));
 }
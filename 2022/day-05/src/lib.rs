@@ -1,7 +1,8 @@
+use aoc_utils::{parse::parse_all, Error};
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{self, digit1, multispace1, newline},
+    character::complete::{self, digit1, line_ending, multispace1},
     character::complete::{alpha1, space1},
     multi::{many1, separated_list1},
     sequence::{delimited, preceded},
@@ -27,8 +28,9 @@ fn line(input: &str) -> IResult<&str, Vec<Option<&str>>> {
     Ok((input, result))
 }
 
+/// A move as written, with stacks numbered from 1.
 #[derive(Debug)]
-struct Move {
+pub struct Move {
     number: u32,
     from: u32,
     to: u32,
@@ -41,30 +43,21 @@ fn move_crate(input: &str) -> IResult<&str, Move> {
     let (input, from) = complete::u32(input)?;
     let (input, _) = tag(" to ")(input)?;
     let (input, to) = complete::u32(input)?;
-    Ok((
-        input,
-        Move {
-            number,
-            from: from - 1,
-            to: to - 1,
-        },
-    ))
+    Ok((input, Move { number, from, to }))
 }
 
 fn crates(input: &str) -> IResult<&str, (Vec<Vec<&str>>, Vec<Move>)> {
-    let (input, crates_horizontal) = separated_list1(newline, line)(input)?;
-    let (input, _) = newline(input)?;
-    let (input, _numbers) = many1(preceded(space1, digit1))(input)?;
+    let (input, crates_horizontal) = separated_list1(line_ending, line)(input)?;
+    let (input, _) = line_ending(input)?;
+    let (input, numbers) = many1(preceded(space1, digit1))(input)?;
     let (input, _) = multispace1(input)?;
-    let (input, moves) = separated_list1(newline, move_crate)(input)?;
+    let (input, moves) = separated_list1(line_ending, move_crate)(input)?;
 
-    let mut crates_vertical: Vec<Vec<Option<&str>>> = vec![];
-    for _ in 0..=crates_horizontal.len() {
-        crates_vertical.push(vec![]);
-    }
+    let width = crates_horizontal.iter().map(Vec::len).max().unwrap_or(0);
+    let mut crates_vertical: Vec<Vec<Option<&str>>> = vec![vec![]; width.max(numbers.len())];
     for vec in crates_horizontal.iter().rev() {
         for (i, c) in vec.iter().enumerate() {
-            crates_vertical[i].push(c.clone());
+            crates_vertical[i].push(*c);
         }
     }
     let final_crates: Vec<Vec<&str>> = crates_vertical
@@ -74,71 +67,79 @@ fn crates(input: &str) -> IResult<&str, (Vec<Vec<&str>>, Vec<Move>)> {
     Ok((input, (final_crates, moves)))
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_, (mut crate_stacks, moves)) = crates(input).unwrap();
-    for Move { number, from, to } in moves.iter() {
-        let len = crate_stacks[*from as usize].len();
-        let drained = crate_stacks[*from as usize]
-            .drain((len - *number as usize)..)
-            .rev()
-            .collect::<Vec<&str>>();
-        for c in drained.iter() {
-            crate_stacks[*to as usize].push(c);
+/// The crate on top of each stack once every move is done, with the crane
+/// lifting the crates of a move one at a time or all at once.
+fn top_crates(stacks: &[Vec<&str>], moves: &[Move], all_at_once: bool) -> Result<String, Error> {
+    let mut stacks = stacks.to_vec();
+    let count = stacks.len();
+    for (i, Move { number, from, to }) in moves.iter().enumerate() {
+        let stack = |n: u32| {
+            (n as usize)
+                .checked_sub(1)
+                .filter(|&index| index < count)
+                .ok_or_else(|| Error::invalid(format!("move {}: there is no stack {n}", i + 1)))
+        };
+        let (from, to) = (stack(*from)?, stack(*to)?);
+        let len = stacks[from].len();
+        let rest = len.checked_sub(*number as usize).ok_or_else(|| {
+            Error::invalid(format!(
+                "move {}: stack {} has fewer than {number} crates",
+                i + 1,
+                from + 1
+            ))
+        })?;
+        let mut lifted = stacks[from].drain(rest..).collect::<Vec<&str>>();
+        if !all_at_once {
+            lifted.reverse();
         }
+        stacks[to].extend(lifted);
     }
-    let result: String = crate_stacks
+    Ok(stacks
         .iter()
-        .map(|v| match v.last() {
-            Some(c) => c,
-            None => "",
-        })
-        .collect();
-
-    result.to_string()
+        .map(|stack| stack.last().copied().unwrap_or(""))
+        .collect())
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (_, (mut crate_stacks, moves)) = crates(input).unwrap();
-    for Move { number, from, to } in moves.iter() {
-        let len = crate_stacks[*from as usize].len();
-        let drained = crate_stacks[*from as usize]
-            .drain((len - *number as usize)..)
-            .collect::<Vec<&str>>();
-        for c in drained.iter() {
-            crate_stacks[*to as usize].push(c);
-        }
-    }
-    let result: String = crate_stacks
-        .iter()
-        .map(|v| match v.last() {
-            Some(c) => c,
-            None => "",
-        })
-        .collect();
-    result
+aoc_utils::solution! {
+    Day05: 2022 day 5;
+    type Parsed<'a> = (Vec<Vec<&'a str>>, Vec<Move>);
+    parse: |input| parse_all(crates, input);
+    part1: |(stacks, moves)| top_crates(stacks, moves, false);
+    part2: |(stacks, moves)| top_crates(stacks, moves, true);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
 
-    const EXAMPLE_INPUT: &str = "    [D]    
-[N] [C]    
-[Z] [M] [P]
- 1   2   3 
-
-move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2";
+    fn example() -> &'static str {
+        inputs::example(2022, 5, 1).unwrap()
+    }
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "CMZ");
+        assert_eq!(process_part1(example()), "CMZ");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "MCD");
+        assert_eq!(process_part2(example()), "MCD");
+    }
+
+    #[test]
+    fn rejects_impossible_moves() {
+        let stacks = "[A]    \n[B] [C]\n 1   2 \n\n";
+        for (moves, message) in [
+            ("move 1 from 0 to 1", "move 1: there is no stack 0"),
+            ("move 1 from 1 to 3", "move 1: there is no stack 3"),
+            (
+                "move 2 from 2 to 1",
+                "move 1: stack 2 has fewer than 2 crates",
+            ),
+        ] {
+            let error = try_process_part1(&format!("{stacks}{moves}")).unwrap_err();
+            assert!(error.to_string().contains(message), "{error}");
+        }
     }
 }
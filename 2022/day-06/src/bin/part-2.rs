@@ -1,7 +1,7 @@
-use day_06::process_part2;
-use std::fs;
+use aoc_utils::inputs;
+use day_2022_06::process_part2;
 
 fn main() {
-    let file = fs::read_to_string("./input.txt").unwrap();
-    println!("{}", process_part2(file.as_str()));
+    let input = inputs::input(2022, 6).unwrap_or_else(|err| panic!("{err}"));
+    println!("{}", process_part2(input));
 }
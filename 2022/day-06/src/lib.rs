@@ -1,71 +1,71 @@
 use std::collections::{HashSet, LinkedList};
 
-pub fn process_part1(input: &str) -> u32 {
+use aoc_utils::Error;
+
+/// Where the first four characters that are all different end.
+fn start_of_packet(datastream: &[char]) -> Result<usize, Error> {
     let mut chars_in_window = LinkedList::new();
     let mut window_set = HashSet::new();
     let mut start_marker = None;
-    for (i, c) in input.chars().enumerate() {
-        while window_set.contains(&c) {
+    for (i, c) in datastream.iter().enumerate() {
+        while window_set.contains(c) {
             window_set.remove(&chars_in_window.pop_front().unwrap());
         }
         if chars_in_window.len() == 4 {
             window_set.remove(&chars_in_window.pop_front().unwrap());
         }
-        chars_in_window.push_back(c);
-        window_set.insert(c);
+        chars_in_window.push_back(*c);
+        window_set.insert(*c);
         if window_set.len() == 4 {
-            start_marker = Some(i as u32 + 1);
+            start_marker = Some(i + 1);
             break;
         }
     }
-    start_marker.expect("could not find start marker")
+    start_marker.ok_or_else(|| Error::invalid("could not find start marker"))
 }
 
-pub fn process_part2(input: &str) -> u32 {
+/// Where the first fourteen characters that are all different end.
+fn start_of_message(datastream: &[char]) -> Result<usize, Error> {
     let window_size = 14;
-    let chars = input.chars().collect::<Vec<char>>();
-    let sequence = chars
+    let sequence = datastream
         .windows(window_size)
         .enumerate()
         .find(|(_i, window)| {
             let set = window.iter().collect::<HashSet<&char>>();
             window.len() == set.len()
         })
-        .expect("could not find start marker");
-    sequence.0 as u32 + window_size as u32
+        .ok_or_else(|| Error::invalid("could not find start marker"))?;
+    Ok(sequence.0 + window_size)
+}
+
+aoc_utils::solution! {
+    Day06: 2022 day 6;
+    type Parsed<'a> = Vec<char>;
+    parse: |input| Ok(input.trim_end().chars().collect());
+    part1: |datastream| start_of_packet(datastream);
+    part2: |datastream| start_of_message(datastream);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
 
-    const EXAMPLE_INPUTS_P1: [(&'static str, u32); 5] = [
-        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7),
-        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5),
-        ("nppdvjthqldpwncqszvftbrmjlhg", 6),
-        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10),
-        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11),
-    ];
-
-    const EXAMPLE_INPUTS_P2: [(&'static str, u32); 5] = [
-        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 19),
-        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 23),
-        ("nppdvjthqldpwncqszvftbrmjlhg", 23),
-        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 29),
-        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 26),
-    ];
+    fn example(n: u8) -> &'static str {
+        inputs::example(2022, 6, n).unwrap()
+    }
 
     #[test]
     fn part1() {
-        for (input, idx) in EXAMPLE_INPUTS_P1.iter() {
-            assert_eq!(process_part1(input), *idx);
+        for (n, idx) in [(1, "7"), (2, "5"), (3, "6"), (4, "10"), (5, "11")] {
+            assert_eq!(process_part1(example(n)), idx);
         }
     }
 
     #[test]
     fn part2() {
-        for (input, idx) in EXAMPLE_INPUTS_P2.iter() {
-            assert_eq!(process_part2(input), *idx);
+        for (n, idx) in [(1, "19"), (2, "23"), (3, "23"), (4, "29"), (5, "26")] {
+            assert_eq!(process_part2(example(n)), idx);
         }
     }
 }
@@ -1,8 +1,8 @@
-#![feature(iter_intersperse)]
+use aoc_utils::{parse::parse_all, Error};
 use nom::{
     branch::alt,
     bytes::complete::{is_a, tag},
-    character::complete::{alpha1, newline},
+    character::complete::{alpha1, line_ending},
     multi::separated_list1,
     sequence::separated_pair,
     IResult,
@@ -12,7 +12,7 @@ use std::collections::BTreeMap;
 #[derive(Debug)]
 enum Operation<'a> {
     Cd(Cd<'a>),
-    Ls(Vec<Files<'a>>),
+    Ls(Vec<Files>),
 }
 
 #[derive(Debug)]
@@ -23,9 +23,9 @@ enum Cd<'a> {
 }
 
 #[derive(Debug)]
-enum Files<'a> {
+enum Files {
     File(u32),
-    Dir(&'a str),
+    Dir,
 }
 
 fn file(input: &str) -> IResult<&str, Files> {
@@ -39,18 +39,18 @@ fn file(input: &str) -> IResult<&str, Files> {
 
 fn directory(input: &str) -> IResult<&str, Files> {
     let (input, _) = tag("dir ")(input)?;
-    let (input, name) = alpha1(input)?;
-    Ok((input, Files::Dir(name)))
+    let (input, _) = alpha1(input)?;
+    Ok((input, Files::Dir))
 }
 
-fn ls(input: &str) -> IResult<&str, Operation> {
+fn ls(input: &str) -> IResult<&str, Operation<'_>> {
     let (input, _) = tag("$ ls")(input)?;
-    let (input, _) = newline(input)?;
-    let (input, files) = separated_list1(newline, alt((file, directory)))(input)?;
+    let (input, _) = line_ending(input)?;
+    let (input, files) = separated_list1(line_ending, alt((file, directory)))(input)?;
     Ok((input, Operation::Ls(files)))
 }
 
-fn cd(input: &str) -> IResult<&str, Operation> {
+fn cd(input: &str) -> IResult<&str, Operation<'_>> {
     let (input, _) = tag("$ cd ")(input)?;
     let (input, dir) = alt((tag(".."), alpha1, tag("/")))(input)?;
     let op = match dir {
@@ -61,14 +61,14 @@ fn cd(input: &str) -> IResult<&str, Operation> {
     Ok((input, op))
 }
 
-fn commands(input: &str) -> IResult<&str, Vec<Operation>> {
-    let (input, cmds) = separated_list1(newline, alt((ls, cd)))(input)?;
+fn commands(input: &str) -> IResult<&str, Vec<Operation<'_>>> {
+    let (input, cmds) = separated_list1(line_ending, alt((ls, cd)))(input)?;
     Ok((input, cmds))
 }
 
 fn calculate_sizes<'a>(
     (mut context, mut sizes): (Vec<&'a str>, BTreeMap<Vec<&'a str>, u32>),
-    command: &'a Operation,
+    command: &Operation<'a>,
 ) -> (Vec<&'a str>, BTreeMap<Vec<&'a str>, u32>) {
     match command {
         Operation::Cd(Cd::Root) => {
@@ -103,72 +103,66 @@ fn calculate_sizes<'a>(
     (context, sizes)
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_, cmds) = commands(input).unwrap();
+/// The total size of the files in each directory, by path.
+fn directory_sizes(input: &str) -> Result<BTreeMap<Vec<&str>, u32>, Error> {
+    let cmds = parse_all(commands, input)?;
     let (_, sizes) = cmds.iter().fold((vec![], BTreeMap::new()), calculate_sizes);
+    Ok(sizes)
+}
 
+/// The total size of the directories smaller than 100000.
+fn total_of_small_directories(sizes: &BTreeMap<Vec<&str>, u32>) -> u32 {
     sizes
         .iter()
         .filter(|(_, &size)| size < 100_000)
         .map(|(_, size)| size)
         .sum::<u32>()
-        .to_string()
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (_, cmds) = commands(input).unwrap();
-    let (_, sizes) = cmds.iter().fold((vec![], BTreeMap::new()), calculate_sizes);
-
+/// The size of the smallest directory that frees up enough space when deleted.
+fn directory_to_delete(sizes: &BTreeMap<Vec<&str>, u32>) -> Result<u32, Error> {
     let total_disk_size = 70_000_000;
     let required_space = 30_000_000;
-    let used_space = sizes.get(&vec![""]).unwrap();
+    let used_space = sizes
+        .get(&vec![""])
+        .ok_or_else(|| Error::invalid("the root directory is never listed"))?;
     let delete_at_least = required_space - (total_disk_size - used_space);
-
     let mut deletion_candidates = sizes
         .iter()
         .filter(|(_, &size)| size > delete_at_least)
         .map(|(_, size)| size)
         .collect::<Vec<&u32>>();
-
     deletion_candidates.sort();
-    deletion_candidates.first().unwrap().to_string()
+    deletion_candidates
+        .first()
+        .map(|size| **size)
+        .ok_or_else(|| Error::invalid("no directory is large enough to delete"))
+}
+
+aoc_utils::solution! {
+    Day07: 2022 day 7;
+    type Parsed<'a> = BTreeMap<Vec<&'a str>, u32>;
+    parse: |input| directory_sizes(input);
+    part1: |sizes| Ok(total_of_small_directories(sizes));
+    part2: |sizes| directory_to_delete(sizes);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
 
-    const EXAMPLE_INPUT: &str = "$ cd /
-$ ls
-dir a
-14848514 b.txt
-8504156 c.dat
-dir d
-$ cd a
-$ ls
-dir e
-29116 f
-2557 g
-62596 h.lst
-$ cd e
-$ ls
-584 i
-$ cd ..
-$ cd ..
-$ cd d
-$ ls
-4060174 j
-8033020 d.log
-5626152 d.ext
-7214296 k";
+    fn example() -> &'static str {
+        inputs::example(2022, 7, 1).unwrap()
+    }
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "95437");
+        assert_eq!(process_part1(example()), "95437");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "24933642");
+        assert_eq!(process_part2(example()), "24933642");
     }
 }
@@ -1,14 +1,27 @@
 use std::collections::{HashMap, HashSet};
 
-pub fn process_part1(input: &str) -> String {
-    let len = input.find('\n').unwrap();
-    let mut grid = HashMap::new();
-    input.lines().enumerate().for_each(|(y, row)| {
-        row.chars().enumerate().for_each(|(x, height)| {
-            grid.insert((x, y), height.to_digit(10));
-        });
-    });
+use aoc_utils::{
+    parse::{digit_grid, parse_all},
+    Error,
+};
+
+type Heights = HashMap<(usize, usize), u32>;
+
+/// The side length of the square forest and the height of every tree in it.
+fn parse_trees(input: &str) -> Result<(usize, Heights), Error> {
+    let trees = parse_all(digit_grid, input)?;
+    if trees.width() != trees.height() {
+        return Err(Error::invalid("the forest isn't square"));
+    }
+    let heights = trees
+        .iter()
+        .map(|((x, y), height)| ((x as usize, y as usize), *height))
+        .collect();
+    Ok((trees.width(), heights))
+}
 
+/// How many trees can be seen from outside the forest.
+fn visible_trees(len: usize, grid: &Heights) -> usize {
     let mut visible = HashSet::new();
 
     for y in 0..len {
@@ -59,18 +72,11 @@ pub fn process_part1(input: &str) -> String {
             }
         }
     }
-    visible.len().to_string()
+    visible.len()
 }
 
-pub fn process_part2(input: &str) -> String {
-    let len = input.find('\n').unwrap();
-    let mut grid = HashMap::new();
-    input.lines().enumerate().for_each(|(y, row)| {
-        row.chars().enumerate().for_each(|(x, height)| {
-            grid.insert((x, y), height.to_digit(10));
-        });
-    });
-
+/// The highest scenic score of any tree.
+fn best_scenic_score(len: usize, grid: &Heights) -> u32 {
     let mut scores = HashMap::new();
 
     for y_h in 1..len - 1 {
@@ -125,23 +131,31 @@ pub fn process_part2(input: &str) -> String {
     }
 
     let max_score = scores.iter().max_by(|a, b| a.1.cmp(b.1)).unwrap();
-    max_score.1.to_string()
+    *max_score.1
+}
+
+aoc_utils::solution! {
+    Day08: 2022 day 8;
+    type Parsed<'a> = (usize, Heights);
+    parse: |input| parse_trees(input);
+    part1: |(len, grid)| Ok(visible_trees(*len, grid));
+    part2: |(len, grid)| Ok(best_scenic_score(*len, grid));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
+
+    fn example() -> &'static str {
+        inputs::example(2022, 8, 1).unwrap()
+    }
 
-    const EXAMPLE_INPUT: &str = "30373
-25512
-65332
-33549
-35390";
     const EXAMPLE_INPUTS: [(&str, &str, &str); 0] = [];
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "21");
+        assert_eq!(process_part1(example()), "21");
     }
 
     #[test]
@@ -153,7 +167,7 @@ mod tests {
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "8");
+        assert_eq!(process_part2(example()), "8");
     }
 
     #[test]
@@ -1,32 +1,14 @@
 use std::collections::HashSet;
 
+use aoc_utils::{
+    parse::{integer, lines, parse_all},
+    Direction,
+};
 use nom::{
-    bytes::complete::tag,
-    character::complete::{alpha1, digit1, newline},
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult, Parser,
+    bytes::complete::tag, character::complete::anychar, combinator::map_opt,
+    sequence::separated_pair, IResult,
 };
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn apply(&self, (x, y): (i32, i32)) -> (i32, i32) {
-        match self {
-            Direction::Up => (x, y - 1),
-            Direction::Down => (x, y + 1),
-            Direction::Left => (x - 1, y),
-            Direction::Right => (x + 1, y),
-        }
-    }
-}
-
 fn move_tail((hx, hy): (i32, i32), (tx, ty): (i32, i32)) -> (i32, i32) {
     let (dx, dy) = ((hx - tx), (hy - ty));
     if dx.abs() > 1 || dy.abs() > 1 {
@@ -37,25 +19,16 @@ fn move_tail((hx, hy): (i32, i32), (tx, ty): (i32, i32)) -> (i32, i32) {
 }
 
 fn parse_input(input: &str) -> IResult<&str, Vec<(Direction, u32)>> {
-    let (input, moves) = separated_list1(
-        newline,
-        separated_pair(
-            alpha1.map(|a: &str| match a {
-                "U" => Direction::Up,
-                "D" => Direction::Down,
-                "L" => Direction::Left,
-                "R" => Direction::Right,
-                _ => panic!("unrecognized direction"),
-            }),
-            tag(" "),
-            digit1.map(|d: &str| d.parse::<u32>().unwrap()),
-        ),
-    )(input)?;
+    let (input, moves) = lines(separated_pair(
+        map_opt(anychar, Direction::from_char),
+        tag(" "),
+        integer,
+    ))(input)?;
     Ok((input, moves))
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_, head_moves) = parse_input(input).unwrap();
+/// How many positions the tail of a two knot rope visits.
+fn tail_positions(head_moves: &[(Direction, u32)]) -> usize {
     let mut head_position = (0, 0);
     let mut unique_tail_positions = HashSet::from([(0, 0)]);
     let mut tail_position = (0, 0);
@@ -63,56 +36,53 @@ pub fn process_part1(input: &str) -> String {
         .iter()
         .flat_map(|(dir, steps)| vec![*dir; *steps as usize])
     {
-        head_position = dir.apply(head_position);
+        head_position = dir.step(head_position);
         tail_position = move_tail(head_position, tail_position);
         unique_tail_positions.insert(tail_position);
     }
-    unique_tail_positions.len().to_string()
+    unique_tail_positions.len()
 }
 
-pub fn process_part2(input: &str) -> String {
+/// How many positions the last knot of a ten knot rope visits.
+fn last_knot_positions(head_moves: &[(Direction, u32)]) -> usize {
     const ROPE_LENGTH: usize = 10;
-    let (_, head_moves) = parse_input(input).unwrap();
     let mut unique_last_knot_positions = HashSet::from([(0, 0)]);
     let mut rope = [(0, 0); ROPE_LENGTH];
     for dir in head_moves
         .iter()
         .flat_map(|(dir, steps)| vec![*dir; *steps as usize])
     {
-        rope[0] = dir.apply(rope[0]);
+        rope[0] = dir.step(rope[0]);
         for i in 1..ROPE_LENGTH {
             rope[i] = move_tail(rope[i - 1], rope[i])
         }
         unique_last_knot_positions.insert(rope[ROPE_LENGTH - 1]);
     }
-    unique_last_knot_positions.len().to_string()
+    unique_last_knot_positions.len()
+}
+
+aoc_utils::solution! {
+    Day09: 2022 day 9;
+    type Parsed<'a> = Vec<(Direction, u32)>;
+    parse: |input| parse_all(parse_input, input);
+    part1: |head_moves| Ok(tail_positions(head_moves));
+    part2: |head_moves| Ok(last_knot_positions(head_moves));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
+
+    fn example(n: u8) -> &'static str {
+        inputs::example(2022, 9, n).unwrap()
+    }
 
-    const EXAMPLE_INPUT: &str = "R 4
-U 4
-L 3
-D 1
-R 4
-D 1
-L 5
-R 2";
-    const LARGER_EXAMPLE_INPUT: &str = "R 5
-U 8
-L 8
-D 3
-R 17
-D 10
-L 25
-U 20";
     const EXAMPLE_INPUTS: [(&str, &str, &str); 0] = [];
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "13");
+        assert_eq!(process_part1(example(1)), "13");
     }
 
     #[test]
@@ -124,12 +94,12 @@ U 20";
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "1");
+        assert_eq!(process_part2(example(1)), "1");
     }
 
     #[test]
     fn part2_2() {
-        assert_eq!(process_part2(LARGER_EXAMPLE_INPUT), "36");
+        assert_eq!(process_part2(example(2)), "36");
     }
 
     #[test]
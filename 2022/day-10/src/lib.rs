@@ -1,17 +1,16 @@
 use std::collections::HashSet;
 
+use aoc_utils::{
+    ocr,
+    parse::{lines, parse_all},
+};
 use itertools::Itertools;
 use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{self, newline},
-    multi::separated_list1,
-    sequence::preceded,
-    IResult, Parser,
+    branch::alt, bytes::complete::tag, character::complete, sequence::preceded, IResult, Parser,
 };
 
 #[derive(Debug)]
-enum Instruction {
+pub enum Instruction {
     Addx(i32),
     Noop,
 }
@@ -28,19 +27,16 @@ impl Instruction {
 use Instruction::*;
 
 fn parse_instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
-    let (input, instructions) = separated_list1(
-        newline,
-        alt((
-            tag("noop").map(|_| Noop),
-            preceded(tag("addx "), complete::i32).map(|v| Addx(v)),
-        )),
-    )(input)?;
-    return Ok((input, instructions));
+    let (input, instructions) = lines(alt((
+        tag("noop").map(|_| Noop),
+        preceded(tag("addx "), complete::i32).map(Addx),
+    )))(input)?;
+    Ok((input, instructions))
 }
 
-pub fn process_part1(input: &str) -> String {
+/// The sum of the signal strengths during the 20th cycle and every 40 after.
+fn signal_strength(ins: &[Instruction]) -> i32 {
     let measure_signal_strength_at: HashSet<i32> = HashSet::from([20, 60, 100, 140, 180, 220]);
-    let (_, ins) = parse_instructions(input).unwrap();
 
     let mut instructions = ins.iter();
     let mut x: i32 = 1;
@@ -72,12 +68,11 @@ pub fn process_part1(input: &str) -> String {
         }
     }
 
-    signal_strengths.iter().sum::<i32>().to_string()
+    signal_strengths.iter().sum::<i32>()
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (_, ins) = parse_instructions(input).unwrap();
-
+/// The CRT after running the program, as six rows of `#` and `.`.
+pub fn draw_crt(ins: &[Instruction]) -> String {
     let mut crt: [char; 40 * 6] = ['.'; 40 * 6];
 
     let mut instructions = ins.iter();
@@ -111,166 +106,32 @@ pub fn process_part2(input: &str) -> String {
     }
 
     crt.chunks(40)
-        .into_iter()
         .map(|row| row.iter().collect::<String>())
         .join("\n")
 }
 
+aoc_utils::solution! {
+    Day10: 2022 day 10;
+    type Parsed<'a> = Vec<Instruction>;
+    parse: |input| parse_all(parse_instructions, input);
+    part1: |instructions| Ok(signal_strength(instructions));
+    part2: |instructions| ocr::recognize(&draw_crt(instructions));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
+
+    fn example() -> &'static str {
+        inputs::example(2022, 10, 1).unwrap()
+    }
 
-    const EXAMPLE_INPUT: &str = "addx 15
-addx -11
-addx 6
-addx -3
-addx 5
-addx -1
-addx -8
-addx 13
-addx 4
-noop
-addx -1
-addx 5
-addx -1
-addx 5
-addx -1
-addx 5
-addx -1
-addx 5
-addx -1
-addx -35
-addx 1
-addx 24
-addx -19
-addx 1
-addx 16
-addx -11
-noop
-noop
-addx 21
-addx -15
-noop
-noop
-addx -3
-addx 9
-addx 1
-addx -3
-addx 8
-addx 1
-addx 5
-noop
-noop
-noop
-noop
-noop
-addx -36
-noop
-addx 1
-addx 7
-noop
-noop
-noop
-addx 2
-addx 6
-noop
-noop
-noop
-noop
-noop
-addx 1
-noop
-noop
-addx 7
-addx 1
-noop
-addx -13
-addx 13
-addx 7
-noop
-addx 1
-addx -33
-noop
-noop
-noop
-addx 2
-noop
-noop
-noop
-addx 8
-noop
-addx -1
-addx 2
-addx 1
-noop
-addx 17
-addx -9
-addx 1
-addx 1
-addx -3
-addx 11
-noop
-noop
-addx 1
-noop
-addx 1
-noop
-noop
-addx -13
-addx -19
-addx 1
-addx 3
-addx 26
-addx -30
-addx 12
-addx -1
-addx 3
-addx 1
-noop
-noop
-noop
-addx -9
-addx 18
-addx 1
-addx 2
-noop
-noop
-addx 9
-noop
-noop
-noop
-addx -1
-addx 2
-addx -37
-addx 1
-addx 3
-noop
-addx 15
-addx -21
-addx 22
-addx -6
-addx 1
-noop
-addx 2
-addx 1
-noop
-addx -10
-noop
-noop
-addx 20
-addx 1
-addx 2
-addx 2
-addx -6
-addx -11
-noop
-noop
-noop";
     const EXAMPLE_INPUTS: [(&str, &str, &str); 0] = [];
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "13140");
+        assert_eq!(process_part1(example()), "13140");
     }
 
     #[test]
@@ -282,9 +143,8 @@ noop";
 
     #[test]
     fn part2() {
-        print!("{}", process_part2(EXAMPLE_INPUT));
         assert_eq!(
-            process_part2(EXAMPLE_INPUT),
+            draw_crt(&parse_all(parse_instructions, example()).unwrap()),
             "##..##..##..##..##..##..##..##..##..##..
 ###...###...###...###...###...###...###.
 ####....####....####....####....####....
@@ -292,6 +152,11 @@ noop";
 ######......######......######......####
 #######.......#######.......#######....."
         );
+        // The example draws stripes rather than letters.
+        let err = try_process_part2(example()).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("invalid input: unknown letter at column 1:"));
     }
 
     #[test]
@@ -1,5 +1,6 @@
 use std::collections::VecDeque;
 
+use aoc_utils::parse::{blocks, parse_all};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -9,7 +10,7 @@ use nom::{
     IResult, Parser,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Value {
     Old,
     Num(u64),
@@ -24,7 +25,7 @@ impl Value {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Operation {
     Mul((Value, Value)),
     Add((Value, Value)),
@@ -39,8 +40,8 @@ impl Operation {
     }
 }
 
-#[derive(Debug)]
-struct Monkey {
+#[derive(Debug, Clone)]
+pub struct Monkey {
     items: VecDeque<u64>,
     operation: Operation,
     test: Test,
@@ -58,7 +59,7 @@ impl Monkey {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Test {
     divisible: u64,
     true_recipient: u64,
@@ -67,7 +68,7 @@ struct Test {
 
 impl Test {
     fn apply(&self, item: u64) -> u64 {
-        if item % self.divisible == 0 {
+        if item.is_multiple_of(self.divisible) {
             self.true_recipient
         } else {
             self.false_recipient
@@ -139,17 +140,19 @@ fn monkey(input: &str) -> IResult<&str, Monkey> {
 }
 
 fn monkeys(input: &str) -> IResult<&str, Vec<Monkey>> {
-    separated_list1(tag("\n\n"), monkey)(input)
+    blocks(monkey)(input)
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_, mut monkeys) = monkeys(input).unwrap();
+/// The product of the two highest inspection counts after `rounds`, with
+/// `relief` applied to each item's worry level after it's inspected.
+fn monkey_business(monkeys: &[Monkey], rounds: usize, relief: impl Fn(u64) -> u64) -> u64 {
+    let mut monkeys = monkeys.to_vec();
 
-    for _round in 0..20 {
+    for _round in 0..rounds {
         for monkey_index in 0..monkeys.len() {
             for _ in 0..monkeys[monkey_index].items.len() {
                 let monkey = monkeys.get_mut(monkey_index).unwrap();
-                let item = monkey.inspect() / 3;
+                let item = relief(monkey.inspect());
                 let monkey_to_send_to = monkey.test(item);
                 monkeys
                     .get_mut(monkey_to_send_to as usize)
@@ -166,78 +169,39 @@ pub fn process_part1(input: &str) -> String {
         .rev()
         .take(2)
         .map(|monkey| monkey.inspect_count)
-        .product::<u64>()
-        .to_string()
+        .product()
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (_, mut monkeys) = monkeys(input).unwrap();
-    let least_common_multiple = monkeys
-        .iter()
-        .map(|monkey| monkey.test.divisible)
-        .product::<u64>();
-
-    for _round in 0..10_000 {
-        for monkey_index in 0..monkeys.len() {
-            for _ in 0..monkeys[monkey_index].items.len() {
-                let monkey = monkeys.get_mut(monkey_index).unwrap();
-                let item = monkey.inspect() % least_common_multiple;
-                let monkey_to_send_to = monkey.test(item);
-                monkeys
-                    .get_mut(monkey_to_send_to as usize)
-                    .unwrap()
-                    .items
-                    .push_back(item);
-            }
-        }
-    }
+/// Keeps worry levels manageable by working modulo every monkey's divisor.
+fn worried_monkey_business(monkeys: &[Monkey]) -> u64 {
+    let least_common_multiple =
+        aoc_utils::number_theory::lcm_all(monkeys.iter().map(|monkey| monkey.test.divisible))
+            .unwrap();
+    monkey_business(monkeys, 10_000, |item| item % least_common_multiple)
+}
 
-    monkeys.sort_by_key(|monkey| monkey.inspect_count);
-    monkeys
-        .iter()
-        .rev()
-        .take(2)
-        .map(|monkey| monkey.inspect_count)
-        .product::<u64>()
-        .to_string()
+aoc_utils::solution! {
+    Day11: 2022 day 11;
+    type Parsed<'a> = Vec<Monkey>;
+    parse: |input| parse_all(monkeys, input);
+    part1: |monkeys| Ok(monkey_business(monkeys, 20, |item| item / 3));
+    part2: |monkeys| Ok(worried_monkey_business(monkeys));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
 
-    const EXAMPLE_INPUT: &str = "Monkey 0:
-  Starting items: 79, 98
-  Operation: new = old * 19
-  Test: divisible by 23
-    If true: throw to monkey 2
-    If false: throw to monkey 3
-
-Monkey 1:
-  Starting items: 54, 65, 75, 74
-  Operation: new = old + 6
-  Test: divisible by 19
-    If true: throw to monkey 2
-    If false: throw to monkey 0
-
-Monkey 2:
-  Starting items: 79, 60, 97
-  Operation: new = old * old
-  Test: divisible by 13
-    If true: throw to monkey 1
-    If false: throw to monkey 3
+    fn example() -> &'static str {
+        inputs::example(2022, 11, 1).unwrap()
+    }
 
-Monkey 3:
-  Starting items: 74
-  Operation: new = old + 3
-  Test: divisible by 17
-    If true: throw to monkey 0
-    If false: throw to monkey 1";
     const EXAMPLE_INPUTS: [(&str, &str, &str); 0] = [];
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "10605");
+        assert_eq!(process_part1(example()), "10605");
     }
 
     #[test]
@@ -249,7 +213,7 @@ Monkey 3:
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "2713310158");
+        assert_eq!(process_part2(example()), "2713310158");
     }
 
     #[test]
@@ -1,132 +1,85 @@
-use std::collections::{HashMap, HashSet, VecDeque};
-
-pub fn process_part1(input: &str) -> String {
-    let grid_height = input.lines().count() as i32;
-    let grid_width = input.find('\n').unwrap() as i32;
-
-    let mut start = None;
-    let mut end = None;
-    let mut grid = HashMap::with_capacity((grid_height * grid_width) as usize);
-
-    for (y, line) in input.lines().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            let (x, y) = (x as i32, y as i32);
-            grid.insert(
-                (x, y),
-                match c {
-                    'S' => {
-                        start = Some((x, y));
-                        0
-                    }
-                    'E' => {
-                        end = Some((x, y));
-                        'z' as u32 - 'a' as u32
-                    }
-                    c => c as u32 - 'a' as u32,
-                },
-            );
-        }
-    }
-
-    let start = start.expect("couldn't find start");
-    let end = end.expect("couldn't find end");
-
-    let mut min_steps_to_goal = None;
-    let mut seen = HashSet::from([(0, 0)]);
-    let mut next_positions = VecDeque::from([(start, 0)]);
-
-    while let Some(((cx, cy), steps)) = next_positions.pop_front() {
-        if (cx, cy) == end {
-            min_steps_to_goal = Some(steps);
-            break;
-        }
-        let current_height = *grid.get(&(cx, cy)).unwrap();
-
-        [(cx - 1, cy), (cx + 1, cy), (cx, cy - 1), (cx, cy + 1)]
-            .iter()
-            .filter(|&&(x, y)| {
-                grid.get(&(x, y))
-                    .map(|height| *height as i32 - current_height as i32 <= 1)
-                    .unwrap_or(false)
-            })
-            .for_each(|neighbor| {
-                if seen.insert(*neighbor) {
-                    next_positions.push_back((*neighbor, steps + 1));
-                }
-            });
-    }
-
-    min_steps_to_goal.unwrap().to_string()
+use aoc_utils::{
+    parse::{grid, parse_all},
+    Error, Grid,
+};
+use nom::character::complete::satisfy;
+
+fn parse_heightmap(input: &str) -> Result<(Grid<char>, Grid<u32>), Error> {
+    let map = parse_all(
+        grid(satisfy(|c| c.is_ascii_lowercase() || c == 'S' || c == 'E')),
+        input,
+    )?;
+    let heights = map.map(|c| match c {
+        'S' => 0,
+        'E' => 'z' as u32 - 'a' as u32,
+        c => *c as u32 - 'a' as u32,
+    });
+    Ok((map, heights))
 }
 
-pub fn process_part2(input: &str) -> String {
-    let grid_height = input.lines().count() as i32;
-    let grid_width = input.find('\n').unwrap() as i32;
+fn find(map: &Grid<char>, square: char) -> Result<(i32, i32), Error> {
+    map.find(|c| *c == square)
+        .ok_or_else(|| Error::invalid(format!("the heightmap has no '{square}'")))
+}
 
-    let mut starts = vec![];
-    let mut end = None;
-    let mut grid = HashMap::with_capacity((grid_height * grid_width) as usize);
+fn steps_to_end(
+    heights: &Grid<u32>,
+    starts: impl Iterator<Item = (i32, i32)>,
+    end: (i32, i32),
+) -> Option<usize> {
+    aoc_utils::tracked_bfs(
+        starts,
+        |p| *p,
+        |p| {
+            let current_height = heights[p];
+            Some(
+                heights
+                    .neighbors4(p)
+                    .filter(|neighbor| heights[*neighbor] <= current_height + 1)
+                    .collect(),
+            )
+        },
+        |p| *p == end,
+    )
+    .depth(&end)
+}
 
-    for (y, line) in input.lines().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            let (x, y) = (x as i32, y as i32);
-            grid.insert(
-                (x, y),
-                match c {
-                    'S' | 'a' => {
-                        starts.push((x, y));
-                        0
-                    }
-                    'E' => {
-                        end = Some((x, y));
-                        'z' as u32 - 'a' as u32
-                    }
-                    c => c as u32 - 'a' as u32,
-                },
-            );
-        }
-    }
+fn fewest_steps_from_start((map, heights): &(Grid<char>, Grid<u32>)) -> Result<usize, Error> {
+    let start = find(map, 'S')?;
+    let end = find(map, 'E')?;
 
-    let end = end.expect("couldn't find end");
+    steps_to_end(heights, [start].into_iter(), end).ok_or_else(|| Error::invalid("no path found"))
+}
 
-    let mut next_positions = VecDeque::from_iter(starts.iter().map(|s| (*s, 0)));
-    let mut seen: HashSet<(i32, i32)> = HashSet::from_iter(starts.into_iter());
+fn fewest_steps_from_any_a((map, heights): &(Grid<char>, Grid<u32>)) -> Result<usize, Error> {
+    let end = find(map, 'E')?;
+    let starts = heights.iter().filter(|(_, h)| **h == 0).map(|(p, _)| p);
 
-    while let Some(((cx, cy), steps)) = next_positions.pop_front() {
-        if (cx, cy) == end {
-            return steps.to_string();
-        }
-        let current_height = *grid.get(&(cx, cy)).unwrap();
+    steps_to_end(heights, starts, end).ok_or_else(|| Error::invalid("no path found"))
+}
 
-        [(cx - 1, cy), (cx + 1, cy), (cx, cy - 1), (cx, cy + 1)]
-            .into_iter()
-            .filter(|p| {
-                grid.get(p)
-                    .map(|height| *height as i32 - current_height as i32 <= 1)
-                    .unwrap_or(false)
-                    && seen.insert(*p)
-            })
-            .for_each(|neighbor| {
-                next_positions.push_back((neighbor, steps + 1));
-            });
-    }
-    return "no path found".to_string();
+aoc_utils::solution! {
+    Day12: 2022 day 12;
+    type Parsed<'a> = (Grid<char>, Grid<u32>);
+    parse: |input| parse_heightmap(input);
+    part1: |heightmap| fewest_steps_from_start(heightmap);
+    part2: |heightmap| fewest_steps_from_any_a(heightmap);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
+
+    fn example() -> &'static str {
+        inputs::example(2022, 12, 1).unwrap()
+    }
 
-    const EXAMPLE_INPUT: &str = "Sabqponm
-abcryxxl
-accszExk
-acctuvwj
-abdefghi";
     const EXAMPLE_INPUTS: [(&str, &str, &str); 0] = [];
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "31");
+        assert_eq!(process_part1(example()), "31");
     }
 
     #[test]
@@ -138,7 +91,7 @@ abdefghi";
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "29");
+        assert_eq!(process_part2(example()), "29");
     }
 
     #[test]
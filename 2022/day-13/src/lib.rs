@@ -1,11 +1,12 @@
 use std::cmp::Ordering;
 
+use aoc_utils::parse::{blocks, parse_all};
 use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{self, newline},
-    multi::{separated_list0, separated_list1},
+    character::complete::{self, line_ending},
+    multi::separated_list0,
     sequence::{delimited, separated_pair},
     IResult, Parser,
 };
@@ -13,7 +14,7 @@ use nom::{
 use Packet::*;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-enum Packet {
+pub enum Packet {
     List(Vec<Packet>),
     Value(u32),
 }
@@ -43,30 +44,25 @@ fn parse_packet(input: &str) -> IResult<&str, Packet> {
 }
 
 fn parse_packet_pairs(input: &str) -> IResult<&str, Vec<(Packet, Packet)>> {
-    separated_list1(
-        tag("\n\n"),
-        separated_pair(parse_packet, newline, parse_packet),
-    )(input)
+    blocks(separated_pair(parse_packet, line_ending, parse_packet))(input)
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_, packet_pairs) = parse_packet_pairs(input).unwrap();
+fn ordered_pair_indices(packet_pairs: &[(Packet, Packet)]) -> usize {
     packet_pairs
         .iter()
         .enumerate()
         .filter_map(|(i, (left, right))| (left < right).then_some(i + 1))
-        .sum::<usize>()
-        .to_string()
+        .sum()
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (_, packet_pairs) = parse_packet_pairs(input).unwrap();
-
+/// The product of where the two divider packets end up once all are sorted.
+fn decoder_key(packet_pairs: &[(Packet, Packet)]) -> usize {
     let tracer1 = List(vec![List(vec![Value(2)])]);
     let tracer2 = List(vec![List(vec![Value(6)])]);
 
     let mut packets = packet_pairs
-        .into_iter()
+        .iter()
+        .cloned()
         .chain([(tracer1.clone(), tracer2.clone())])
         .flat_map(|(l, r)| [l, r])
         .sorted();
@@ -74,44 +70,33 @@ pub fn process_part2(input: &str) -> String {
     let tracer1_idx = packets.clone().position(|p| tracer1 == p).unwrap();
     let tracer2_idx = packets.position(|p| tracer2 == p).unwrap();
 
-    ((tracer1_idx + 1) * (tracer2_idx + 1)).to_string()
+    (tracer1_idx + 1) * (tracer2_idx + 1)
+}
+
+aoc_utils::solution! {
+    Day13: 2022 day 13;
+    type Parsed<'a> = Vec<(Packet, Packet)>;
+    parse: |input| parse_all(parse_packet_pairs, input);
+    part1: |packet_pairs| Ok(ordered_pair_indices(packet_pairs));
+    part2: |packet_pairs| Ok(decoder_key(packet_pairs));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
 
-    const EXAMPLE_INPUT: &str = "[1,1,3,1,1]
-[1,1,5,1,1]
-
-[[1],[2,3,4]]
-[[1],4]
-
-[9]
-[[8,7,6]]
-
-[[4,4],4,4]
-[[4,4],4,4,4]
-
-[7,7,7,7]
-[7,7,7]
-
-[]
-[3]
-
-[[[]]]
-[[]]
-
-[1,[2,[3,[4,[5,6,7]]]],8,9]
-[1,[2,[3,[4,[5,6,0]]]],8,9]";
+    fn example() -> &'static str {
+        inputs::example(2022, 13, 1).unwrap()
+    }
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "13");
+        assert_eq!(process_part1(example()), "13");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "140");
+        assert_eq!(process_part2(example()), "140");
     }
 }
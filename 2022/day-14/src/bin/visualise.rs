@@ -0,0 +1,54 @@
+//! Pours the sand of part 1, or with `2` as the first argument that of part 2,
+//! and writes it to `day-14.gif` or the path given as the second argument.
+
+use std::env;
+
+use aoc_utils::{
+    inputs,
+    render::{Animation, Image, Rgb},
+};
+use day_2022_14::{parse_cave, trace_sand};
+
+const AIR: Rgb = [20, 20, 40];
+const ROCK: Rgb = [120, 110, 100];
+const SAND: Rgb = [230, 190, 90];
+const FRAMES: usize = 200;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let floor = args.first().is_some_and(|part| part == "2");
+    let path = args.get(1).map_or("day-14.gif", String::as_str);
+
+    let input = inputs::input(2022, 14).unwrap_or_else(|err| panic!("{err}"));
+    let rocks = parse_cave(input).unwrap_or_else(|err| panic!("{err}"));
+    let sand = trace_sand(&rocks, floor);
+
+    // Every frame covers everything that will ever be drawn, so they line up.
+    let points = || {
+        rocks
+            .iter()
+            .chain(&sand)
+            .map(|&(x, y)| (x as usize, y as usize))
+    };
+    let min_x = points().map(|(x, _)| x).min().unwrap();
+    let max_x = points().map(|(x, _)| x).max().unwrap();
+    let max_y = points().map(|(_, y)| y).max().unwrap();
+
+    let mut image = Image::filled(max_x - min_x + 1, max_y + 1, AIR);
+    for &(x, y) in &rocks {
+        image.set((x as usize - min_x, y as usize), ROCK);
+    }
+
+    let mut animation = Animation::new(4);
+    let per_frame = sand.len().div_ceil(FRAMES).max(1);
+    for grains in sand.chunks(per_frame) {
+        for &(x, y) in grains {
+            image.set((x as usize - min_x, y as usize), SAND);
+        }
+        animation.push(image.scaled(3));
+    }
+    animation
+        .write_gif(path)
+        .unwrap_or_else(|err| panic!("cannot write {path}: {err}"));
+    println!("{} frames written to {path}", animation.len());
+}
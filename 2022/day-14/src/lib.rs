@@ -1,25 +1,23 @@
 use std::collections::HashSet;
 
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag, character::complete::newline, multi::separated_list1,
-    sequence::separated_pair, IResult,
+use aoc_utils::{
+    parse::{lines, parse_all},
+    Error,
 };
+use itertools::Itertools;
+use nom::{bytes::complete::tag, multi::separated_list1, sequence::separated_pair, IResult};
 
 const SAND_SPAWN_POS: (u32, u32) = (500, 0);
 
 fn parse_rocks(input: &str) -> IResult<&str, impl Iterator<Item = (u32, u32)>> {
-    let (input, path_pairs) = separated_list1(
-        newline,
-        separated_list1(
-            tag(" -> "),
-            separated_pair(
-                nom::character::complete::u32,
-                tag(","),
-                nom::character::complete::u32,
-            ),
+    let (input, path_pairs) = lines(separated_list1(
+        tag(" -> "),
+        separated_pair(
+            nom::character::complete::u32,
+            tag(","),
+            nom::character::complete::u32,
         ),
-    )(input)?;
+    ))(input)?;
 
     // Have to use into_iter or the values won't live long enough
     let rocks_iter = path_pairs.into_iter().flat_map(|path| {
@@ -33,90 +31,81 @@ fn parse_rocks(input: &str) -> IResult<&str, impl Iterator<Item = (u32, u32)>> {
     Ok((input, rocks_iter))
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_, rocks) = parse_rocks(input).unwrap();
+pub type Point = (u32, u32);
 
-    let mut grid: HashSet<(u32, u32)> = HashSet::from_iter(rocks);
-    let rock_count = grid.len();
+/// The rocks of the cave, of which there has to be at least one.
+pub fn parse_cave(input: &str) -> Result<HashSet<Point>, Error> {
+    let rocks: HashSet<Point> = parse_all(parse_rocks, input)?.collect();
+    if rocks.is_empty() {
+        return Err(Error::invalid("there are no rocks"));
+    }
+    Ok(rocks)
+}
 
-    let max_y = *grid.iter().map(|(_, y)| y).max().unwrap();
+/// Where every unit of sand comes to rest among the `rocks`, in the order it
+/// does. Without a `floor`, sand stops once it falls past the lowest rock;
+/// with one, once it piles up to where it is poured in.
+pub fn trace_sand(rocks: &HashSet<Point>, floor: bool) -> Vec<Point> {
+    let max_y = rocks.iter().map(|(_, y)| *y).max().unwrap_or(0);
+    // The floor is 2 below the lowest rock, so sand rests 1 above it.
+    let bottom = if floor { max_y + 1 } else { max_y };
 
+    let mut grid = rocks.clone();
+    let mut sand = vec![];
     loop {
         let (mut sand_x, mut sand_y) = SAND_SPAWN_POS;
 
-        while sand_y < max_y {
-            if grid.get(&(sand_x, sand_y + 1)).is_none() {
-                sand_y += 1;
-            } else if grid.get(&(sand_x - 1, sand_y + 1)).is_none() {
+        while sand_y < bottom {
+            if !grid.contains(&(sand_x, sand_y + 1)) {
                 sand_y += 1;
+            } else if !grid.contains(&(sand_x - 1, sand_y + 1)) {
                 sand_x -= 1;
-            } else if grid.get(&(sand_x + 1, sand_y + 1)).is_none() {
                 sand_y += 1;
+            } else if !grid.contains(&(sand_x + 1, sand_y + 1)) {
                 sand_x += 1;
+                sand_y += 1;
             } else {
-                grid.insert((sand_x, sand_y));
                 break;
             }
         }
 
         // fallen by all rocks - no more sand will be stable
-        if sand_y == max_y {
+        if sand_y == bottom && !floor {
             break;
         }
-    }
-
-    (grid.len() - rock_count).to_string()
-}
-
-pub fn process_part2(input: &str) -> String {
-    let (_, rocks) = parse_rocks(input).unwrap();
-
-    let mut grid: HashSet<(u32, u32)> = HashSet::from_iter(rocks);
-    let rock_count = grid.len();
-
-    // floor is 2 below highest y, so stop at 1 past
-    let max_y = 1 + grid.iter().map(|(_, y)| y).max().unwrap();
-
-    loop {
-        let (mut sand_x, mut sand_y) = SAND_SPAWN_POS;
 
-        while sand_y < max_y {
-            if grid.get(&(sand_x, sand_y + 1)).is_none() {
-                sand_y += 1;
-            } else if grid.get(&(sand_x - 1, sand_y + 1)).is_none() {
-                sand_x -= 1;
-                sand_y += 1;
-            } else if grid.get(&(sand_x + 1, sand_y + 1)).is_none() {
-                sand_x += 1;
-                sand_y += 1;
-            } else {
-                grid.insert((sand_x, sand_y));
-                break;
-            }
-        }
-
-        if sand_y == max_y {
-            // stop sand if it hits the floor
-            grid.insert((sand_x, sand_y));
-        } else if (sand_x, sand_y) == SAND_SPAWN_POS {
+        grid.insert((sand_x, sand_y));
+        sand.push((sand_x, sand_y));
+        if (sand_x, sand_y) == SAND_SPAWN_POS {
             break;
         }
     }
 
-    (grid.len() - rock_count).to_string()
+    sand
+}
+
+aoc_utils::solution! {
+    Day14: 2022 day 14;
+    type Parsed<'a> = HashSet<Point>;
+    parse: |input| parse_cave(input);
+    part1: |rocks| Ok(trace_sand(rocks, false).len());
+    part2: |rocks| Ok(trace_sand(rocks, true).len());
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
+
+    fn example() -> &'static str {
+        inputs::example(2022, 14, 1).unwrap()
+    }
 
-    const EXAMPLE_INPUT: &str = "498,4 -> 498,6 -> 496,6
-503,4 -> 502,4 -> 502,9 -> 494,9";
     const EXAMPLE_INPUTS: [(&str, &str, &str); 0] = [];
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "24");
+        assert_eq!(process_part1(example()), "24");
     }
 
     #[test]
@@ -128,7 +117,7 @@ mod tests {
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "93");
+        assert_eq!(process_part2(example()), "93");
     }
 
     #[test]
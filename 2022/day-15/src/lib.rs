@@ -1,17 +1,18 @@
-use std::{collections::HashMap, hash::Hash, ops::RangeInclusive};
+use std::{collections::HashMap, hash::Hash};
 
-use itertools::Itertools;
+use aoc_utils::{
+    parse::{lines, parse_all},
+    Error, RangeSet,
+};
 use nom::{
     bytes::complete::tag,
-    character::complete::newline,
-    multi::separated_list1,
     sequence::{preceded, separated_pair},
     IResult,
 };
 use rayon::prelude::*;
 
 #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
-struct Point {
+pub struct Point {
     x: i32,
     y: i32,
 }
@@ -32,120 +33,105 @@ fn parse_sensor(input: &str) -> IResult<&str, (Point, Point)> {
 }
 
 fn parse_sensors_and_beacons(input: &str) -> IResult<&str, Vec<(Point, Point)>> {
-    separated_list1(newline, parse_sensor)(input)
+    lines(parse_sensor)(input)
 }
 
 fn distance(Point { x: x1, y: y1 }: &Point, Point { x: x2, y: y2 }: &Point) -> i32 {
     (x1 - x2).abs() + (y1 - y2).abs()
 }
 
-pub fn process_part1(input: &str, y_pos: i32) -> String {
-    let (_, sensors_and_beacons) = parse_sensors_and_beacons(input).unwrap();
-    let (sensors, beacons): (Vec<_>, Vec<_>) = sensors_and_beacons.into_iter().unzip();
-
-    let sensor_to_distance: HashMap<&Point, i32> = sensors
+/// Each sensor with how far it can see, which is as far as its closest beacon.
+fn sensor_to_distance(sensors_and_beacons: &[(Point, Point)]) -> HashMap<&Point, i32> {
+    sensors_and_beacons
         .iter()
-        .zip(beacons.iter())
         .map(|(sensor, beacon)| (sensor, distance(sensor, beacon)))
-        .collect();
+        .collect()
+}
 
+fn covered_on_line(sensor_to_distance: &HashMap<&Point, i32>, y_pos: i32) -> RangeSet<i32> {
     sensor_to_distance
         .iter()
-        .filter(|(sensor, distance)| {
-            let sensor_y_range = (sensor.y - **distance)..(sensor.y + **distance);
-            sensor_y_range.contains(&y_pos)
-        })
-        .flat_map(|(sensor, max_distance)| {
+        .filter_map(|(sensor, max_distance)| {
             let distance_to_line = (sensor.y - y_pos).abs();
             let max_distance_on_line = max_distance - distance_to_line;
-            (sensor.x - max_distance_on_line)..=sensor.x + max_distance_on_line
+            (max_distance_on_line >= 0)
+                .then(|| (sensor.x - max_distance_on_line)..(sensor.x + max_distance_on_line + 1))
         })
-        .unique()
-        .filter(|x| !beacons.contains(&Point { x: *x, y: y_pos }))
-        .count()
-        .to_string()
+        .collect()
 }
 
-pub fn process_part2(input: &str, maximum: i32) -> String {
-    let (_, sensors_and_beacons) = parse_sensors_and_beacons(input).unwrap();
-    let (sensors, beacons): (Vec<_>, Vec<_>) = sensors_and_beacons.into_iter().unzip();
+/// Number of positions on row `y_pos` where the distress beacon cannot be.
+pub fn count_excluded_positions(sensors_and_beacons: &[(Point, Point)], y_pos: i32) -> i32 {
+    let sensor_to_distance = sensor_to_distance(sensors_and_beacons);
 
-    let sensor_to_distance: HashMap<&Point, i32> = sensors
+    let beacons_on_line = sensors_and_beacons
         .iter()
-        .zip(beacons.iter())
-        .map(|(sensor, beacon)| (sensor, distance(sensor, beacon)))
+        .map(|(_, beacon)| beacon)
+        .filter(|beacon| beacon.y == y_pos)
+        .map(|beacon| beacon.x..beacon.x + 1)
         .collect();
 
+    covered_on_line(&sensor_to_distance, y_pos)
+        .difference(&beacons_on_line)
+        .len()
+}
+
+/// Tuning frequency of the only position within `0..=maximum` on both axes that
+/// no sensor covers.
+pub fn find_tuning_frequency(
+    sensors_and_beacons: &[(Point, Point)],
+    maximum: i32,
+) -> Result<i64, Error> {
+    let sensor_to_distance = sensor_to_distance(sensors_and_beacons);
+
     const STEP_SIZE: i32 = 1000;
+    let search_area = RangeSet::from(0..=maximum);
 
     let (x, y) = (0..=maximum)
         .step_by(STEP_SIZE as usize)
         .par_bridge()
         .find_map_any(|y| {
-            (y..y + STEP_SIZE).find_map(|yy| {
-                let ranges: Vec<RangeInclusive<i32>> = sensor_to_distance
-                    .iter()
-                    .filter_map(move |(sensor, max_distance)| {
-                        let distance_to_line = (sensor.y - yy).abs();
-                        if distance_to_line >= *max_distance {
-                            None
-                        } else {
-                            let max_distance_on_line = *max_distance - distance_to_line;
-                            Some(
-                                (sensor.x - max_distance_on_line).max(0)
-                                    ..=(sensor.x + max_distance_on_line.min(maximum)),
-                            )
-                        }
-                    })
-                    .sorted_by_key(|range| (*range.start(), *range.end()))
-                    .collect_vec();
-
-                let mut maximum_range = 0..=0;
-
-                for range in ranges.into_iter() {
-                    // extend the maximum range if the ranges overlap, or touch with no space in between
-                    if maximum_range.end() + 1 >= *range.start() {
-                        maximum_range =
-                            *maximum_range.start()..=(*maximum_range.end().max(range.end()));
-                    } else {
-                        return Some((maximum_range.end() + 1, yy));
-                    }
-                }
-                return None;
+            (y..(y + STEP_SIZE).min(maximum + 1)).find_map(|yy| {
+                search_area
+                    .difference(&covered_on_line(&sensor_to_distance, yy))
+                    .min()
+                    .map(|x| (x, yy))
             })
         })
-        .unwrap();
+        .ok_or_else(|| Error::invalid("every position is covered by a sensor"))?;
 
-    (x as i64 * 4_000_000 + y as i64).to_string()
+    Ok(x as i64 * 4_000_000 + y as i64)
+}
+
+aoc_utils::solution! {
+    Day15: 2022 day 15;
+    type Parsed<'a> = Vec<(Point, Point)>;
+    parse: |input| parse_all(parse_sensors_and_beacons, input);
+    part1: |sensors_and_beacons| Ok(count_excluded_positions(sensors_and_beacons, 2_000_000));
+    part2: |sensors_and_beacons| find_tuning_frequency(sensors_and_beacons, 4_000_000);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
 
-    const EXAMPLE_INPUT: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
-Sensor at x=9, y=16: closest beacon is at x=10, y=16
-Sensor at x=13, y=2: closest beacon is at x=15, y=3
-Sensor at x=12, y=14: closest beacon is at x=10, y=16
-Sensor at x=10, y=20: closest beacon is at x=10, y=16
-Sensor at x=14, y=17: closest beacon is at x=10, y=16
-Sensor at x=8, y=7: closest beacon is at x=2, y=10
-Sensor at x=2, y=0: closest beacon is at x=2, y=10
-Sensor at x=0, y=11: closest beacon is at x=2, y=10
-Sensor at x=20, y=14: closest beacon is at x=25, y=17
-Sensor at x=17, y=20: closest beacon is at x=21, y=22
-Sensor at x=16, y=7: closest beacon is at x=15, y=3
-Sensor at x=14, y=3: closest beacon is at x=15, y=3
-Sensor at x=20, y=1: closest beacon is at x=15, y=3
-";
+    fn example() -> &'static str {
+        inputs::example(2022, 15, 1).unwrap()
+    }
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT, 10), "26");
+        let sensors_and_beacons = parse_all(parse_sensors_and_beacons, example()).unwrap();
+        assert_eq!(count_excluded_positions(&sensors_and_beacons, 10), 26);
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT, 20), "56000011");
+        let sensors_and_beacons = parse_all(parse_sensors_and_beacons, example()).unwrap();
+        assert_eq!(
+            find_tuning_frequency(&sensors_and_beacons, 20).unwrap(),
+            56000011
+        );
     }
 }
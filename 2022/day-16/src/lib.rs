@@ -1,18 +1,19 @@
 use std::collections::{HashMap, HashSet};
 
+use aoc_utils::{
+    parse::{integer, labelled, lines, parse_all},
+    Error,
+};
 use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{alpha1, newline},
-    multi::{separated_list0, separated_list1},
-    sequence::preceded,
-    IResult,
+    branch::alt, bytes::complete::tag, character::complete::alpha1, multi::separated_list0, IResult,
 };
 
-fn parse_valve(input: &str) -> IResult<&str, (&str, u32, Vec<&str>)> {
-    let (input, valve) = preceded(tag("Valve "), alpha1)(input)?;
-    let (input, flow_rate) =
-        preceded(tag(" has flow rate="), nom::character::complete::u32)(input)?;
+/// A valve's name, flow rate and the valves its tunnels lead to.
+type Valve<'a> = (&'a str, u32, Vec<&'a str>);
+
+fn parse_valve(input: &str) -> IResult<&str, Valve<'_>> {
+    let (input, valve) = labelled("Valve", alpha1)(input)?;
+    let (input, flow_rate) = labelled(" has flow rate=", integer)(input)?;
 
     let (input, _) = alt((
         tag("; tunnels lead to valves "),
@@ -22,8 +23,30 @@ fn parse_valve(input: &str) -> IResult<&str, (&str, u32, Vec<&str>)> {
     Ok((input, (valve, flow_rate, neighbors)))
 }
 
-fn parse_valves(input: &str) -> IResult<&str, Vec<(&str, u32, Vec<&str>)>> {
-    separated_list1(newline, parse_valve)(input)
+fn parse_valves(input: &str) -> Result<Vec<Valve<'_>>, Error> {
+    parse_all(lines(parse_valve), input)
+}
+
+/// The tunnels leading out of each valve and the flow rate of each valve.
+pub struct Valves<'a> {
+    graph: HashMap<&'a str, Vec<&'a str>>,
+    flows: HashMap<&'a str, u32>,
+}
+
+impl<'a> Valves<'a> {
+    fn new(input: &'a str) -> Result<Self, Error> {
+        let valves = parse_valves(input)?;
+        let flows: HashMap<_, _> = HashMap::from_iter(valves.iter().map(|(v, f, _)| (*v, *f)));
+        if let Some(unknown) = ["AA"]
+            .iter()
+            .chain(valves.iter().flat_map(|(_, _, n)| n))
+            .find(|v| !flows.contains_key(*v))
+        {
+            return Err(Error::invalid(format!("there is no valve {unknown}")));
+        }
+        let graph = HashMap::from_iter(valves.into_iter().map(|(v, _, n)| (v, n)));
+        Ok(Self { graph, flows })
+    }
 }
 
 fn dfs_helper<'a>(
@@ -32,8 +55,7 @@ fn dfs_helper<'a>(
     flow_rate: u32,
     total_flowed: u32,
     open_valves: &HashSet<&str>,
-    graph: &'a HashMap<&str, Vec<&str>>,
-    flows: &HashMap<&str, u32>,
+    valves: &'a Valves<'_>,
     cache: &mut HashMap<(u32, &'a str, u32), u32>,
 ) -> Option<u32> {
     if time == 30 {
@@ -48,28 +70,28 @@ fn dfs_helper<'a>(
     }
     cache.insert(cache_key, total_flowed);
 
-    let current_valve = graph.get(current_location).unwrap();
+    let current_valve = valves.graph.get(current_location).unwrap();
 
-    let best_result_open_current =
-        if *flows.get(current_location).unwrap() > 0 && !open_valves.contains(current_location) {
-            let mut new_open_valves = open_valves.clone();
-            new_open_valves.insert(current_location);
+    let best_result_open_current = if *valves.flows.get(current_location).unwrap() > 0
+        && !open_valves.contains(current_location)
+    {
+        let mut new_open_valves = open_valves.clone();
+        new_open_valves.insert(current_location);
 
-            let new_total_flowed = total_flowed + flow_rate;
-            let new_flow_rate = flow_rate + flows.get(current_location).unwrap();
-            dfs_helper(
-                time + 1,
-                current_location,
-                new_flow_rate,
-                new_total_flowed,
-                &new_open_valves,
-                graph,
-                flows,
-                cache,
-            )
-        } else {
-            None
-        };
+        let new_total_flowed = total_flowed + flow_rate;
+        let new_flow_rate = flow_rate + valves.flows.get(current_location).unwrap();
+        dfs_helper(
+            time + 1,
+            current_location,
+            new_flow_rate,
+            new_total_flowed,
+            &new_open_valves,
+            valves,
+            cache,
+        )
+    } else {
+        None
+    };
 
     let best_result_to_neighbor = current_valve
         .iter()
@@ -80,8 +102,7 @@ fn dfs_helper<'a>(
                 flow_rate,
                 total_flowed + flow_rate,
                 open_valves,
-                graph,
-                flows,
+                valves,
                 cache,
             )
         })
@@ -92,13 +113,11 @@ fn dfs_helper<'a>(
 
 fn dfs_helper2<'a>(
     time: u32,
-    my_location: &'a str,
-    elephant_location: &'a str,
+    (my_location, elephant_location): (&'a str, &'a str),
     flow_rate: u32,
     total_flowed: u32,
     open_valves: &HashSet<&str>,
-    graph: &'a HashMap<&str, Vec<&str>>,
-    flows: &HashMap<&str, u32>,
+    valves: &'a Valves<'_>,
     cache: &mut HashMap<(u32, &'a str, &'a str, u32), u32>,
 ) -> Option<u32> {
     if time == 26 {
@@ -113,10 +132,10 @@ fn dfs_helper2<'a>(
     }
     cache.insert(cache_key, total_flowed);
 
-    let my_current_valve = graph.get(my_location).unwrap();
-    let my_flow_rate = flows.get(my_location).unwrap();
-    let elephant_current_valve = graph.get(elephant_location).unwrap();
-    let elephant_flow_rate = flows.get(elephant_location).unwrap();
+    let my_current_valve = valves.graph.get(my_location).unwrap();
+    let my_flow_rate = valves.flows.get(my_location).unwrap();
+    let elephant_current_valve = valves.graph.get(elephant_location).unwrap();
+    let elephant_flow_rate = valves.flows.get(elephant_location).unwrap();
 
     let can_open_my_valve = *my_flow_rate > 0 && !open_valves.contains(my_location);
     let can_open_elephant_valve =
@@ -132,13 +151,11 @@ fn dfs_helper2<'a>(
         for new_elephant_location in elephant_current_valve.iter() {
             results.push(dfs_helper2(
                 time + 1,
-                my_location,
-                *new_elephant_location,
+                (my_location, new_elephant_location),
                 flow_rate + my_flow_rate,
                 total_flowed + flow_rate,
                 &new_open_valves,
-                graph,
-                flows,
+                valves,
                 cache,
             ));
         }
@@ -152,13 +169,11 @@ fn dfs_helper2<'a>(
         for my_new_location in my_current_valve.iter() {
             results.push(dfs_helper2(
                 time + 1,
-                *my_new_location,
-                elephant_location,
+                (my_new_location, elephant_location),
                 flow_rate + elephant_flow_rate,
                 total_flowed + flow_rate,
                 &new_open_valves,
-                graph,
-                flows,
+                valves,
                 cache,
             ));
         }
@@ -172,13 +187,11 @@ fn dfs_helper2<'a>(
 
         results.push(dfs_helper2(
             time + 1,
-            my_location,
-            elephant_location,
+            (my_location, elephant_location),
             flow_rate + my_flow_rate + elephant_flow_rate,
             total_flowed + flow_rate,
             &new_open_valves,
-            graph,
-            flows,
+            valves,
             cache,
         ));
     }
@@ -188,13 +201,11 @@ fn dfs_helper2<'a>(
         for my_new_location in my_current_valve.iter() {
             results.push(dfs_helper2(
                 time + 1,
-                my_new_location,
-                new_elephant_location,
+                (my_new_location, new_elephant_location),
                 flow_rate,
                 total_flowed + flow_rate,
                 open_valves,
-                graph,
-                flows,
+                valves,
                 cache,
             ));
         }
@@ -203,61 +214,42 @@ fn dfs_helper2<'a>(
     results.into_iter().flatten().max()
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_, valves) = parse_valves(input).unwrap();
-
-    let flows: HashMap<&str, u32> = HashMap::from_iter(valves.iter().map(|(v, f, _)| (*v, *f)));
-    let graph: HashMap<&str, Vec<&str>> =
-        HashMap::from_iter(valves.into_iter().map(|(v, _, n)| (v, n)));
+fn most_pressure_alone(valves: &Valves<'_>) -> Result<u32, Error> {
     let mut cache: HashMap<(u32, &str, u32), u32> = HashMap::new();
 
-    let result = dfs_helper(0, "AA", 0, 0, &HashSet::new(), &graph, &flows, &mut cache)
-        .expect("couldn't find a solution at all");
-    result.to_string()
+    dfs_helper(0, "AA", 0, 0, &HashSet::new(), valves, &mut cache)
+        .ok_or_else(|| Error::invalid("couldn't find a solution at all"))
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (_, valves) = parse_valves(input).unwrap();
-
-    let flows: HashMap<&str, u32> = HashMap::from_iter(valves.iter().map(|(v, f, _)| (*v, *f)));
-    let graph: HashMap<&str, Vec<&str>> =
-        HashMap::from_iter(valves.into_iter().map(|(v, _, n)| (v, n)));
+fn most_pressure_with_elephant(valves: &Valves<'_>) -> Result<u32, Error> {
     let mut cache: HashMap<(u32, &str, &str, u32), u32> = HashMap::new();
 
-    let result = dfs_helper2(
-        0,
-        "AA",
-        "AA",
-        0,
-        0,
-        &HashSet::new(),
-        &graph,
-        &flows,
-        &mut cache,
-    )
-    .expect("coudn't find a solution at all");
-    result.to_string()
+    dfs_helper2(0, ("AA", "AA"), 0, 0, &HashSet::new(), valves, &mut cache)
+        .ok_or_else(|| Error::invalid("couldn't find a solution at all"))
+}
+
+aoc_utils::solution! {
+    Day16: 2022 day 16;
+    type Parsed<'a> = Valves<'a>;
+    parse: |input| Valves::new(input);
+    part1: |valves| most_pressure_alone(valves);
+    part2: |valves| most_pressure_with_elephant(valves);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
+
+    fn example() -> &'static str {
+        inputs::example(2022, 16, 1).unwrap()
+    }
 
-    const EXAMPLE_INPUT: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
-Valve BB has flow rate=13; tunnels lead to valves CC, AA
-Valve CC has flow rate=2; tunnels lead to valves DD, BB
-Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
-Valve EE has flow rate=3; tunnels lead to valves FF, DD
-Valve FF has flow rate=0; tunnels lead to valves EE, GG
-Valve GG has flow rate=0; tunnels lead to valves FF, HH
-Valve HH has flow rate=22; tunnel leads to valve GG
-Valve II has flow rate=0; tunnels lead to valves AA, JJ
-Valve JJ has flow rate=21; tunnel leads to valve II";
     const EXAMPLE_INPUTS: [(&str, &str, &str); 0] = [];
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "1651");
+        assert_eq!(process_part1(example()), "1651");
     }
 
     #[test]
@@ -269,7 +261,7 @@ Valve JJ has flow rate=21; tunnel leads to valve II";
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "1707");
+        assert_eq!(process_part2(example()), "1707");
     }
 
     #[test]
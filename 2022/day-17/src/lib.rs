@@ -1,4 +1,8 @@
-use std::collections::HashMap;
+use std::fmt;
+
+use aoc_utils::{parse::parse_all, render, Error, Grid};
+use itertools::Itertools;
+use nom::{branch::alt, character::complete::char, combinator::value, multi::many1};
 
 const SHAPES: [&str; 5] = [
     "####",
@@ -19,15 +23,11 @@ const SHAPES: [&str; 5] = [
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 struct Row(i64, i64);
 
-fn parse_pushes(input: &str) -> Vec<i64> {
-    input
-        .chars()
-        .map(|c| match c {
-            '>' => 1,
-            '<' => -1,
-            _ => panic!("unrecognized char"),
-        })
-        .collect()
+fn parse_pushes(input: &str) -> Result<Vec<i64>, Error> {
+    parse_all(
+        many1(alt((value(1, char('>')), value(-1, char('<'))))),
+        input,
+    )
 }
 
 fn parse_shape(shape: &str) -> Vec<Row> {
@@ -44,26 +44,7 @@ fn parse_shape(shape: &str) -> Vec<Row> {
     rows
 }
 
-// fn print_grid(grid: &HashMap<i64, i64>) {
-//     let max_y = grid.keys().max().unwrap_or(&0);
-//     for y in (0..=*max_y).rev() {
-//         print!("{:2}: ", y);
-//         let row = grid.get(&y).unwrap();
-//         for x in 0..=8 {
-//             if x == 0 || x == 8 {
-//                 print!("@");
-//             } else if (row & (1 << x)) != 0 {
-//                 print!("#");
-//             } else {
-//                 print!(".");
-//             }
-//         }
-//         print!("\n");
-//     }
-//     print!("\n\n\n");
-// }
-
-fn shape_at(shape: &Vec<Row>, pos: (i64, i64)) -> impl Iterator<Item = Row> + '_ {
+fn shape_at(shape: &[Row], pos: (i64, i64)) -> impl Iterator<Item = Row> + '_ {
     shape
         .iter()
         .map(move |Row(y, row)| Row(y + pos.1, row << (pos.0 + 1)))
@@ -74,109 +55,168 @@ fn collides(a: i64, b: i64) -> bool {
     (a & (b | walls)) != 0
 }
 
-fn process_shapes(input: &str, num_shapes: i64) -> String {
-    let pushes = parse_pushes(input);
-
-    let mut cache: HashMap<(usize, usize, Vec<i64>), (i64, i64)> = HashMap::new();
-
-    let mut pushes = pushes.iter().enumerate().cycle();
-    let mut shapes = SHAPES.into_iter().map(parse_shape).enumerate().cycle();
-    let mut grid: Vec<i64> = vec![0];
-
-    let mut max_y: i64 = 0;
-    let mut max_y_offset: i64 = 0;
-    let mut n_shapes = 0;
-
-    while n_shapes < num_shapes {
-        // print_grid(&grid);
-
-        let (shape_idx, c_shape) = shapes.next().unwrap();
-        let mut cur_pos = (2, max_y + 3);
-
-        loop {
-            let (push_idx, dx) = pushes.next().unwrap();
-            let top_of_grid: Vec<_> = grid.iter().rev().take(100).map(|v| *v).collect();
-            let cache_key = (shape_idx, push_idx, top_of_grid);
-            let cached = cache.get(&cache_key);
-            if cached.is_none() {
-                cache.insert(cache_key, (max_y, n_shapes));
-            } else {
-                let (cached_max_y, cached_n_shapes) = cache.get(&cache_key).unwrap();
-                let cycle_shapes = n_shapes - cached_n_shapes;
-                let cycle_height = max_y - cached_max_y;
-                let num_cycles = (num_shapes - n_shapes) / cycle_shapes;
-                max_y_offset += num_cycles * cycle_height;
-                n_shapes += num_cycles * cycle_shapes;
-                // cache should only be used once, can't be bothered to spend more time on this problem so just clearing it
-                cache.clear();
-            }
-
-            // check walls after push
-            let next_pos = (cur_pos.0 + dx, cur_pos.1);
-            let mut c_shape_coords = shape_at(&c_shape, next_pos);
-            if c_shape_coords.all(|row| !collides(row.1, *grid.get(row.0 as usize).unwrap_or(&0))) {
-                cur_pos = next_pos;
-            }
+#[derive(Debug, Clone)]
+struct Chamber {
+    grid: Vec<i64>,
+    max_y: i64,
+    shape_idx: usize,
+    push_idx: usize,
+}
 
-            // fall down
-            let next_pos = (cur_pos.0, cur_pos.1 - 1);
-            let mut n_shape_coords = shape_at(&c_shape, next_pos);
-            if n_shape_coords
-                .any(|row| row.0 < 0 || (row.1 & *grid.get(row.0 as usize).unwrap_or(&0)) != 0)
-            {
-                // stop here
-                let cur_coords = shape_at(&c_shape, cur_pos);
-
-                for Row(y, row) in cur_coords {
-                    max_y = max_y.max(y + 1);
-
-                    // update grid
-                    let mut grid_row = grid.get_mut(y as usize);
-                    if grid_row.is_none() {
-                        grid.push(0);
-                        grid_row = grid.get_mut(y as usize);
-                    }
-                    let g = grid_row.unwrap();
-                    *g = (*g) | row;
+/// The settled rocks the way the puzzle draws them, with the floor at the bottom.
+impl fmt::Display for Chamber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let height = self.max_y as usize;
+        let cells = self.grid[..height]
+            .iter()
+            .rev()
+            .flat_map(|row| (1..=7).map(move |x| row & (1 << x) != 0))
+            .collect();
+        let rocks = render::ascii(
+            &Grid::new(7, height, cells),
+            |&rock| {
+                if rock {
+                    '#'
+                } else {
+                    '.'
                 }
+            },
+        );
+        for line in rocks.lines() {
+            writeln!(f, "|{line}|")?;
+        }
+        write!(f, "+-------+")
+    }
+}
 
-                break;
-            } else {
-                // keep falling
-                cur_pos = next_pos;
-            }
+fn drop_shape(mut chamber: Chamber, shapes: &[Vec<Row>], pushes: &[i64]) -> Chamber {
+    let c_shape = &shapes[chamber.shape_idx];
+    let grid = &mut chamber.grid;
+    let mut cur_pos = (2, chamber.max_y + 3);
+
+    loop {
+        let dx = pushes[chamber.push_idx];
+        chamber.push_idx = (chamber.push_idx + 1) % pushes.len();
+
+        // check walls after push
+        let next_pos = (cur_pos.0 + dx, cur_pos.1);
+        let mut c_shape_coords = shape_at(c_shape, next_pos);
+        if c_shape_coords.all(|row| !collides(row.1, *grid.get(row.0 as usize).unwrap_or(&0))) {
+            cur_pos = next_pos;
         }
 
-        n_shapes += 1;
+        // fall down
+        let next_pos = (cur_pos.0, cur_pos.1 - 1);
+        let mut n_shape_coords = shape_at(c_shape, next_pos);
+        if n_shape_coords
+            .any(|row| row.0 < 0 || (row.1 & *grid.get(row.0 as usize).unwrap_or(&0)) != 0)
+        {
+            // stop here
+            let cur_coords = shape_at(c_shape, cur_pos);
+
+            for Row(y, row) in cur_coords {
+                chamber.max_y = chamber.max_y.max(y + 1);
+
+                // update grid
+                let mut grid_row = grid.get_mut(y as usize);
+                if grid_row.is_none() {
+                    grid.push(0);
+                    grid_row = grid.get_mut(y as usize);
+                }
+                let g = grid_row.unwrap();
+                *g |= row;
+            }
+
+            break;
+        } else {
+            // keep falling
+            cur_pos = next_pos;
+        }
     }
 
-    (max_y + max_y_offset).to_string()
+    chamber.shape_idx = (chamber.shape_idx + 1) % shapes.len();
+    chamber
 }
 
-pub fn process_part1(input: &str) -> String {
-    process_shapes(input, 2022)
+/// How tall the tower is once `num_shapes` rocks have come to rest.
+fn tower_height(pushes: &[i64], num_shapes: usize) -> i64 {
+    let shapes = SHAPES.into_iter().map(parse_shape).collect_vec();
+
+    let chamber = Chamber {
+        grid: vec![0],
+        max_y: 0,
+        shape_idx: 0,
+        push_idx: 0,
+    };
+
+    // The top of the tower is a good enough fingerprint, a falling shape will
+    // never make it further down than that.
+    let heights = aoc_utils::find_cycle_by(
+        chamber,
+        |chamber| drop_shape(chamber, &shapes, pushes),
+        |chamber| {
+            (
+                chamber.shape_idx,
+                chamber.push_idx,
+                chamber.grid.iter().rev().take(100).copied().collect_vec(),
+            )
+        },
+        |chamber| chamber.max_y,
+    );
+
+    heights.extrapolate(num_shapes, |max_y| *max_y)
 }
 
-pub fn process_part2(input: &str) -> String {
-    process_shapes(input, 1_000_000_000_000)
+aoc_utils::solution! {
+    Day17: 2022 day 17;
+    type Parsed<'a> = Vec<i64>;
+    parse: |input| parse_pushes(input);
+    part1: |pushes| Ok(tower_height(pushes, 2022));
+    part2: |pushes| Ok(tower_height(pushes, 1_000_000_000_000));
 }
 
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-
     use super::*;
+    use aoc_utils::inputs;
 
-    const EXAMPLE_INPUT: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+    fn example() -> &'static str {
+        inputs::example(2022, 17, 1).unwrap()
+    }
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "3068");
+        assert_eq!(process_part1(example()), "3068");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "1514285714288");
+        assert_eq!(process_part2(example()), "1514285714288");
+    }
+
+    #[test]
+    fn draw_chamber() {
+        let pushes = parse_pushes(example()).unwrap();
+        let shapes = SHAPES.into_iter().map(parse_shape).collect_vec();
+        let mut chamber = Chamber {
+            grid: vec![0],
+            max_y: 0,
+            shape_idx: 0,
+            push_idx: 0,
+        };
+        for _ in 0..3 {
+            chamber = drop_shape(chamber, &shapes, &pushes);
+        }
+        assert_eq!(
+            chamber.to_string(),
+            "|..#....|
+|..#....|
+|####...|
+|..###..|
+|...#...|
+|..####.|
++-------+"
+        );
     }
 
     #[test]
@@ -227,18 +267,18 @@ mod tests {
         let shape = parse_shape("####");
         // walls
         let moved_shape = shape_at(&shape, (0, 0)).collect_vec();
-        assert_eq!(collides(moved_shape.get(0).unwrap().1, 0), false);
+        assert!(!collides(moved_shape[0].1, 0));
         let moved_shape = shape_at(&shape, (-1, 0)).collect_vec();
-        assert_eq!(collides(moved_shape.get(0).unwrap().1, 0), true);
+        assert!(collides(moved_shape[0].1, 0));
         let moved_shape = shape_at(&shape, (4, 0)).collect_vec();
-        assert_eq!(collides(moved_shape.get(0).unwrap().1, 0), true);
+        assert!(collides(moved_shape[0].1, 0));
 
         // other shapes
         let moved_shape = shape_at(&shape, (0, 0)).collect_vec();
-        assert_eq!(collides(moved_shape.get(0).unwrap().1, 2), true);
-        assert_eq!(collides(moved_shape.get(0).unwrap().1, 4), true);
-        assert_eq!(collides(moved_shape.get(0).unwrap().1, 8), true);
-        assert_eq!(collides(moved_shape.get(0).unwrap().1, 16), true);
-        assert_eq!(collides(moved_shape.get(0).unwrap().1, 32), false);
+        assert!(collides(moved_shape[0].1, 2));
+        assert!(collides(moved_shape[0].1, 4));
+        assert!(collides(moved_shape[0].1, 8));
+        assert!(collides(moved_shape[0].1, 16));
+        assert!(!collides(moved_shape[0].1, 32));
     }
 }
@@ -1,23 +1,14 @@
 use std::collections::HashSet;
 
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, newline},
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult, Parser,
+use aoc_utils::{
+    parse::{integer, lines, parse_all},
+    Error,
 };
+use nom::{bytes::complete::tag, sequence::preceded, sequence::tuple};
 
-fn parse_droplets(input: &str) -> IResult<&str, Vec<(i32, i32, i32)>> {
-    separated_list1(
-        newline,
-        separated_pair(
-            complete::i32,
-            tag(","),
-            separated_pair(complete::i32, tag(","), complete::i32),
-        )
-        .map(|(x, (y, z))| (x, y, z)),
-    )(input)
+fn parse_droplets(input: &str) -> Result<Vec<(i32, i32, i32)>, Error> {
+    let coordinate = || preceded(tag(","), integer);
+    parse_all(lines(tuple((integer, coordinate(), coordinate()))), input)
 }
 
 fn neighbors((x, y, z): &(i32, i32, i32)) -> impl Iterator<Item = (i32, i32, i32)> + '_ {
@@ -32,9 +23,8 @@ fn neighbors((x, y, z): &(i32, i32, i32)) -> impl Iterator<Item = (i32, i32, i32
     .into_iter()
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_, droplets) = parse_droplets(input).unwrap();
-    let space: HashSet<(i32, i32, i32)> = HashSet::from_iter(droplets.into_iter());
+fn surface_area(droplets: &[(i32, i32, i32)]) -> usize {
+    let space: HashSet<(i32, i32, i32)> = droplets.iter().copied().collect();
 
     let mut total_faces = 0;
     for droplet in space.iter() {
@@ -42,13 +32,13 @@ pub fn process_part1(input: &str) -> String {
         dbg!(&droplet, &covered_faces);
         total_faces += 6 - covered_faces;
     }
-    total_faces.to_string()
+    total_faces
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (_, droplets) = parse_droplets(input).unwrap();
+/// The surface area without the faces of pockets of air trapped inside.
+fn exterior_surface_area(droplets: &[(i32, i32, i32)]) -> usize {
     let droplet_count = droplets.len();
-    let space: HashSet<(i32, i32, i32)> = HashSet::from_iter(droplets.into_iter());
+    let space: HashSet<(i32, i32, i32)> = droplets.iter().copied().collect();
 
     let mut total_faces = 0;
     for droplet in space.iter() {
@@ -82,34 +72,33 @@ pub fn process_part2(input: &str) -> String {
         }
         total_faces += 6 - covered_faces;
     }
-    total_faces.to_string()
+    total_faces
+}
+
+aoc_utils::solution! {
+    Day18: 2022 day 18;
+    type Parsed<'a> = Vec<(i32, i32, i32)>;
+    parse: |input| parse_droplets(input);
+    part1: |droplets| Ok(surface_area(droplets));
+    part2: |droplets| Ok(exterior_surface_area(droplets));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
 
-    const EXAMPLE_INPUT: &str = "2,2,2
-1,2,2
-3,2,2
-2,1,2
-2,3,2
-2,2,1
-2,2,3
-2,2,4
-2,2,6
-1,2,5
-3,2,5
-2,1,5
-2,3,5";
+    fn example() -> &'static str {
+        inputs::example(2022, 18, 1).unwrap()
+    }
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "64");
+        assert_eq!(process_part1(example()), "64");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "58");
+        assert_eq!(process_part2(example()), "58");
     }
 }
@@ -1,16 +1,14 @@
 use std::collections::VecDeque;
 
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, newline},
-    multi::separated_list1,
-    sequence::preceded,
-    IResult,
+use aoc_utils::{
+    parse::{lines, parse_all},
+    Error,
 };
+use nom::{bytes::complete::tag, character::complete, sequence::preceded, IResult};
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 
 #[derive(Debug)]
-struct Blueprint {
+pub struct Blueprint {
     id: i32,
     ore_robot_cost_ore: i32,
     clay_robot_cost_ore: i32,
@@ -70,13 +68,12 @@ fn parse_blueprint(input: &str) -> IResult<&str, Blueprint> {
     ))
 }
 
-fn parse_blueprints(input: &str) -> IResult<&str, Vec<Blueprint>> {
-    separated_list1(newline, parse_blueprint)(input)
+fn parse_blueprints(input: &str) -> Result<Vec<Blueprint>, Error> {
+    parse_all(lines(parse_blueprint), input)
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_, blueprints) = parse_blueprints(input).unwrap();
-
+/// The sum of each blueprint's id times the most geodes it can open in 24 minutes.
+fn quality_levels(blueprints: &[Blueprint]) -> i32 {
     blueprints
         .par_iter()
         .map(|blueprint| {
@@ -201,15 +198,12 @@ pub fn process_part1(input: &str) -> String {
 
             blueprint.id * best_geodes
         })
-        .sum::<i32>()
-        .to_string()
+        .sum()
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (_, mut blueprints) = parse_blueprints(input).unwrap();
-    blueprints = blueprints.into_iter().take(3).collect();
-
-    blueprints
+/// The product of the most geodes the first three blueprints can open in 32 minutes.
+fn geodes_of_first_three(blueprints: &[Blueprint]) -> i64 {
+    blueprints[..blueprints.len().min(3)]
         .par_iter()
         .map(|blueprint| {
             let total_time = 32;
@@ -290,32 +284,30 @@ pub fn process_part2(input: &str) -> String {
                 }
 
                 // build obsidian robot
-                if max_obsidian_cost > state.robot_obsidian {
-                    if state.robot_clay > 0 {
-                        let time_required = if state.ore >= blueprint.obsidian_robot_cost_ore
-                            && state.clay >= blueprint.obsidian_robot_cost_clay
-                        {
-                            1
-                        } else {
-                            1 + ((blueprint.obsidian_robot_cost_ore - state.ore) as f32
-                                / state.robot_ore as f32)
-                                .ceil()
-                                .max(
-                                    ((blueprint.obsidian_robot_cost_clay - state.clay) as f32
-                                        / state.robot_clay as f32)
-                                        .ceil(),
-                                ) as i32
-                        };
-
-                        if time_required < state.time_left {
-                            let mut next_state = state.clone();
-                            next_state.add_resources(time_required);
-                            next_state.time_left -= time_required;
-                            next_state.clay -= blueprint.obsidian_robot_cost_clay;
-                            next_state.ore -= blueprint.obsidian_robot_cost_ore;
-                            next_state.robot_obsidian += 1;
-                            stack.push_back(next_state);
-                        }
+                if max_obsidian_cost > state.robot_obsidian && state.robot_clay > 0 {
+                    let time_required = if state.ore >= blueprint.obsidian_robot_cost_ore
+                        && state.clay >= blueprint.obsidian_robot_cost_clay
+                    {
+                        1
+                    } else {
+                        1 + ((blueprint.obsidian_robot_cost_ore - state.ore) as f32
+                            / state.robot_ore as f32)
+                            .ceil()
+                            .max(
+                                ((blueprint.obsidian_robot_cost_clay - state.clay) as f32
+                                    / state.robot_clay as f32)
+                                    .ceil(),
+                            ) as i32
+                    };
+
+                    if time_required < state.time_left {
+                        let mut next_state = state.clone();
+                        next_state.add_resources(time_required);
+                        next_state.time_left -= time_required;
+                        next_state.clay -= blueprint.obsidian_robot_cost_clay;
+                        next_state.ore -= blueprint.obsidian_robot_cost_ore;
+                        next_state.robot_obsidian += 1;
+                        stack.push_back(next_state);
                     }
                 }
 
@@ -349,24 +341,33 @@ pub fn process_part2(input: &str) -> String {
 
             best_geodes
         })
-        .product::<i64>()
-        .to_string()
+        .product()
+}
+
+aoc_utils::solution! {
+    Day19: 2022 day 19;
+    type Parsed<'a> = Vec<Blueprint>;
+    parse: |input| parse_blueprints(input);
+    part1: |blueprints| Ok(quality_levels(blueprints));
+    part2: |blueprints| Ok(geodes_of_first_three(blueprints));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
 
-    const EXAMPLE_INPUT: &str = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
-Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
+    fn example() -> &'static str {
+        inputs::example(2022, 19, 1).unwrap()
+    }
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "33");
+        assert_eq!(process_part1(example()), "33");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "3472");
+        assert_eq!(process_part2(example()), "3472");
     }
 }
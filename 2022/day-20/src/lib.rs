@@ -1,81 +1,57 @@
-use std::collections::VecDeque;
-
-use nom::{
-    character::complete::{self, newline},
-    multi::separated_list1,
-    IResult,
+use aoc_utils::{
+    parse::{integer, lines, parse_all},
+    CircularSequence, Error,
 };
 
-fn parse_input(input: &str) -> IResult<&str, Vec<i64>> {
-    separated_list1(newline, complete::i64)(input)
-}
-pub fn process_part1(input: &str) -> String {
-    let (_, numbers) = parse_input(input).unwrap();
-    let mut list = VecDeque::from_iter(numbers.into_iter().enumerate());
-    let original_list = list.clone();
-    for (idx, num) in original_list.iter() {
-        let current_index = list
-            .iter()
-            .position(|(i, n)| *n == *num && *i == *idx)
-            .unwrap();
-        list.remove(current_index);
-        list.insert(
-            (current_index as i64 + num).rem_euclid(list.len() as i64) as usize,
-            (*idx, *num),
-        );
-    }
-    let zero_pos = list.iter().position(|(_, n)| *n == 0).unwrap();
-    [1000, 2000, 3000]
-        .iter()
-        .map(|pos| list.get((zero_pos + pos) % list.len()).unwrap().1)
-        .sum::<i64>()
-        .to_string()
+fn parse_input(input: &str) -> Result<Vec<i64>, Error> {
+    parse_all(lines(integer), input)
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (_, numbers) = parse_input(input).unwrap();
-    let mut list = VecDeque::from_iter(numbers.into_iter().map(|n| n * 811589153).enumerate());
-    let original_list = list.clone();
-    for _ in 0..10 {
-        for (idx, num) in original_list.iter() {
-            let current_index = list
-                .iter()
-                .position(|(i, n)| *n == *num && *i == *idx)
-                .unwrap();
-            list.remove(current_index);
-            list.insert(
-                (current_index as i64 + num).rem_euclid(list.len() as i64) as usize,
-                (*idx, *num),
-            );
+/// Multiplies every number by the decryption `key` and moves each by its value,
+/// in the order they were in originally, then sums the numbers 1000, 2000 and
+/// 3000 places after the 0.
+fn grove_coordinates(numbers: &[i64], key: i64, rounds: usize) -> Result<i64, Error> {
+    let zero = numbers
+        .iter()
+        .position(|n| *n == 0)
+        .ok_or_else(|| Error::invalid("the file has no 0"))?;
+    let mut file: CircularSequence<i64> = numbers.iter().map(|n| n * key).collect();
+    for _ in 0..rounds {
+        for handle in 0..file.len() {
+            file.move_by(handle, *file.value(handle));
         }
     }
-    let zero_pos = list.iter().position(|(_, n)| *n == 0).unwrap();
-    [1000, 2000, 3000]
+    let zero_pos = file.position(zero);
+    Ok([1000, 2000, 3000]
         .iter()
-        .map(|pos| list.get((zero_pos + pos) % list.len()).unwrap().1)
-        .sum::<i64>()
-        .to_string()
+        .map(|pos| file.get(zero_pos + pos).unwrap())
+        .sum())
+}
+
+aoc_utils::solution! {
+    Day20: 2022 day 20;
+    type Parsed<'a> = Vec<i64>;
+    parse: |input| parse_input(input);
+    part1: |numbers| grove_coordinates(numbers, 1, 1);
+    part2: |numbers| grove_coordinates(numbers, 811589153, 10);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
 
-    const EXAMPLE_INPUT: &str = "1
-2
--3
-3
--2
-0
-4";
+    fn example() -> &'static str {
+        inputs::example(2022, 20, 1).unwrap()
+    }
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "3");
+        assert_eq!(process_part1(example()), "3");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "1623178306");
+        assert_eq!(process_part2(example()), "1623178306");
     }
 }
@@ -0,0 +1,265 @@
+//! Solving for what `humn` has to yell by treating every monkey's number as a
+//! linear expression in it, `slope * humn + offset`, with exact fractions.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use aoc_utils::{number_theory::gcd, Error};
+
+use crate::{monkey, Monkey, Operation};
+
+/// A fraction in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Ratio {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Ratio {
+    const ZERO: Ratio = Ratio::integer(0);
+    const ONE: Ratio = Ratio::integer(1);
+
+    const fn integer(n: i128) -> Ratio {
+        Ratio {
+            numerator: n,
+            denominator: 1,
+        }
+    }
+
+    fn new(numerator: i128, denominator: i128) -> Ratio {
+        let divisor = gcd(numerator.unsigned_abs(), denominator.unsigned_abs()) as i128;
+        let sign = denominator.signum();
+        Ratio {
+            numerator: sign * numerator / divisor,
+            denominator: sign * denominator / divisor,
+        }
+    }
+
+    fn to_integer(self) -> Option<i128> {
+        (self.denominator == 1).then_some(self.numerator)
+    }
+}
+
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.denominator {
+            1 => write!(f, "{}", self.numerator),
+            denominator => write!(f, "{}/{denominator}", self.numerator),
+        }
+    }
+}
+
+impl Add for Ratio {
+    type Output = Ratio;
+
+    fn add(self, rhs: Ratio) -> Ratio {
+        Ratio::new(
+            self.numerator * rhs.denominator + rhs.numerator * self.denominator,
+            self.denominator * rhs.denominator,
+        )
+    }
+}
+
+impl Neg for Ratio {
+    type Output = Ratio;
+
+    fn neg(self) -> Ratio {
+        Ratio::integer(-1) * self
+    }
+}
+
+impl Sub for Ratio {
+    type Output = Ratio;
+
+    fn sub(self, rhs: Ratio) -> Ratio {
+        self + -rhs
+    }
+}
+
+impl Mul for Ratio {
+    type Output = Ratio;
+
+    fn mul(self, rhs: Ratio) -> Ratio {
+        Ratio::new(
+            self.numerator * rhs.numerator,
+            self.denominator * rhs.denominator,
+        )
+    }
+}
+
+impl Div for Ratio {
+    type Output = Ratio;
+
+    /// Panics when dividing by zero, which [`linear`] checks for first.
+    fn div(self, rhs: Ratio) -> Ratio {
+        assert_ne!(rhs, Ratio::ZERO, "division by zero");
+        Ratio::new(
+            self.numerator * rhs.denominator,
+            self.denominator * rhs.numerator,
+        )
+    }
+}
+
+/// `slope * humn + offset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Linear {
+    slope: Ratio,
+    offset: Ratio,
+}
+
+impl Linear {
+    fn constant(value: Ratio) -> Linear {
+        Linear {
+            slope: Ratio::ZERO,
+            offset: value,
+        }
+    }
+
+    fn is_constant(&self) -> bool {
+        self.slope == Ratio::ZERO
+    }
+
+    fn scale(self, factor: Ratio) -> Linear {
+        Linear {
+            slope: self.slope * factor,
+            offset: self.offset * factor,
+        }
+    }
+}
+
+/// What `name` yells in terms of what `humn` yells.
+fn linear(monkeys: &HashMap<&str, Monkey>, name: &str) -> Result<Linear, Error> {
+    if name == "humn" {
+        return Ok(Linear {
+            slope: Ratio::ONE,
+            offset: Ratio::ZERO,
+        });
+    }
+    Ok(match monkey(monkeys, name)? {
+        Monkey::Value(value) => Linear::constant(Ratio::integer(*value as i128)),
+        Monkey::Calculate(monkey_a, op, monkey_b) => {
+            let a = linear(monkeys, monkey_a)?;
+            let b = linear(monkeys, monkey_b)?;
+            match op {
+                Operation::Add => Linear {
+                    slope: a.slope + b.slope,
+                    offset: a.offset + b.offset,
+                },
+                Operation::Sub => Linear {
+                    slope: a.slope - b.slope,
+                    offset: a.offset - b.offset,
+                },
+                Operation::Mul if a.is_constant() => b.scale(a.offset),
+                Operation::Mul if b.is_constant() => a.scale(b.offset),
+                Operation::Mul => {
+                    return Err(Error::invalid(format!(
+                        "{name} multiplies two numbers that depend on humn"
+                    )))
+                }
+                Operation::Div if !b.is_constant() => {
+                    return Err(Error::invalid(format!(
+                        "{name} divides by a number that depends on humn"
+                    )))
+                }
+                Operation::Div if b.offset == Ratio::ZERO => {
+                    return Err(Error::invalid(format!("{monkey_b} yells 0 to {name}")))
+                }
+                Operation::Div => a.scale(Ratio::ONE / b.offset),
+            }
+        }
+    })
+}
+
+/// The number `humn` has to yell for both monkeys `root` listens to to yell the
+/// same number, found in one go by solving `a * humn + b = c * humn + d`.
+pub fn solve_for_humn(monkeys: &HashMap<&str, Monkey>) -> Result<i64, Error> {
+    let Monkey::Calculate(a, _, b) = monkey(monkeys, "root")? else {
+        return Err(Error::invalid("root yells a number"));
+    };
+    let (a, b) = (linear(monkeys, a)?, linear(monkeys, b)?);
+    let slope = a.slope - b.slope;
+    let offset = b.offset - a.offset;
+    if slope == Ratio::ZERO {
+        return Err(Error::invalid(if offset == Ratio::ZERO {
+            "root's monkeys yell the same number whatever humn yells"
+        } else {
+            "root's monkeys never yell the same number"
+        }));
+    }
+    let humn = offset / slope;
+    humn.to_integer()
+        .and_then(|humn| i64::try_from(humn).ok())
+        .ok_or_else(|| Error::invalid(format!("humn would have to yell {humn}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_monkeys;
+
+    #[test]
+    fn fractions() {
+        assert_eq!(Ratio::new(6, -4), Ratio::new(-3, 2));
+        assert_eq!(Ratio::new(1, 2) + Ratio::new(1, 3), Ratio::new(5, 6));
+        assert_eq!(Ratio::new(1, 2) / Ratio::new(-1, 4), Ratio::integer(-2));
+        assert_eq!(Ratio::new(-3, 6).to_string(), "-1/2");
+    }
+
+    #[test]
+    fn solves_for_humn_used_more_than_once() {
+        let monkeys = parse_monkeys(
+            "root: aaaa + bbbb
+aaaa: humn + humn
+bbbb: cccc / dddd
+cccc: humn - eeee
+dddd: 3
+eeee: 9
+humn: 1",
+        )
+        .unwrap();
+        // 2h = (h - 9) / 3
+        assert_eq!(
+            solve_for_humn(&monkeys),
+            Err(Error::invalid("humn would have to yell -9/5"))
+        );
+
+        let monkeys = parse_monkeys(
+            "root: aaaa + bbbb
+aaaa: humn + humn
+bbbb: humn - eeee
+eeee: 9
+humn: 1",
+        )
+        .unwrap();
+        assert_eq!(solve_for_humn(&monkeys), Ok(-9));
+    }
+
+    #[test]
+    fn reports_non_linear_equations() {
+        let monkeys =
+            parse_monkeys("root: aaaa + bbbb\naaaa: humn * humn\nbbbb: 4\nhumn: 1").unwrap();
+        assert_eq!(
+            solve_for_humn(&monkeys),
+            Err(Error::invalid(
+                "aaaa multiplies two numbers that depend on humn"
+            ))
+        );
+        let monkeys =
+            parse_monkeys("root: aaaa + bbbb\naaaa: bbbb / humn\nbbbb: 4\nhumn: 1").unwrap();
+        assert_eq!(
+            solve_for_humn(&monkeys),
+            Err(Error::invalid(
+                "aaaa divides by a number that depends on humn"
+            ))
+        );
+        let monkeys =
+            parse_monkeys("root: aaaa + bbbb\naaaa: humn - humn\nbbbb: 4\nhumn: 1").unwrap();
+        assert_eq!(
+            solve_for_humn(&monkeys),
+            Err(Error::invalid("root's monkeys never yell the same number"))
+        );
+    }
+}
@@ -1,18 +1,22 @@
+mod expression;
+
 use std::collections::HashMap;
 
+use aoc_utils::{
+    parse::{integer, lines, parse_all},
+    Error,
+};
 use nom::{
+    branch::alt,
     bytes::complete::tag,
-    character::{
-        complete::{self, alpha1, anychar, multispace1, newline},
-        is_alphabetic,
-    },
-    multi::separated_list1,
-    sequence::delimited,
+    character::complete::{alpha1, one_of},
+    combinator::map,
+    sequence::{delimited, separated_pair, tuple},
     IResult,
 };
 
 #[derive(Debug, Clone)]
-enum Operation {
+pub enum Operation {
     Add,
     Sub,
     Mul,
@@ -20,103 +24,101 @@ enum Operation {
 }
 
 #[derive(Debug, Clone)]
-enum Monkey<'a> {
+pub enum Monkey<'a> {
     Value(i64),
     Calculate(&'a str, Operation, &'a str),
 }
 
-fn parse_monkey(input: &str) -> IResult<&str, (&str, Monkey)> {
-    let (input, name) = alpha1(input)?;
-    let (input, _) = tag(": ")(input)?;
-    if is_alphabetic(input.chars().nth(0).unwrap() as u8) {
-        let (input, monkey_a) = alpha1(input)?;
-        let (input, operator) = delimited(tag(" "), anychar, tag(" "))(input)?;
-        let (input, monkey_b) = alpha1(input)?;
-        let op = match operator {
-            '+' => Operation::Add,
-            '-' => Operation::Sub,
-            '/' => Operation::Div,
-            '*' => Operation::Mul,
-            _ => panic!("unknown operation"),
-        };
-        Ok((input, (name, Monkey::Calculate(monkey_a, op, monkey_b))))
-    } else {
-        let (input, val) = complete::i64(input)?;
-        Ok((input, (name, Monkey::Value(val))))
-    }
+fn parse_monkey(input: &str) -> IResult<&str, (&str, Monkey<'_>)> {
+    let operation = map(one_of("+-*/"), |op| match op {
+        '+' => Operation::Add,
+        '-' => Operation::Sub,
+        '*' => Operation::Mul,
+        _ => Operation::Div,
+    });
+    let calculate = map(
+        tuple((alpha1, delimited(tag(" "), operation, tag(" ")), alpha1)),
+        |(monkey_a, op, monkey_b)| Monkey::Calculate(monkey_a, op, monkey_b),
+    );
+    separated_pair(
+        alpha1,
+        tag(": "),
+        alt((map(integer, Monkey::Value), calculate)),
+    )(input)
 }
 
-fn parse_monkeys(input: &str) -> IResult<&str, HashMap<&str, Monkey>> {
-    let (input, monkeys) = separated_list1(multispace1, parse_monkey)(input)?;
-    Ok((input, HashMap::from_iter(monkeys.into_iter())))
+fn parse_monkeys(input: &str) -> Result<HashMap<&str, Monkey<'_>>, Error> {
+    Ok(HashMap::from_iter(parse_all(lines(parse_monkey), input)?))
 }
 
-fn resolve_monkeys(monkeys: &HashMap<&str, Monkey>, name: &str) -> i64 {
-    let monkey = monkeys.get(name).unwrap();
-    match monkey {
+fn monkey<'a, 'b>(
+    monkeys: &'b HashMap<&str, Monkey<'a>>,
+    name: &str,
+) -> Result<&'b Monkey<'a>, Error> {
+    monkeys
+        .get(name)
+        .ok_or_else(|| Error::invalid(format!("there is no monkey named {name}")))
+}
+
+fn resolve_monkeys(monkeys: &HashMap<&str, Monkey>, name: &str) -> Result<i64, Error> {
+    Ok(match monkey(monkeys, name)? {
         Monkey::Value(val) => *val,
         Monkey::Calculate(monkey_a, op, monkey_b) => {
-            let val_a = resolve_monkeys(monkeys, &monkey_a);
-            let val_b = resolve_monkeys(monkeys, &monkey_b);
+            let val_a = resolve_monkeys(monkeys, monkey_a)?;
+            let val_b = resolve_monkeys(monkeys, monkey_b)?;
             match op {
                 Operation::Add => val_a + val_b,
                 Operation::Sub => val_a - val_b,
                 Operation::Mul => val_a * val_b,
-                Operation::Div => val_a / val_b,
+                Operation::Div => val_a
+                    .checked_div(val_b)
+                    .ok_or_else(|| Error::invalid(format!("{monkey_b} yells 0 to {name}")))?,
             }
         }
-    }
+    })
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_, monkeys) = parse_monkeys(input).unwrap();
-    resolve_monkeys(&monkeys, "root").to_string()
-}
+/// What humn has to yell for root's two monkeys to yell the same number.
+fn humn_yell(monkeys: &HashMap<&str, Monkey<'_>>) -> Result<i64, Error> {
+    let value = expression::solve_for_humn(monkeys)?;
 
-pub fn process_part2(input: &str) -> String {
-    let (_, mut monkeys) = parse_monkeys(input).unwrap();
-    let mut value = 150;
-    loop {
-        monkeys.insert("humn", Monkey::Value(value));
-        let Monkey::Calculate(a, _, b)= monkeys.get("root").unwrap() else {
-        panic!("oh no");
+    // The solution is exact, but monkeys round when they divide.
+    let mut monkeys = monkeys.clone();
+    monkeys.insert("humn", Monkey::Value(value));
+    let Monkey::Calculate(a, _, b) = monkey(&monkeys, "root")? else {
+        unreachable!("root was checked while solving");
     };
-        let monkey_a = resolve_monkeys(&monkeys, a);
-        let monkey_b = resolve_monkeys(&monkeys, b);
-        if monkey_a == monkey_b {
-            break;
-        } else {
-            println!("{} != {}", monkey_a, monkey_b);
-            value += 1.max((monkey_a - monkey_b) / 100000);
-        }
+    if resolve_monkeys(&monkeys, a)? != resolve_monkeys(&monkeys, b)? {
+        return Err(Error::invalid(format!(
+            "humn yelling {value} only works without rounding"
+        )));
     }
-    value.to_string()
+    Ok(value)
+}
+
+aoc_utils::solution! {
+    Day21: 2022 day 21;
+    type Parsed<'a> = HashMap<&'a str, Monkey<'a>>;
+    parse: |input| parse_monkeys(input);
+    part1: |monkeys| resolve_monkeys(monkeys, "root");
+    part2: |monkeys| humn_yell(monkeys);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
+    use proptest::prelude::*;
+
+    fn example() -> &'static str {
+        inputs::example(2022, 21, 1).unwrap()
+    }
 
-    const EXAMPLE_INPUT: &str = "root: pppw + sjmn
-dbpl: 5
-cczh: sllz + lgvd
-zczc: 2
-ptdq: humn - dvpt
-dvpt: 3
-lfqf: 4
-humn: 5
-ljgn: 2
-sjmn: drzm * dbpl
-sllz: 4
-pppw: cczh / lfqf
-lgvd: ljgn * ptdq
-drzm: hmdt - zczc
-hmdt: 32";
     const EXAMPLE_INPUTS: [(&str, &str, &str); 0] = [];
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "152");
+        assert_eq!(process_part1(example()), "152");
     }
 
     #[test]
@@ -127,9 +129,8 @@ hmdt: 32";
     }
 
     #[test]
-    #[ignore]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "301");
+        assert_eq!(process_part2(example()), "301");
     }
 
     #[test]
@@ -139,4 +140,127 @@ hmdt: 32";
             assert_eq!(process_part2(input), answer_part_2.to_string());
         }
     }
+
+    /// A name of letters only, unique for every `n` below `26 * 26 * 26`.
+    fn name(prefix: char, n: usize) -> String {
+        let letter = |i: usize| (b'a' + (i % 26) as u8) as char;
+        [prefix, letter(n / 26 / 26), letter(n / 26), letter(n)]
+            .iter()
+            .collect()
+    }
+
+    /// Appends monkeys that work out `value` between them, returning the name of
+    /// the one that yells it.
+    fn yell_constant(monkeys: &mut Vec<String>, value: i64, form: u8) -> String {
+        let name = name('c', monkeys.len());
+        let (a, b) = (format!("{name}a"), format!("{name}b"));
+        let (job, parts) = match (value, form) {
+            (value, _) if value < 0 => (format!("{a} - {b}"), Some((0, -value))),
+            (_, 1) => (format!("{a} + {b}"), Some((value / 2, value - value / 2))),
+            (_, 2) => (format!("{a} / {b}"), Some((value * 3, 3))),
+            _ => (value.to_string(), None),
+        };
+        if let Some((value_a, value_b)) = parts {
+            monkeys.push(format!("{a}: {value_a}"));
+            monkeys.push(format!("{b}: {value_b}"));
+        }
+        monkeys.push(format!("{name}: {job}"));
+        name
+    }
+
+    /// Monkeys where `humn` goes through a chain of additions, subtractions and
+    /// multiplications before one side of `root` compares it with a number.
+    fn monkeys() -> impl Strategy<Value = Vec<String>> {
+        let chain = prop::collection::vec((0..5u8, 1..=9i64, 0..3u8), 0..=6);
+        (
+            -2000..=2000i64,
+            chain,
+            0..3u8,
+            any::<bool>(),
+            0..=20i64,
+            0..3usize,
+        )
+            .prop_map(|(answer, chain, form, humn_left, humn, root_op)| {
+                let mut monkeys = vec![format!("humn: {humn}")];
+                let mut side = "humn".to_string();
+                let mut value = answer;
+                for (i, (op, constant, form)) in chain.into_iter().enumerate() {
+                    let other = yell_constant(&mut monkeys, constant, form);
+                    let (job, next) = match op {
+                        0 => (format!("{side} + {other}"), value + constant),
+                        1 => (format!("{other} + {side}"), constant + value),
+                        2 => (format!("{side} * {other}"), value * constant),
+                        3 => (format!("{side} - {other}"), value - constant),
+                        _ => (format!("{other} - {side}"), constant - value),
+                    };
+                    let name = name('h', i);
+                    monkeys.push(format!("{name}: {job}"));
+                    side = name;
+                    value = next;
+                }
+                let other = yell_constant(&mut monkeys, value, form);
+                let (left, right) = if humn_left {
+                    (side, other)
+                } else {
+                    (other, side)
+                };
+                let op = ["+", "-", "*"][root_op];
+                monkeys.push(format!("root: {left} {op} {right}"));
+                monkeys
+            })
+            .prop_shuffle()
+    }
+
+    /// Works out what a monkey yells straight from the text of the jobs.
+    fn yell(jobs: &HashMap<&str, &str>, name: &str, humn: i64) -> i64 {
+        if name == "humn" {
+            return humn;
+        }
+        match jobs[name].split(' ').collect::<Vec<_>>()[..] {
+            [value] => value.parse().unwrap(),
+            [a, op, b] => {
+                let (a, b) = (yell(jobs, a, humn), yell(jobs, b, humn));
+                match op {
+                    "+" => a + b,
+                    "-" => a - b,
+                    "*" => a * b,
+                    _ => a / b,
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// The job of every monkey, by name.
+    fn jobs(monkeys: &[String]) -> HashMap<&str, &str> {
+        monkeys
+            .iter()
+            .map(|monkey| monkey.split_once(": ").unwrap())
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn part1_matches_brute_force(monkeys in monkeys()) {
+            let jobs = jobs(&monkeys);
+            let humn = jobs["humn"].parse().unwrap();
+            prop_assert_eq!(
+                process_part1(&monkeys.join("\n")),
+                yell(&jobs, "root", humn).to_string()
+            );
+        }
+
+        #[test]
+        fn part2_matches_brute_force(monkeys in monkeys()) {
+            let jobs = jobs(&monkeys);
+            let [left, _, right] = jobs["root"].split(' ').collect::<Vec<_>>()[..] else {
+                unreachable!()
+            };
+            let answer = (0..)
+                .flat_map(|n| [n, -n - 1])
+                .find(|&humn| yell(&jobs, left, humn) == yell(&jobs, right, humn))
+                .unwrap();
+            prop_assert_eq!(process_part2(&monkeys.join("\n")), answer.to_string());
+        }
+    }
 }
@@ -1,71 +1,45 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
-use glam::UVec2;
+use aoc_utils::{
+    parse::{blank_line, lines, parse_all},
+    Direction, Error, Turn,
+};
+use glam::{IVec3, UVec2};
 use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{self, line_ending, one_of},
-    combinator::iterator,
+    character::complete::{self, one_of},
+    combinator::map,
     multi::many1,
-    sequence::{separated_pair, terminated},
+    sequence::separated_pair,
     IResult, Parser,
 };
 
 #[derive(Debug)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-#[derive(Debug)]
-enum Turn {
-    Left,
-    Right,
-}
-
-#[derive(Debug)]
-enum Move {
+pub enum Move {
     Paces(u32),
     Turn(Turn),
 }
 
-impl Direction {
-    fn turn(self, turn: &Turn) -> Direction {
-        use Direction::*;
-        match (self, turn) {
-            (Up, Turn::Left) => Left,
-            (Up, Turn::Right) => Right,
-            (Down, Turn::Left) => Right,
-            (Down, Turn::Right) => Left,
-            (Left, Turn::Left) => Down,
-            (Left, Turn::Right) => Up,
-            (Right, Turn::Left) => Up,
-            (Right, Turn::Right) => Down,
-        }
-    }
-}
-
 fn moves(input: &str) -> IResult<&str, Vec<Move>> {
     many1(alt((
         alt((
             tag("L").map(|_| Move::Turn(Turn::Left)),
             tag("R").map(|_| Move::Turn(Turn::Right)),
         )),
-        complete::u32.map(|n| Move::Paces(n)),
+        complete::u32.map(Move::Paces),
     )))(input)
 }
 
-#[derive(Debug)]
-enum Cell {
+#[derive(Debug, Clone, Copy)]
+pub enum Cell {
     Space,
     Wall,
 }
 
 #[derive(Debug)]
-struct Field(HashMap<UVec2, Cell>);
+pub struct Field(HashMap<UVec2, Cell>);
 
 impl Field {
     fn get_row(&self, target_y: u32) -> Vec<(&UVec2, &Cell)> {
@@ -86,26 +60,27 @@ impl Field {
 }
 
 fn field(input: &str) -> IResult<&str, Field> {
-    let mut it = iterator(input, terminated(many1(one_of(" .#")), line_ending));
-    let parsed: HashMap<UVec2, Cell> = it
-        .enumerate()
-        .flat_map(|(y, row)| {
-            row.into_iter()
+    let cell = map(one_of(" .#"), |cell| match cell {
+        '.' => Some(Cell::Space),
+        '#' => Some(Cell::Wall),
+        _ => None,
+    });
+    map(lines(many1(cell)), |rows| {
+        Field(
+            rows.into_iter()
                 .enumerate()
-                .filter_map(move |(x, cell)| match cell {
-                    ' ' => None,
-                    '.' => Some((UVec2::new(x as u32, y as u32), Cell::Space)),
-                    '#' => Some((UVec2::new(x as u32, y as u32), Cell::Wall)),
-                    _ => panic!("invalid character"),
+                .flat_map(|(y, row)| {
+                    row.into_iter()
+                        .enumerate()
+                        .filter_map(move |(x, cell)| Some((UVec2::new(x as u32, y as u32), cell?)))
                 })
-        })
-        .collect();
-    let res: IResult<_, _> = it.finish();
-    res.map(|(input, _)| (input, Field(parsed)))
+                .collect(),
+        )
+    })(input)
 }
 
-fn field_and_moves(input: &str) -> IResult<&str, (Field, Vec<Move>)> {
-    separated_pair(field, line_ending, moves)(input)
+fn field_and_moves(input: &str) -> Result<(Field, Vec<Move>), Error> {
+    parse_all(separated_pair(field, blank_line, moves), input)
 }
 
 fn pace<'a>(
@@ -131,9 +106,9 @@ fn pace<'a>(
     }
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_, (field, moves)) = field_and_moves(input).unwrap();
-
+/// The password after following the `moves`, wrapping around to the other side
+/// of the map at its edges.
+fn flat_password(field: &Field, moves: &[Move]) -> Result<u32, Error> {
     let mut facing = Direction::Right;
     let starting_position = field
         .0
@@ -142,11 +117,11 @@ pub fn process_part1(input: &str) -> String {
             (y1, x1).cmp(&(y2, x2))
         })
         .next()
-        .unwrap();
+        .ok_or_else(|| Error::invalid("the map has no tiles"))?;
     let mut current_position = *starting_position.0;
 
     for m in moves {
-        match m {
+        match *m {
             Move::Paces(paces_to_move) => {
                 match facing {
                     Direction::Up => {
@@ -183,35 +158,229 @@ pub fn process_part1(input: &str) -> String {
                     }
                 };
             }
-            Move::Turn(turn) => facing = facing.turn(&turn),
+            Move::Turn(turn) => facing = facing.turn(turn),
         }
     }
 
-    (1000 * (current_position.y + 1)
-        + 4 * (current_position.x + 1)
-        + match facing {
-            Direction::Up => 3,
-            Direction::Down => 1,
-            Direction::Left => 2,
-            Direction::Right => 0,
-        })
-    .to_string()
+    Ok(1000 * (current_position.y + 1) + 4 * (current_position.x + 1) + facing.index() as u32)
+}
+
+/// Where a face of the net ends up once it's folded into a cube.
+///
+/// `normal` points out of the cube, and `right` and `down` are where the `x` and
+/// `y` axes of the net point on the face.
+#[derive(Debug, Clone, Copy)]
+struct Face {
+    origin: (i32, i32),
+    normal: IVec3,
+    right: IVec3,
+    down: IVec3,
+}
+
+impl Face {
+    /// The way `facing` points on the folded face.
+    fn vector(&self, facing: Direction) -> IVec3 {
+        match facing {
+            Direction::Right => self.right,
+            Direction::Left => -self.right,
+            Direction::Down => self.down,
+            Direction::Up => -self.down,
+        }
+    }
+
+    fn facing(&self, vector: IVec3) -> Direction {
+        Direction::ALL
+            .into_iter()
+            .find(|facing| self.vector(*facing) == vector)
+            .unwrap()
+    }
+
+    /// The face on the other side of the edge towards `facing`, which the net
+    /// folds down so that the way across the edge ends up pointing into the
+    /// cube.
+    fn fold(&self, facing: Direction, origin: (i32, i32)) -> Face {
+        let (right, down) = match facing {
+            Direction::Right => (-self.normal, self.down),
+            Direction::Left => (self.normal, self.down),
+            Direction::Down => (self.right, -self.normal),
+            Direction::Up => (self.right, self.normal),
+        };
+        Face {
+            origin,
+            normal: self.vector(facing),
+            right,
+            down,
+        }
+    }
+}
+
+/// A net folded into a cube.
+///
+/// Positions on the cube use doubled coordinates centred on the cube, so that
+/// the middles of tiles land on whole numbers: a face is `2 * size` across and
+/// the tiles on it are 2 apart.
+#[derive(Debug)]
+struct Cube {
+    size: i32,
+    faces: Vec<Face>,
+}
+
+impl Cube {
+    /// Works out the size of the faces from the number of tiles, then folds the
+    /// faces around the first one, one edge of the net at a time.
+    fn fold(field: &Field) -> Result<Cube, Error> {
+        let size = ((field.0.len() / 6) as f64).sqrt() as usize;
+        if size == 0 || 6 * size * size != field.0.len() {
+            return Err(Error::invalid("the map doesn't have six square faces"));
+        }
+        let size = size as i32;
+
+        let mut origins: Vec<(i32, i32)> = field
+            .0
+            .keys()
+            .map(|p| (p.x as i32 / size, p.y as i32 / size))
+            .sorted_by_key(|(x, y)| (*y, *x))
+            .dedup()
+            .collect();
+        if origins.len() != 6 {
+            return Err(Error::invalid("the map doesn't have six square faces"));
+        }
+
+        let first = Face {
+            origin: origins.remove(0),
+            normal: IVec3::Z,
+            right: IVec3::X,
+            down: IVec3::Y,
+        };
+        let mut faces = vec![first];
+        let mut queue = VecDeque::from([first]);
+        while let Some(face) = queue.pop_front() {
+            for facing in Direction::ALL {
+                let Some(index) = origins
+                    .iter()
+                    .position(|origin| *origin == facing.step(face.origin))
+                else {
+                    continue;
+                };
+                let next = face.fold(facing, origins.remove(index));
+                faces.push(next);
+                queue.push_back(next);
+            }
+        }
+
+        let normals: Vec<IVec3> = faces.iter().map(|face| face.normal).unique().collect();
+        if !origins.is_empty() || normals.len() != 6 {
+            return Err(Error::invalid("the map doesn't fold into a cube"));
+        }
+        Ok(Cube { size, faces })
+    }
+
+    fn face_at(&self, (x, y): (i32, i32)) -> &Face {
+        let origin = (x.div_euclid(self.size), y.div_euclid(self.size));
+        self.faces
+            .iter()
+            .find(|face| face.origin == origin)
+            .unwrap()
+    }
+
+    /// The tile one step from `position` towards `facing`, and the way it faces
+    /// after going over an edge of the cube if that's what it took.
+    fn step(
+        &self,
+        field: &Field,
+        position: (i32, i32),
+        facing: Direction,
+    ) -> ((i32, i32), Direction) {
+        let next = facing.step(position);
+        if next.0 >= 0
+            && next.1 >= 0
+            && field
+                .0
+                .contains_key(&UVec2::new(next.0 as u32, next.1 as u32))
+        {
+            return (next, facing);
+        }
+
+        let face = self.face_at(position);
+        let (i, j) = (
+            position.0 - face.origin.0 * self.size,
+            position.1 - face.origin.1 * self.size,
+        );
+        let point = face.normal * self.size
+            + face.right * (2 * i + 1 - self.size)
+            + face.down * (2 * j + 1 - self.size);
+
+        // Half a tile out to the edge, then half a tile down the other side.
+        let across = face.vector(facing);
+        let point = point + across - face.normal;
+        let next_face = self.faces.iter().find(|f| f.normal == across).unwrap();
+        let (i, j) = (
+            (next_face.right.dot(point) + self.size - 1) / 2,
+            (next_face.down.dot(point) + self.size - 1) / 2,
+        );
+        (
+            (
+                next_face.origin.0 * self.size + i,
+                next_face.origin.1 * self.size + j,
+            ),
+            next_face.facing(-face.normal),
+        )
+    }
+}
+
+/// The password after following the `moves` around the map folded into a cube.
+fn cube_password(field: &Field, moves: &[Move]) -> Result<i32, Error> {
+    let cube = Cube::fold(field)?;
+
+    let mut facing = Direction::Right;
+    let mut position = field
+        .0
+        .keys()
+        .map(|p| (p.x as i32, p.y as i32))
+        .min_by_key(|(x, y)| (*y, *x))
+        .unwrap();
+
+    for m in moves {
+        match *m {
+            Move::Paces(paces_to_move) => {
+                for _ in 0..paces_to_move {
+                    let (next, next_facing) = cube.step(field, position, facing);
+                    if let Cell::Wall = field.0[&UVec2::new(next.0 as u32, next.1 as u32)] {
+                        break;
+                    }
+                    position = next;
+                    facing = next_facing;
+                }
+            }
+            Move::Turn(turn) => facing = facing.turn(turn),
+        }
+    }
+
+    Ok(1000 * (position.1 + 1) + 4 * (position.0 + 1) + facing.index() as i32)
 }
 
-pub fn process_part2(input: &str) -> String {
-    "".to_string()
+aoc_utils::solution! {
+    Day22: 2022 day 22;
+    type Parsed<'a> = (Field, Vec<Move>);
+    parse: |input| field_and_moves(input);
+    part1: |(field, moves)| flat_password(field, moves);
+    part2: |(field, moves)| cube_password(field, moves);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
 
-    const EXAMPLE_INPUT: &str = include_str!("../test.txt");
     const EXAMPLE_INPUTS: [(&str, &str, &str); 0] = [];
 
+    fn example() -> &'static str {
+        inputs::example(2022, 22, 1).unwrap()
+    }
+
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "6032");
+        assert_eq!(process_part1(example()), "6032");
     }
 
     #[test]
@@ -222,9 +391,95 @@ mod tests {
     }
 
     #[test]
-    #[ignore]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "");
+        assert_eq!(process_part2(example()), "5031");
+    }
+
+    /// The 11 ways of unfolding a cube, each of which also shows up turned and
+    /// mirrored.
+    const NETS: [&str; 11] = [
+        "#...\n####\n#...",
+        "#...\n####\n.#..",
+        "#...\n####\n..#.",
+        "#...\n####\n...#",
+        ".#..\n####\n.#..",
+        ".#..\n####\n..#.",
+        "##..\n.###\n.#..",
+        "##..\n.###\n..#.",
+        "##..\n.###\n...#",
+        "##..\n.##.\n..##",
+        "###..\n..###",
+    ];
+
+    fn orientations(net: &str) -> Vec<Vec<Vec<char>>> {
+        let mut layout: Vec<Vec<char>> = net.lines().map(|line| line.chars().collect()).collect();
+        let mut orientations = vec![];
+        for _ in 0..4 {
+            let turned: Vec<Vec<char>> = (0..layout[0].len())
+                .map(|x| layout.iter().rev().map(|row| row[x]).collect())
+                .collect();
+            let mirrored = turned
+                .iter()
+                .map(|row| row.iter().rev().copied().collect())
+                .collect();
+            orientations.push(mirrored);
+            orientations.push(turned.clone());
+            layout = turned;
+        }
+        orientations
+    }
+
+    fn open_field(layout: &[Vec<char>], size: u32) -> Field {
+        let mut field = HashMap::new();
+        for (y, row) in layout.iter().enumerate() {
+            for (x, _) in row.iter().enumerate().filter(|(_, c)| **c == '#') {
+                for (i, j) in (0..size).cartesian_product(0..size) {
+                    let tile = UVec2::new(x as u32 * size + i, y as u32 * size + j);
+                    field.insert(tile, Cell::Space);
+                }
+            }
+        }
+        Field(field)
+    }
+
+    #[test]
+    fn every_net_folds_into_a_cube() {
+        let size = 3;
+        for net in NETS {
+            for layout in orientations(net) {
+                let field = open_field(&layout, size as u32);
+                let cube = Cube::fold(&field).unwrap();
+                for (tile, facing) in field.0.keys().cartesian_product(Direction::ALL) {
+                    let start = (tile.x as i32, tile.y as i32);
+
+                    // Going all the way around the cube ends up back at the start.
+                    let (mut position, mut heading) = (start, facing);
+                    for _ in 0..4 * size {
+                        (position, heading) = cube.step(&field, position, heading);
+                    }
+                    assert_eq!((position, heading), (start, facing), "{layout:?}");
+
+                    // Every step can be undone by turning around.
+                    let (next, heading) = cube.step(&field, start, facing);
+                    let (back, heading) = cube.step(&field, next, heading.reverse());
+                    assert_eq!((back, heading.reverse()), (start, facing), "{layout:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_maps_that_are_not_cubes() {
+        let field = open_field(&[vec!['#'; 6]], 2);
+        assert_eq!(
+            Cube::fold(&field).unwrap_err(),
+            Error::invalid("the map doesn't fold into a cube")
+        );
+        let field = open_field(&[vec!['#'; 5]], 2);
+        assert_eq!(
+            Cube::fold(&field).unwrap_err(),
+            Error::invalid("the map doesn't have six square faces")
+        );
     }
 
     #[test]
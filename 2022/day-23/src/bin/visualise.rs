@@ -0,0 +1,58 @@
+//! Spreads the elves out until none of them moves, and writes every round to
+//! `day-23.gif` or the path given as the first argument.
+
+use std::env;
+
+use aoc_utils::{
+    inputs,
+    render::{Animation, Image, Rgb},
+};
+use day_2022_23::{elves, spread_round};
+use glam::IVec2;
+
+const GROUND: Rgb = [40, 70, 40];
+const ELF: Rgb = [220, 70, 60];
+const FRAMES: usize = 200;
+
+fn main() {
+    let path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "day-23.gif".to_string());
+
+    let input = inputs::input(2022, 23).unwrap_or_else(|err| panic!("{err}"));
+    let mut rounds = vec![elves(input).unwrap_or_else(|err| panic!("{err}"))];
+    loop {
+        let last = &rounds[rounds.len() - 1];
+        let next = spread_round(last, rounds.len() - 1);
+        if &next == last {
+            break;
+        }
+        rounds.push(next);
+    }
+
+    // Every frame covers everything that will ever be drawn, so they line up.
+    let points = || rounds.iter().flatten().copied();
+    let min = points().reduce(IVec2::min).expect("there are no elves");
+    let max = points().reduce(IVec2::max).expect("there are no elves");
+    let size = max - min + IVec2::ONE;
+
+    let mut animation = Animation::new(4);
+    let per_frame = rounds.len().div_ceil(FRAMES).max(1);
+    let last = rounds.len() - 1;
+    for (_, elves) in rounds
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i % per_frame == 0 || i == last)
+    {
+        let mut image = Image::filled(size.x as usize, size.y as usize, GROUND);
+        for &elf in elves {
+            let position = elf - min;
+            image.set((position.x as usize, position.y as usize), ELF);
+        }
+        animation.push(image.scaled(3));
+    }
+    animation
+        .write_gif(&path)
+        .unwrap_or_else(|err| panic!("cannot write {path}: {err}"));
+    println!("{} frames written to {path}", animation.len());
+}
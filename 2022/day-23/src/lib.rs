@@ -1,197 +1,139 @@
 use std::collections::{HashMap, HashSet};
 
+use aoc_utils::{
+    parse::{lines, parse_all},
+    Error,
+};
 use glam::IVec2;
 use itertools::Itertools;
-use itertools::MinMaxResult::MinMax;
-use nom::{
-    branch::alt,
-    character::complete::{line_ending, one_of},
-    combinator::{eof, iterator},
-    multi::many1,
-    sequence::terminated,
-    IResult,
-};
-
-fn elves(input: &str) -> IResult<&str, HashSet<IVec2>> {
-    let mut it = iterator(
-        input,
-        terminated(many1(one_of(".#")), alt((line_ending, eof))),
-    );
-    let elves = it
+use nom::{character::complete::one_of, combinator::map, multi::many1};
+
+/// Where the elves are in the scan.
+pub fn elves(input: &str) -> Result<HashSet<IVec2>, Error> {
+    let elf = map(one_of(".#"), |c| c == '#');
+    let rows = parse_all(lines(many1(elf)), input)?;
+    Ok(rows
+        .into_iter()
         .enumerate()
-        .flat_map(|(y, line)| {
-            line.into_iter()
+        .flat_map(|(y, row)| {
+            row.into_iter()
                 .enumerate()
-                .filter_map(move |(x, c)| match c {
-                    '.' => None,
-                    '#' => Some(IVec2::new(x as i32, y as i32)),
-                    _ => panic!("unknown char"),
-                })
+                .filter(|(_, elf)| *elf)
+                .map(move |(x, _)| IVec2::new(x as i32, y as i32))
         })
-        .collect::<HashSet<IVec2>>();
-    let res: IResult<_, _> = it.finish();
-    Ok((res.unwrap().0, elves))
+        .collect())
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_, mut elves) = elves(input).unwrap();
-    let checks = vec![
-        [IVec2::new(-1, -1), IVec2::new(0, -1), IVec2::new(1, -1)],
-        [IVec2::new(-1, 1), IVec2::new(0, 1), IVec2::new(1, 1)],
-        [IVec2::new(-1, -1), IVec2::new(-1, 0), IVec2::new(-1, 1)],
-        [IVec2::new(1, -1), IVec2::new(1, 0), IVec2::new(1, 1)],
-    ];
-    let checks_iter = checks.iter().cycle();
-
-    for i in 0..10 {
-        let local_checks = checks_iter.clone().skip(i).take(4);
-        let mut proposed_moves: HashMap<IVec2, Vec<IVec2>> = HashMap::new();
-
-        for elf in elves.iter() {
-            // check for all empty around elf
-            if local_checks
-                .clone()
-                .flat_map(|v| v.iter().map(|vec| *vec + *elf))
-                .unique()
-                .all(|value| elves.get(&value).is_none())
-            {
-                proposed_moves
-                    .entry(*elf)
-                    // .and_modify(|value| value.push(*elf))
-                    .or_insert(vec![*elf]);
-                continue;
-            }
-            // check for a possible move in a direction
-            let possible_move = local_checks.clone().find_map(|checks| {
-                checks
-                    .iter()
-                    .all(|position| elves.get(&(*position + *elf)).is_none())
-                    .then_some(checks[1] + *elf)
-            });
-            if let Some(r#move) = possible_move {
-                proposed_moves
-                    .entry(r#move)
-                    .and_modify(|value| value.push(*elf))
-                    .or_insert(vec![*elf]);
+const CHECKS: [[IVec2; 3]; 4] = [
+    [IVec2::new(-1, -1), IVec2::new(0, -1), IVec2::new(1, -1)],
+    [IVec2::new(-1, 1), IVec2::new(0, 1), IVec2::new(1, 1)],
+    [IVec2::new(-1, -1), IVec2::new(-1, 0), IVec2::new(-1, 1)],
+    [IVec2::new(1, -1), IVec2::new(1, 0), IVec2::new(1, 1)],
+];
+
+/// Where the elves are after round `i`, counting from 0, which decides the
+/// direction they look in first.
+pub fn spread_round(elves: &HashSet<IVec2>, i: usize) -> HashSet<IVec2> {
+    let local_checks = CHECKS.iter().cycle().skip(i).take(4);
+    let mut proposed_moves: HashMap<IVec2, Vec<IVec2>> = HashMap::new();
+
+    for elf in elves.iter() {
+        // check for all empty around elf
+        if local_checks
+            .clone()
+            .flat_map(|v| v.iter().map(|vec| *vec + *elf))
+            .unique()
+            .all(|value| !elves.contains(&value))
+        {
+            proposed_moves
+                .entry(*elf)
+                // .and_modify(|value| value.push(*elf))
+                .or_insert(vec![*elf]);
+            continue;
+        }
+        // check for a possible move in a direction
+        let possible_move = local_checks.clone().find_map(|checks| {
+            checks
+                .iter()
+                .all(|position| !elves.contains(&(*position + *elf)))
+                .then_some(checks[1] + *elf)
+        });
+        if let Some(r#move) = possible_move {
+            proposed_moves
+                .entry(r#move)
+                .and_modify(|value| value.push(*elf))
+                .or_insert(vec![*elf]);
+        } else {
+            proposed_moves
+                .entry(*elf)
+                // .and_modify(|value| value.push(*elf))
+                .or_insert(vec![*elf]);
+        }
+    }
+
+    proposed_moves
+        .into_iter()
+        .flat_map(|(desired_position, elves_to_move)| {
+            if elves_to_move.len() == 1 {
+                vec![desired_position]
             } else {
-                proposed_moves
-                    .entry(*elf)
-                    // .and_modify(|value| value.push(*elf))
-                    .or_insert(vec![*elf]);
+                elves_to_move
             }
-        }
+        })
+        .collect()
+}
 
-        elves = proposed_moves
-            .into_iter()
-            .flat_map(|(desired_position, elves_to_move)| {
-                if elves_to_move.len() == 1 {
-                    vec![desired_position]
-                } else {
-                    elves_to_move
-                }
-            })
-            .collect::<HashSet<IVec2>>();
-    }
+/// The empty ground in the smallest rectangle around the elves after 10 rounds.
+fn empty_ground(elves: &HashSet<IVec2>) -> Result<usize, Error> {
+    let elves = (0..10).fold(elves.clone(), |elves, i| spread_round(&elves, i));
 
-    let minmax_x = elves.iter().map(|v| v.x).minmax();
-    let minmax_y = elves.iter().map(|v| v.y).minmax();
+    let minmax_x = elves.iter().map(|v| v.x).minmax().into_option();
+    let minmax_y = elves.iter().map(|v| v.y).minmax().into_option();
 
-    let (MinMax(x1,x2), MinMax(y1,y2)) = (minmax_x, minmax_y) else {
-        panic!("")
+    let (Some((x1, x2)), Some((y1, y2))) = (minmax_x, minmax_y) else {
+        return Err(Error::invalid("there are no elves"));
     };
 
     let min_box_size = (x2 - x1 + 1) * (y2 - y1 + 1);
-    (min_box_size as usize - elves.len()).to_string()
+    Ok(min_box_size as usize - elves.len())
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (_, mut elves) = elves(input).unwrap();
-    let checks = vec![
-        [IVec2::new(-1, -1), IVec2::new(0, -1), IVec2::new(1, -1)],
-        [IVec2::new(-1, 1), IVec2::new(0, 1), IVec2::new(1, 1)],
-        [IVec2::new(-1, -1), IVec2::new(-1, 0), IVec2::new(-1, 1)],
-        [IVec2::new(1, -1), IVec2::new(1, 0), IVec2::new(1, 1)],
-    ];
-    let checks_iter = checks.iter().cycle();
-
+/// The first round in which no elf moves.
+fn still_round(elves: &HashSet<IVec2>) -> usize {
+    let mut elves = elves.clone();
     let mut rounds = 0;
-
-    for i in 0.. {
-        let local_checks = checks_iter.clone().skip(i).take(4);
-        let mut proposed_moves: HashMap<IVec2, Vec<IVec2>> = HashMap::new();
-
-        for elf in elves.iter() {
-            // check for all empty around elf
-            if local_checks
-                .clone()
-                .flat_map(|v| v.iter().map(|vec| *vec + *elf))
-                .unique()
-                .all(|value| elves.get(&value).is_none())
-            {
-                proposed_moves
-                    .entry(*elf)
-                    // .and_modify(|value| value.push(*elf))
-                    .or_insert(vec![*elf]);
-                continue;
-            }
-            // check for a possible move in a direction
-            let possible_move = local_checks.clone().find_map(|checks| {
-                checks
-                    .iter()
-                    .all(|position| elves.get(&(*position + *elf)).is_none())
-                    .then_some(checks[1] + *elf)
-            });
-            if let Some(r#move) = possible_move {
-                proposed_moves
-                    .entry(r#move)
-                    .and_modify(|value| value.push(*elf))
-                    .or_insert(vec![*elf]);
-            } else {
-                proposed_moves
-                    .entry(*elf)
-                    // .and_modify(|value| value.push(*elf))
-                    .or_insert(vec![*elf]);
-            }
-        }
-
-        let new_elves = proposed_moves
-            .into_iter()
-            .flat_map(|(desired_position, elves_to_move)| {
-                if elves_to_move.len() == 1 {
-                    vec![desired_position]
-                } else {
-                    elves_to_move
-                }
-            })
-            .collect::<HashSet<IVec2>>();
-
+    loop {
+        let new_elves = spread_round(&elves, rounds);
+        rounds += 1;
         if elves == new_elves {
-            rounds = i;
-            break;
-        } else {
-            elves = new_elves;
+            return rounds;
         }
+        elves = new_elves;
     }
+}
 
-    (rounds + 1).to_string()
+aoc_utils::solution! {
+    Day23: 2022 day 23;
+    type Parsed<'a> = HashSet<IVec2>;
+    parse: |input| elves(input);
+    part1: |elves| empty_ground(elves);
+    part2: |elves| Ok(still_round(elves));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
+
+    fn example() -> &'static str {
+        inputs::example(2022, 23, 1).unwrap()
+    }
 
-    const EXAMPLE_INPUT: &str = "....#..
-..###.#
-#...#.#
-.#...##
-#.###..
-##.#.##
-.#..#..";
     const EXAMPLE_INPUTS: [(&str, &str, &str); 0] = [];
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "110");
+        assert_eq!(process_part1(example()), "110");
     }
 
     #[test]
@@ -203,7 +145,7 @@ mod tests {
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "20");
+        assert_eq!(process_part2(example()), "20");
     }
 
     #[test]
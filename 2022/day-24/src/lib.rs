@@ -1,122 +1,96 @@
 use std::collections::HashMap;
 
+use aoc_utils::{
+    number_theory::lcm,
+    parse::{lines, parse_all},
+    Direction, Error,
+};
 use glam::IVec2;
 use itertools::Itertools;
 use nom::{
     branch::alt,
-    character::complete::{char, line_ending},
-    combinator::{eof, iterator},
+    character::complete::{char, one_of},
+    combinator::map_opt,
     multi::many1,
-    sequence::terminated,
-    IResult, Parser,
+    Parser,
 };
-use petgraph::{algo::dijkstra, prelude::DiGraphMap};
 
 #[derive(Debug, PartialEq, Clone)]
-enum Cell {
+pub enum Cell {
     Wall,
     Blizzard(Vec<Direction>),
     Space,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum Direction {
-    Left,
-    Right,
-    Up,
-    Down,
-}
-
-fn grid(input: &str) -> IResult<&str, HashMap<IVec2, Cell>> {
-    let mut it = iterator(
-        input,
-        terminated(
-            many1(alt((
-                char('.').map(|_| Cell::Space),
-                char('#').map(|_| Cell::Wall),
-                char('v').map(|_| Cell::Blizzard(vec![Direction::Down])),
-                char('^').map(|_| Cell::Blizzard(vec![Direction::Up])),
-                char('<').map(|_| Cell::Blizzard(vec![Direction::Left])),
-                char('>').map(|_| Cell::Blizzard(vec![Direction::Right])),
-            ))),
-            alt((line_ending, eof)),
-        ),
-    );
-    let cells = it
+fn grid(input: &str) -> Result<HashMap<IVec2, Cell>, Error> {
+    let blizzard = map_opt(one_of("^>v<"), |c| {
+        Direction::from_char(c).map(|direction| Cell::Blizzard(vec![direction]))
+    });
+    let cell = alt((
+        char('.').map(|_| Cell::Space),
+        char('#').map(|_| Cell::Wall),
+        blizzard,
+    ));
+    let rows = parse_all(lines(many1(cell)), input)?;
+    if !rows.iter().map(Vec::len).all_equal() {
+        return Err(Error::invalid("the rows of the valley differ in length"));
+    }
+    if rows.len() < 3 || rows[0].len() < 3 {
+        return Err(Error::invalid("the valley has no room inside its walls"));
+    }
+    let size = IVec2::new(rows[0].len() as i32, rows.len() as i32);
+    let grid: HashMap<IVec2, Cell> = rows
+        .into_iter()
         .enumerate()
         .flat_map(|(y, line)| {
             line.into_iter()
                 .enumerate()
                 .map(move |(x, cell)| (IVec2::new(x as i32, y as i32), cell))
         })
-        .collect::<HashMap<IVec2, Cell>>();
-    let res: IResult<_, _> = it.finish();
-    Ok((res.unwrap().0, cells))
+        .collect();
+    let in_wall = |p: &IVec2| p.x == 0 || p.y == 0 || p.x == size.x - 1 || p.y == size.y - 1;
+    match grid
+        .iter()
+        .find(|(position, cell)| matches!(cell, Cell::Blizzard(_)) && in_wall(position))
+    {
+        Some((position, _)) => Err(Error::invalid(format!(
+            "the blizzard at {position} is outside the valley"
+        ))),
+        None => Ok(grid),
+    }
 }
 
+/// The width and height of the valley inside its walls.
 fn dimensions(grid: &HashMap<IVec2, Cell>) -> IVec2 {
-    let x_max = grid.iter().map(|(v, _)| v.x).max().unwrap();
-    let y_max = grid.iter().map(|(v, _)| v.y).max().unwrap();
-    IVec2::new(x_max - 1, y_max - 1)
+    grid.keys()
+        .fold(IVec2::ZERO, |max, &position| max.max(position))
+        - IVec2::ONE
 }
 
 fn move_blizzard(
-    grid: &HashMap<IVec2, Cell>,
     new_grid: &mut HashMap<IVec2, Cell>,
+    inside: IVec2,
     position: &IVec2,
     direction: &Direction,
 ) {
-    let movement = match direction {
-        Direction::Left => IVec2::new(-1, 0),
-        Direction::Right => IVec2::new(1, 0),
-        Direction::Up => IVec2::new(0, -1),
-        Direction::Down => IVec2::new(0, 1),
-    };
-    let desired_position = *position + movement;
-    match grid.get(&desired_position) {
-        Some(Cell::Wall) => {
-            let wall_position = grid
-                .iter()
-                .find(|(ivec, cell)| {
-                    let is_wall = cell == &&Cell::Wall;
-                    let is_in_row_or_column = match direction {
-                        Direction::Left => ivec.x > position.x && ivec.y == position.y,
-                        Direction::Right => ivec.x < position.x && ivec.y == position.y,
-                        Direction::Up => ivec.y > position.y && ivec.x == position.x,
-                        Direction::Down => ivec.y < position.y && ivec.x == position.x,
-                    };
-                    is_wall && is_in_row_or_column
-                })
-                .unwrap()
-                .0;
-            let next_to_wall = *wall_position + movement;
-            new_grid
-                .entry(next_to_wall)
-                .and_modify(|cell| {
-                    if let Cell::Blizzard(directions) = cell {
-                        directions.push(*direction);
-                    }
-                })
-                .or_insert(Cell::Blizzard(vec![*direction]));
-        }
-        Some(_) => {
-            new_grid
-                .entry(desired_position)
-                .and_modify(|cell| {
-                    if let Cell::Blizzard(directions) = cell {
-                        directions.push(*direction);
-                    }
-                })
-                .or_insert(Cell::Blizzard(vec![*direction]));
-        }
-        None => {
-            panic!("should not be none")
-        }
-    }
+    // A blizzard leaving the valley comes back in on the opposite side, so it
+    // wraps around the space inside the walls, which starts at (1, 1).
+    let moved = *position - IVec2::ONE + IVec2::from(direction.delta());
+    let next_position =
+        IVec2::new(moved.x.rem_euclid(inside.x), moved.y.rem_euclid(inside.y)) + IVec2::ONE;
+    new_grid
+        .entry(next_position)
+        .and_modify(|cell| {
+            if let Cell::Blizzard(directions) = cell {
+                directions.push(*direction);
+            }
+        })
+        .or_insert(Cell::Blizzard(vec![*direction]));
 }
 
 fn step(grid: &HashMap<IVec2, Cell>) -> HashMap<IVec2, Cell> {
     let mut new_grid = HashMap::new();
+    let inside = dimensions(grid);
 
     let blizzards = grid.iter().filter_map(|(pos, cell)| match cell {
         Cell::Wall => None,
@@ -125,204 +99,125 @@ fn step(grid: &HashMap<IVec2, Cell>) -> HashMap<IVec2, Cell> {
     });
     for (position, directions) in blizzards {
         for direction in directions {
-            move_blizzard(grid, &mut new_grid, position, direction);
+            move_blizzard(&mut new_grid, inside, position, direction);
         }
     }
 
     // copy walls
-    grid.iter().for_each(|(pos, cell)| match cell {
-        Cell::Wall => {
+    grid.iter().for_each(|(pos, cell)| {
+        if cell == &Cell::Wall {
             new_grid.insert(*pos, cell.clone());
         }
-        _ => (),
     });
 
     // spaces
-    let total_size = dimensions(grid) + IVec2::new(2, 2);
+    let total_size = inside + IVec2::new(2, 2);
     for (y, x) in (0..total_size.y).cartesian_product(0..total_size.x) {
         let pos = IVec2::new(x, y);
-        if new_grid.get(&pos).is_none() {
-            new_grid.insert(pos, Cell::Space);
-        }
+        new_grid.entry(pos).or_insert(Cell::Space);
     }
 
     new_grid
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_, mut grid) = grid(input).unwrap();
-    let grid_dimensions = dimensions(&grid);
-    let step_cycle_number = [
-        (grid_dimensions.x..).step_by(grid_dimensions.x as usize),
-        (grid_dimensions.y..).step_by(grid_dimensions.y as usize),
-    ]
-    .into_iter()
-    .kmerge()
-    .tuple_windows()
-    .find(|(a, b)| a == b)
-    .unwrap()
-    .0;
-    let end_position = grid
-        .iter()
-        .filter_map(|(pos, cell)| match cell {
-            Cell::Wall => None,
-            Cell::Blizzard(_) => None,
-            Cell::Space => Some(pos),
-        })
-        .max_by(|IVec2 { x: x1, y: y1 }, IVec2 { x: x2, y: y2 }| (y1, x1).cmp(&(y2, x2)))
-        .unwrap()
-        .clone();
+/// Every blizzard configuration until they repeat, indexed by minute.
+fn blizzard_cycle(mut grid: HashMap<IVec2, Cell>) -> Result<Vec<HashMap<IVec2, Cell>>, Error> {
+    let inside = dimensions(&grid);
+    let step_cycle_number = lcm(inside.x as usize, inside.y as usize)
+        .ok_or_else(|| Error::invalid("the valley is too large for its blizzards to repeat"))?;
 
-    let mut edges: Vec<((i32, i32, i32), (i32, i32, i32))> = vec![];
-    for i in 0..step_cycle_number {
+    let mut grids = Vec::with_capacity(step_cycle_number);
+    for _ in 0..step_cycle_number {
         let next_grid = step(&grid);
-        let origin_spaces = grid.iter().filter_map(|(pos, cell)| match cell {
-            Cell::Wall => None,
-            Cell::Blizzard(_) => None,
-            Cell::Space => Some(pos),
-        });
-
-        for origin_position in origin_spaces {
-            let possibe_next_positions = vec![(-1, 0), (0, 0), (1, 0), (0, -1), (0, 1)]
-                .into_iter()
-                .filter_map(|(y, x)| {
-                    let next_position = IVec2::new(x, y) + *origin_position;
-                    if let Some(Cell::Space) = next_grid.get(&next_position) {
-                        Some(next_position)
-                    } else {
-                        None
-                    }
-                });
-            for pos in possibe_next_positions {
-                edges.push((
-                    (origin_position.x, origin_position.y, i),
-                    (
-                        pos.x,
-                        pos.y,
-                        if i + 1 == step_cycle_number { 0 } else { i + 1 },
-                    ),
-                ));
-            }
-        }
-
+        grids.push(grid);
         grid = next_grid;
     }
-
-    let graph = DiGraphMap::<(i32, i32, i32), ()>::from_edges(edges);
-    let result = dijkstra(&graph, (1, 0, 0), None, |_| 1);
-    result
-        .iter()
-        .filter_map(|(end, value)| {
-            if end.0 == end_position.x && end.1 == end_position.y {
-                Some(value)
-            } else {
-                None
-            }
-        })
-        .min()
-        .unwrap()
-        .to_string()
+    Ok(grids)
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (_, mut grid) = grid(input).unwrap();
-    let grid_dimensions = dimensions(&grid);
-    let step_cycle_number = [
-        (grid_dimensions.x..).step_by(grid_dimensions.x as usize),
-        (grid_dimensions.y..).step_by(grid_dimensions.y as usize),
-    ]
-    .into_iter()
-    .kmerge()
-    .tuple_windows()
-    .find(|(a, b)| a == b)
-    .unwrap()
-    .0;
-    let end_position = grid
-        .iter()
+fn end_position(grid: &HashMap<IVec2, Cell>) -> Result<IVec2, Error> {
+    grid.iter()
         .filter_map(|(pos, cell)| match cell {
             Cell::Wall => None,
             Cell::Blizzard(_) => None,
             Cell::Space => Some(pos),
         })
         .max_by(|IVec2 { x: x1, y: y1 }, IVec2 { x: x2, y: y2 }| (y1, x1).cmp(&(y2, x2)))
-        .unwrap()
-        .clone();
-
-    let mut edges: Vec<((i32, i32, i32), (i32, i32, i32))> = vec![];
-    for i in 0..step_cycle_number {
-        let next_grid = step(&grid);
-        let origin_spaces = grid.iter().filter_map(|(pos, cell)| match cell {
-            Cell::Wall => None,
-            Cell::Blizzard(_) => None,
-            Cell::Space => Some(pos),
-        });
+        .copied()
+        .ok_or_else(|| Error::invalid("the valley has no open ground"))
+}
 
-        for origin_position in origin_spaces {
-            let possibe_next_positions = vec![(-1, 0), (0, 0), (1, 0), (0, -1), (0, 1)]
+/// Minutes needed to walk from `start` to `end` when setting off at minute `departure`.
+fn shortest_trip(
+    grids: &[HashMap<IVec2, Cell>],
+    start: IVec2,
+    end: IVec2,
+    departure: usize,
+) -> Result<usize, Error> {
+    aoc_utils::dijkstra(
+        [(start, departure % grids.len())].into_iter(),
+        |&(position, minute)| {
+            let next_minute = (minute + 1) % grids.len();
+            let next_grid = &grids[next_minute];
+            [(-1, 0), (0, 0), (1, 0), (0, -1), (0, 1)]
                 .into_iter()
-                .filter_map(|(y, x)| {
-                    let next_position = IVec2::new(x, y) + *origin_position;
-                    if let Some(Cell::Space) = next_grid.get(&next_position) {
-                        Some(next_position)
-                    } else {
-                        None
-                    }
-                });
-            for pos in possibe_next_positions {
-                edges.push((
-                    (origin_position.x, origin_position.y, i),
-                    (
-                        pos.x,
-                        pos.y,
-                        if i + 1 == step_cycle_number { 0 } else { i + 1 },
-                    ),
-                ));
-            }
-        }
+                .map(move |(y, x)| IVec2::new(x, y) + position)
+                .filter(|next_position| next_grid.get(next_position) == Some(&Cell::Space))
+                .map(move |next_position| ((next_position, next_minute), 1))
+                .collect_vec()
+        },
+        |(position, _)| *position == end,
+    )
+    .ok_or_else(|| Error::invalid(format!("{end} can't be reached from {start}")))
+}
 
-        grid = next_grid;
+/// Every blizzard configuration until they repeat, and where the valley's exit is.
+fn valley(input: &str) -> Result<(Vec<HashMap<IVec2, Cell>>, IVec2), Error> {
+    let grid = grid(input)?;
+    if grid.get(&IVec2::new(1, 0)) != Some(&Cell::Space) {
+        return Err(Error::invalid("the valley has no entrance at (1, 0)"));
     }
+    let end_position = end_position(&grid)?;
+    Ok((blizzard_cycle(grid)?, end_position))
+}
 
-    let graph = DiGraphMap::<(i32, i32, i32), ()>::from_edges(edges);
-    let result = dijkstra(&graph, (1, 0, 0), None, |_| 1);
-    let to_goal = result
-        .iter()
-        .filter(|(end, _)| end.0 == end_position.x && end.1 == end_position.y)
-        .min_by_key(|(_, value)| *value)
-        .unwrap();
+/// Minutes needed to get to the exit, back to the entrance for the snacks and
+/// out again.
+fn there_and_back_again(
+    grids: &[HashMap<IVec2, Cell>],
+    end_position: IVec2,
+) -> Result<usize, Error> {
+    let start_position = IVec2::new(1, 0);
 
-    let result = dijkstra(&graph, *to_goal.0, None, |_| 1);
-    let back_to_camp = result
-        .iter()
-        .filter(|(end, _)| end.0 == 1 && end.1 == 0)
-        .min_by_key(|(_, value)| *value)
-        .unwrap();
+    let to_goal = shortest_trip(grids, start_position, end_position, 0)?;
+    let back_to_camp = shortest_trip(grids, end_position, start_position, to_goal)?;
+    let back_to_goal = shortest_trip(grids, start_position, end_position, to_goal + back_to_camp)?;
 
-    let result = dijkstra(&graph, *back_to_camp.0, None, |_| 1);
-    let back_to_goal = result
-        .iter()
-        .filter(|(end, _)| end.0 == end_position.x && end.1 == end_position.y)
-        .min_by_key(|(_, value)| *value)
-        .unwrap();
+    Ok(to_goal + back_to_camp + back_to_goal)
+}
 
-    (*to_goal.1 + *back_to_camp.1 + *back_to_goal.1).to_string()
+aoc_utils::solution! {
+    Day24: 2022 day 24;
+    type Parsed<'a> = (Vec<HashMap<IVec2, Cell>>, IVec2);
+    parse: |input| valley(input);
+    part1: |(grids, end)| shortest_trip(grids, IVec2::new(1, 0), *end, 0);
+    part2: |(grids, end)| there_and_back_again(grids, *end);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
+
+    fn example() -> &'static str {
+        inputs::example(2022, 24, 1).unwrap()
+    }
 
-    const EXAMPLE_INPUT: &str = "#.######
-#>>.<^<#
-#.<..<<#
-#>v.><>#
-#<^v^^>#
-######.#";
     const EXAMPLE_INPUTS: [(&str, &str, &str); 0] = [];
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "18");
+        assert_eq!(process_part1(example()), "18");
     }
 
     #[test]
@@ -334,7 +229,26 @@ mod tests {
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "54");
+        assert_eq!(process_part2(example()), "54");
+    }
+
+    #[test]
+    fn rejects_malformed_valleys() {
+        for (input, message) in [
+            (
+                "#.####\n#.>.\n####.#",
+                "the rows of the valley differ in length",
+            ),
+            ("#.#\n#.#", "the valley has no room inside its walls"),
+            (
+                "#.##\n#..>\n##.#",
+                "the blizzard at [3, 1] is outside the valley",
+            ),
+            ("##.#\n#..#\n#.##", "the valley has no entrance at (1, 0)"),
+        ] {
+            let error = try_process_part1(input).unwrap_err();
+            assert!(error.to_string().contains(message), "{error}");
+        }
     }
 
     #[test]
@@ -1,6 +1,10 @@
 use std::iter::Sum;
 use std::str::FromStr;
 
+use aoc_utils::{
+    parse::{lines, parse_all},
+    Error,
+};
 use itertools::unfold;
 use nom::character::complete::char;
 use nom::Parser;
@@ -17,7 +21,7 @@ fn snafu_digits(input: &str) -> IResult<&str, Vec<i8>> {
 }
 
 fn snafu(input: &str) -> IResult<&str, Snafu> {
-    let (_, digits) = snafu_digits(input)?;
+    let (input, digits) = snafu_digits(input)?;
     let decimal = digits
         .iter()
         .rev()
@@ -30,19 +34,15 @@ fn snafu(input: &str) -> IResult<&str, Snafu> {
 }
 
 #[derive(Debug, PartialEq)]
-struct Snafu {
+pub struct Snafu {
     decimal: i64,
 }
 
 impl FromStr for Snafu {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, snafu) = snafu(s).map_err(|err| {
-            dbg!(err);
-            "unparsable"
-        })?;
-        Ok(snafu)
+        parse_all(snafu, s)
     }
 }
 
@@ -86,43 +86,31 @@ impl Snafu {
     }
 }
 
-impl Sum<Snafu> for Snafu {
-    fn sum<I: Iterator<Item = Snafu>>(iter: I) -> Self {
+impl<'a> Sum<&'a Snafu> for Snafu {
+    fn sum<I: Iterator<Item = &'a Snafu>>(iter: I) -> Self {
         Snafu {
             decimal: iter.map(|s| s.decimal).sum(),
         }
     }
 }
 
-pub fn process_part1(input: &str) -> String {
-    let sum = input
-        .split("\n")
-        .map(|s| s.parse::<Snafu>().unwrap())
-        .sum::<Snafu>();
-    sum.to_snafu_string()
-}
-
-pub fn process_part2(input: &str) -> String {
-    "".to_string()
+aoc_utils::solution! {
+    Day25: 2022 day 25;
+    parts: [One];
+    type Parsed<'a> = Vec<Snafu>;
+    parse: |input| parse_all(lines(snafu), input);
+    part1: |numbers| Ok(numbers.iter().sum::<Snafu>().to_snafu_string());
+    part2: |_| Ok("");
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
 
-    const EXAMPLE_INPUT: &str = "1=-0-2
-12111
-2=0=
-21
-2=01
-111
-20012
-112
-1=-1=
-1-12
-12
-1=
-122";
+    fn example() -> &'static str {
+        inputs::example(2022, 25, 1).unwrap()
+    }
 
     #[test]
     fn parser_works() {
@@ -143,12 +131,12 @@ mod tests {
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "4890");
+        assert_eq!(process_part1(example()), "2=-1=0");
     }
 
     #[test]
     #[ignore]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "");
+        assert_eq!(process_part2(example()), "");
     }
 }
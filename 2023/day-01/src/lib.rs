@@ -1,29 +1,31 @@
-pub fn process_part1(input: &str) -> String {
-    input.lines().map(process_line).sum::<u32>().to_string()
-}
-
-fn process_line(line: &str) -> u32 {
-    let digits = line
-        .chars()
-        .filter_map(|c| c.to_digit(10))
-        .collect::<Vec<u32>>();
-    digits[0] * 10 + digits.last().unwrap()
-}
+use aoc_utils::{parse::map_lines, Error};
 
-pub fn process_part2(input: &str) -> String {
-    input
-        .lines()
-        .map(process_line_part2)
-        .sum::<u32>()
-        .to_string()
+/// A digit on a line, either written as one or spelled out in letters.
+#[derive(Debug)]
+pub struct Digit {
+    value: u32,
+    spelled_out: bool,
 }
 
-fn process_line_part2(line: &str) -> u32 {
+/// Every digit on a line, of which there has to be at least one.
+fn parse_line(line: &str) -> Result<Vec<Digit>, &'static str> {
     let digits = line
         .char_indices()
-        .filter_map(|(i, c)| c.to_digit(10).or_else(|| parse_digit_letters(&line[i..])))
-        .collect::<Vec<u32>>();
-    digits[0] * 10 + digits.last().unwrap()
+        .filter_map(|(i, c)| match c.to_digit(10) {
+            Some(value) => Some(Digit {
+                value,
+                spelled_out: false,
+            }),
+            None => parse_digit_letters(&line[i..]).map(|value| Digit {
+                value,
+                spelled_out: true,
+            }),
+        })
+        .collect::<Vec<Digit>>();
+    if digits.is_empty() {
+        return Err("the line has no digits");
+    }
+    Ok(digits)
 }
 
 fn parse_digit_letters(input: &str) -> Option<u32> {
@@ -41,18 +43,52 @@ fn parse_digit_letters(input: &str) -> Option<u32> {
     }
 }
 
+/// The first and last digit of a line, read as a two-digit number.
+fn calibration_value(digits: &[u32]) -> Result<u32, &'static str> {
+    match (digits.first(), digits.last()) {
+        (Some(first), Some(last)) => Ok(first * 10 + last),
+        _ => Err("the line has no digits"),
+    }
+}
+
+/// The sum of every line's calibration value, counting the digits that are
+/// spelled out only if `spelled_out` is set.
+fn calibration_sum(lines: &[Vec<Digit>], spelled_out: bool) -> Result<u32, Error> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, digits)| {
+            let digits = digits
+                .iter()
+                .filter(|digit| spelled_out || !digit.spelled_out)
+                .map(|digit| digit.value)
+                .collect::<Vec<u32>>();
+            calibration_value(&digits)
+                .map_err(|err| Error::invalid(format!("line {}: {err}", i + 1)))
+        })
+        .sum()
+}
+
+aoc_utils::solution! {
+    Day01: 2023 day 1;
+    type Parsed<'a> = Vec<Vec<Digit>>;
+    parse: |input| map_lines(input, parse_line);
+    part1: |lines| calibration_sum(lines, false);
+    part2: |lines| calibration_sum(lines, true);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
 
-    const EXAMPLE_INPUT: &str = "1abc2
-pqr3stu8vwx
-a1b2c3d4e5f
-treb7uchet";
+    fn example() -> &'static str {
+        inputs::example(2023, 1, 1).unwrap()
+    }
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "142");
+        assert_eq!(process_part1(example()), "142");
     }
 
     #[test]
@@ -1,10 +1,14 @@
+use aoc_utils::{
+    parse::{lines, parse_all},
+    Error,
+};
 use nom::{
-    branch::alt, bytes::complete::tag, character::complete::newline, combinator::opt, multi::many1,
-    multi::separated_list1, sequence::preceded, IResult, Parser,
+    branch::alt, bytes::complete::tag, combinator::opt, multi::many1, multi::separated_list1,
+    sequence::preceded, IResult, Parser,
 };
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     red: Option<u32>,
     green: Option<u32>,
     blue: Option<u32>,
@@ -56,16 +60,17 @@ fn parse_game(input: &str) -> IResult<&str, (u32, Vec<Game>)> {
     Ok((input, (id, rounds)))
 }
 
-fn parse_games(input: &str) -> IResult<&str, Vec<(u32, Vec<Game>)>> {
-    separated_list1(newline, parse_game)(input)
+fn parse_games(input: &str) -> Result<Vec<(u32, Vec<Game>)>, Error> {
+    parse_all(lines(parse_game), input)
 }
 
 const MAX_RED: u32 = 12;
 const MAX_GREEN: u32 = 13;
 const MAX_BLUE: u32 = 14;
 
-pub fn process_part1(input: &str) -> String {
-    let (_, games) = parse_games(input).unwrap();
+/// The sum of the ids of the games possible with only 12 red, 13 green and 14
+/// blue cubes.
+fn possible_game_ids(games: &[(u32, Vec<Game>)]) -> u32 {
     games
         .iter()
         .filter_map(|(id, rounds)| {
@@ -78,43 +83,45 @@ pub fn process_part1(input: &str) -> String {
             }
             Some(id)
         })
-        .sum::<u32>()
-        .to_string()
+        .sum()
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (_, games) = parse_games(input).unwrap();
+/// The sum of the powers of the fewest cubes of each color each game needs.
+fn total_power(games: &[(u32, Vec<Game>)]) -> u32 {
     games
         .iter()
         .map(|(_, rounds)| {
-            let fewest_red = rounds.iter().max_by_key(|r| r.red).unwrap().red.unwrap();
-            let fewest_green = rounds
-                .iter()
-                .max_by_key(|r| r.green)
-                .unwrap()
-                .green
-                .unwrap();
-            let fewest_blue = rounds.iter().max_by_key(|r| r.blue).unwrap().blue.unwrap();
+            // A color that is never drawn isn't needed at all.
+            let fewest_red = rounds.iter().filter_map(|r| r.red).max().unwrap_or(0);
+            let fewest_green = rounds.iter().filter_map(|r| r.green).max().unwrap_or(0);
+            let fewest_blue = rounds.iter().filter_map(|r| r.blue).max().unwrap_or(0);
             fewest_red * fewest_green * fewest_blue
         })
-        .sum::<u32>()
-        .to_string()
+        .sum()
+}
+
+aoc_utils::solution! {
+    Day02: 2023 day 2;
+    type Parsed<'a> = Vec<(u32, Vec<Game>)>;
+    parse: |input| parse_games(input);
+    part1: |games| Ok(possible_game_ids(games));
+    part2: |games| Ok(total_power(games));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
+
+    fn example() -> &'static str {
+        inputs::example(2023, 2, 1).unwrap()
+    }
 
-    const EXAMPLE_INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
     const EXAMPLE_INPUTS: [(&str, &str, &str); 0] = [];
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "8");
+        assert_eq!(process_part1(example()), "8");
     }
 
     #[test]
@@ -126,7 +133,7 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "2286");
+        assert_eq!(process_part2(example()), "2286");
     }
 
     #[test]
@@ -1,5 +1,14 @@
 use std::collections::{HashMap, HashSet};
 
+use aoc_utils::{
+    parse::{parse_all, sparse_grid},
+    Error,
+};
+
+fn parse_schematic(input: &str) -> Result<HashMap<(i32, i32), char>, Error> {
+    parse_all(sparse_grid(Some), input)
+}
+
 fn part_number(grid: &HashMap<(i32, i32), char>, (x, y): (i32, i32)) -> ((i32, i32), u32) {
     let mut start_x = x;
     while grid
@@ -23,14 +32,8 @@ fn part_number(grid: &HashMap<(i32, i32), char>, (x, y): (i32, i32)) -> ((i32, i
     ((start_x, y), num)
 }
 
-pub fn process_part1(input: &str) -> String {
-    let mut grid = HashMap::new();
-    for (y, line) in input.lines().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            grid.insert((x as i32, y as i32), c);
-        }
-    }
-
+/// The sum of the numbers next to a symbol.
+fn part_number_sum(grid: &HashMap<(i32, i32), char>) -> u32 {
     let positions_of_symbols = grid
         .iter()
         .filter_map(|(p, c)| {
@@ -59,24 +62,18 @@ pub fn process_part1(input: &str) -> String {
 
         neighbors.into_iter().for_each(|neighbor| {
             if grid.get(&neighbor).is_some_and(|c| c.is_ascii_digit()) {
-                let (origin, value) = part_number(&grid, neighbor);
+                let (origin, value) = part_number(grid, neighbor);
                 if reachable_from_symbol.insert(origin) {
                     numbers.push(value);
                 }
             }
         });
     }
-    numbers.iter().sum::<u32>().to_string()
+    numbers.iter().sum()
 }
 
-pub fn process_part2(input: &str) -> String {
-    let mut grid = HashMap::new();
-    for (y, line) in input.lines().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            grid.insert((x as i32, y as i32), c);
-        }
-    }
-
+/// The sum of the products of the two numbers next to each gear.
+fn gear_ratio_sum(grid: &HashMap<(i32, i32), char>) -> u32 {
     let positions_of_symbols = grid
         .iter()
         .filter_map(|(p, c)| {
@@ -107,7 +104,7 @@ pub fn process_part2(input: &str) -> String {
             .into_iter()
             .filter_map(|neighbor| {
                 if grid.get(&neighbor).is_some_and(|c| c.is_ascii_digit()) {
-                    let (origin, value) = part_number(&grid, neighbor);
+                    let (origin, value) = part_number(grid, neighbor);
                     if reachable_from_symbol.insert(origin) {
                         return Some(value);
                     };
@@ -120,28 +117,31 @@ pub fn process_part2(input: &str) -> String {
             gear_ratios.push(neighbor_numbers.iter().product());
         }
     }
-    gear_ratios.iter().sum::<u32>().to_string()
+    gear_ratios.iter().sum()
+}
+
+aoc_utils::solution! {
+    Day03: 2023 day 3;
+    type Parsed<'a> = HashMap<(i32, i32), char>;
+    parse: |input| parse_schematic(input);
+    part1: |grid| Ok(part_number_sum(grid));
+    part2: |grid| Ok(gear_ratio_sum(grid));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
+
+    fn example() -> &'static str {
+        inputs::example(2023, 3, 1).unwrap()
+    }
 
-    const EXAMPLE_INPUT: &str = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+..58
-..592.....
-......755.
-...$.*....
-.664.598..";
     const EXAMPLE_INPUTS: [(&str, &str, &str); 0] = [];
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "4361");
+        assert_eq!(process_part1(example()), "4361");
     }
 
     #[test]
@@ -223,7 +223,7 @@ mod tests {
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "467835");
+        assert_eq!(process_part2(example()), "467835");
     }
 
     #[test]
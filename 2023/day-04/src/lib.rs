@@ -1,51 +1,51 @@
 use std::collections::{hash_map::Entry, HashMap, HashSet, VecDeque};
 
+use aoc_utils::{
+    parse::{integer, integer_list, labelled, lines, parse_all},
+    Error,
+};
 use nom::{
     bytes::complete::tag,
-    character::complete::{newline, space1},
-    multi::separated_list1,
-    sequence::{preceded, separated_pair, terminated},
+    character::complete::space1,
+    sequence::{delimited, separated_pair, terminated},
     IResult,
 };
 
-fn parse_card(input: &str) -> IResult<&str, (Vec<u32>, Vec<u32>)> {
-    let (input, _) = tag("Card")(input)?;
-    let (input, _) = space1(input)?;
-    let (input, _) = nom::character::complete::u32(input)?;
-    let (input, _) = tag(":")(input)?;
+/// The winning numbers of a card and the numbers on it.
+pub type Card = (Vec<u32>, Vec<u32>);
+
+fn parse_card(input: &str) -> IResult<&str, Card> {
+    let (input, _) = terminated(labelled("Card", integer::<u32>), tag(":"))(input)?;
     let (input, _) = space1(input)?;
     separated_pair(
-        separated_list1(space1, nom::character::complete::u32),
-        terminated(preceded(space1, tag("|")), space1),
-        separated_list1(space1, nom::character::complete::u32),
+        integer_list,
+        delimited(space1, tag("|"), space1),
+        integer_list,
     )(input)
 }
 
-fn parse_cards(input: &str) -> IResult<&str, Vec<(Vec<u32>, Vec<u32>)>> {
-    separated_list1(newline, parse_card)(input)
+fn parse_cards(input: &str) -> Result<Vec<Card>, Error> {
+    parse_all(lines(parse_card), input)
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_, cards) = parse_cards(input).unwrap();
+fn total_points(cards: &[Card]) -> u32 {
     cards
-        .into_iter()
+        .iter()
         .map(|(winning, my)| {
-            let winning_set: HashSet<u32> = winning.into_iter().collect();
-            let num_winning_cards =
-                my.into_iter().filter(|c| winning_set.contains(c)).count() as u32;
+            let winning_set: HashSet<&u32> = winning.iter().collect();
+            let num_winning_cards = my.iter().filter(|c| winning_set.contains(c)).count() as u32;
             if num_winning_cards == 0 {
                 return 0;
             };
             (2u32).pow(num_winning_cards - 1)
         })
-        .sum::<u32>()
-        .to_string()
+        .sum()
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (_, cards) = parse_cards(input).unwrap();
-    let cards: HashMap<u32, (Vec<u32>, Vec<u32>)> = cards
-        .into_iter()
+/// How many scratchcards there are once every won copy has been scratched.
+fn total_scratchcards(cards: &[Card]) -> Result<u32, Error> {
+    let cards: HashMap<u32, &Card> = cards
+        .iter()
         .enumerate()
         .map(|(i, v)| (i as u32 + 1, v))
         .collect();
@@ -58,10 +58,11 @@ pub fn process_part2(input: &str) -> String {
         total_cards += 1;
 
         let num_winning_cards = if let Entry::Vacant(e) = match_cache.entry(card_number) {
-            let (winning, my) = cards.get(&card_number).unwrap();
+            let (winning, my) = cards.get(&card_number).ok_or_else(|| {
+                Error::invalid(format!("card {card_number} is won but doesn't exist"))
+            })?;
             let winning_set: HashSet<u32> = winning.clone().into_iter().collect();
-            let num_winning_cards =
-                my.iter().filter(|c| winning_set.contains(c)).count() as u32;
+            let num_winning_cards = my.iter().filter(|c| winning_set.contains(c)).count() as u32;
             e.insert(num_winning_cards);
             num_winning_cards
         } else {
@@ -72,27 +73,33 @@ pub fn process_part2(input: &str) -> String {
             to_process.push_back(extra_card);
         }
     }
-    total_cards.to_string()
+    Ok(total_cards)
+}
+
+aoc_utils::solution! {
+    Day04: 2023 day 4;
+    type Parsed<'a> = Vec<Card>;
+    parse: |input| parse_cards(input);
+    part1: |cards| Ok(total_points(cards));
+    part2: |cards| total_scratchcards(cards);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
 
-    const EXAMPLE_INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+    fn example() -> &'static str {
+        inputs::example(2023, 4, 1).unwrap()
+    }
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "13");
+        assert_eq!(process_part1(example()), "13");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "30");
+        assert_eq!(process_part2(example()), "30");
     }
 }
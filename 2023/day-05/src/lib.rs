@@ -1,36 +1,45 @@
 use std::ops::Range;
 
+use aoc_utils::{
+    parse::{blank_line, blocks, lines, parse_all},
+    Error, RangeSet,
+};
 use nom::{
     bytes::complete::tag,
-    character::complete::{multispace0, newline, not_line_ending, space0, space1},
+    character::complete::{line_ending, not_line_ending, space0, space1},
     multi::separated_list1,
-    sequence::{separated_pair, terminated},
+    sequence::{preceded, separated_pair, terminated},
     IResult,
 };
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
 fn parse_seeds(input: &str) -> IResult<&str, Vec<u64>> {
-    let (input, _) = tag("seeds: ")(input)?;
-    separated_list1(space1, nom::character::complete::u64)(input)
+    preceded(
+        tag("seeds: "),
+        separated_list1(space1, nom::character::complete::u64),
+    )(input)
 }
 
-fn parse_seed_ranges(input: &str) -> IResult<&str, Vec<u64>> {
-    let (input, _) = tag("seeds: ")(input)?;
-    let (input, ranges) = separated_list1(
-        space1,
-        separated_pair(
-            nom::character::complete::u64,
-            space1,
-            nom::character::complete::u64,
-        ),
-    )(input)?;
-    let seeds = ranges
-        .into_iter()
-        .flat_map(|(range_start, range_length)| (range_start..range_start + range_length))
-        .collect();
-    Ok((input, seeds))
+/// The seeds read one at a time.
+fn single_seeds(seeds: &[u64]) -> RangeSet<u64> {
+    seeds.iter().map(|&seed| seed..seed + 1).collect()
+}
+
+/// The seeds read as pairs of a range's start and length.
+fn seed_ranges(seeds: &[u64]) -> Result<RangeSet<u64>, Error> {
+    if !seeds.len().is_multiple_of(2) {
+        return Err(Error::invalid(
+            "every seed range needs a start and a length",
+        ));
+    }
+    Ok(seeds
+        .chunks(2)
+        .map(|range| range[0]..range[0] + range[1])
+        .collect())
 }
 
+/// Source ranges with the start of the destination range each maps onto.
+pub type Map = Vec<(Range<u64>, u64)>;
+
 fn parse_range(input: &str) -> IResult<&str, (Range<u64>, u64)> {
     let (input, destination_range_start) =
         terminated(nom::character::complete::u64, space1)(input)?;
@@ -45,109 +54,64 @@ fn parse_range(input: &str) -> IResult<&str, (Range<u64>, u64)> {
     ))
 }
 
-fn parse_map(input: &str) -> IResult<&str, Vec<(Range<u64>, u64)>> {
-    let (input, _) = multispace0(input)?;
-    let (input, _) = not_line_ending(input)?;
-    let (input, _) = multispace0(input)?;
-    separated_list1(newline, parse_range)(input)
+fn parse_map(input: &str) -> IResult<&str, Map> {
+    preceded(terminated(not_line_ending, line_ending), lines(parse_range))(input)
 }
 
-fn lookup_in_map(map: &[(Range<u64>, u64)], val: &u64) -> u64 {
-    let Some((source_range, destination_range_start)) = map.iter().find(|(r, _)| r.contains(val))
-    else {
-        return *val;
-    };
-    destination_range_start + val - source_range.start
+/// The seeds and every map in the order they're applied.
+fn parse_almanac(input: &str) -> Result<(Vec<u64>, Vec<Map>), Error> {
+    parse_all(
+        separated_pair(parse_seeds, blank_line, blocks(parse_map)),
+        input,
+    )
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (input, seeds) = parse_seeds(input).unwrap();
-    let (input, seed_to_soil_map) = parse_map(input).unwrap();
-    let (input, soil_to_fertilizer_map) = parse_map(input).unwrap();
-    let (input, fertilizer_to_water_map) = parse_map(input).unwrap();
-    let (input, water_to_light_map) = parse_map(input).unwrap();
-    let (input, light_to_temperature_map) = parse_map(input).unwrap();
-    let (input, temperature_to_humidity_map) = parse_map(input).unwrap();
-    let (_, humidity_to_location_map) = parse_map(input).unwrap();
-    seeds
-        .iter()
-        .map(|v| lookup_in_map(&seed_to_soil_map, v))
-        .map(|v| lookup_in_map(&soil_to_fertilizer_map, &v))
-        .map(|v| lookup_in_map(&fertilizer_to_water_map, &v))
-        .map(|v| lookup_in_map(&water_to_light_map, &v))
-        .map(|v| lookup_in_map(&light_to_temperature_map, &v))
-        .map(|v| lookup_in_map(&temperature_to_humidity_map, &v))
-        .map(|v| lookup_in_map(&humidity_to_location_map, &v))
+fn lowest_location(seeds: RangeSet<u64>, maps: &[Map]) -> Result<u64, Error> {
+    maps.iter()
+        .fold(seeds, |values, map| map_ranges(map, &values))
         .min()
-        .unwrap()
-        .to_string()
+        .ok_or_else(|| Error::invalid("there are no seeds"))
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (input, seeds) = parse_seed_ranges(input).unwrap();
-    let (input, seed_to_soil_map) = parse_map(input).unwrap();
-    let (input, soil_to_fertilizer_map) = parse_map(input).unwrap();
-    let (input, fertilizer_to_water_map) = parse_map(input).unwrap();
-    let (input, water_to_light_map) = parse_map(input).unwrap();
-    let (input, light_to_temperature_map) = parse_map(input).unwrap();
-    let (input, temperature_to_humidity_map) = parse_map(input).unwrap();
-    let (_, humidity_to_location_map) = parse_map(input).unwrap();
-    seeds
-        .into_par_iter()
-        .map(|v| lookup_in_map(&seed_to_soil_map, &v))
-        .map(|v| lookup_in_map(&soil_to_fertilizer_map, &v))
-        .map(|v| lookup_in_map(&fertilizer_to_water_map, &v))
-        .map(|v| lookup_in_map(&water_to_light_map, &v))
-        .map(|v| lookup_in_map(&light_to_temperature_map, &v))
-        .map(|v| lookup_in_map(&temperature_to_humidity_map, &v))
-        .map(|v| lookup_in_map(&humidity_to_location_map, &v))
-        .min()
-        .unwrap()
-        .to_string()
+/// Everything `values` maps to, splitting ranges wherever a source range of
+/// `map` starts or ends. Values outside of every source range map to themselves.
+fn map_ranges(map: &[(Range<u64>, u64)], values: &RangeSet<u64>) -> RangeSet<u64> {
+    let mut unmapped = values.clone();
+    let mut mapped = RangeSet::new();
+    for (source_range, destination_range_start) in map {
+        let source = RangeSet::from(source_range.clone());
+        mapped = mapped.union(
+            &unmapped
+                .intersection(&source)
+                .shift(source_range.start, *destination_range_start),
+        );
+        unmapped = unmapped.difference(&source);
+    }
+    mapped.union(&unmapped)
+}
+
+aoc_utils::solution! {
+    Day05: 2023 day 5;
+    type Parsed<'a> = (Vec<u64>, Vec<Map>);
+    parse: |input| parse_almanac(input);
+    part1: |(seeds, maps)| lowest_location(single_seeds(seeds), maps);
+    part2: |(seeds, maps)| lowest_location(seed_ranges(seeds)?, maps);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
 
-    const EXAMPLE_INPUT: &str = "seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
+    fn example() -> &'static str {
+        inputs::example(2023, 5, 1).unwrap()
+    }
 
-humidity-to-location map:
-60 56 37
-56 93 4";
     const EXAMPLE_INPUTS: [(&str, &str, &str); 0] = [];
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "35");
+        assert_eq!(process_part1(example()), "35");
     }
 
     #[test]
@@ -157,9 +121,22 @@ humidity-to-location map:
         }
     }
 
+    #[test]
+    fn splits_ranges_at_map_boundaries() {
+        let map = vec![(5..10, 100), (12..14, 50)];
+        assert_eq!(
+            map_ranges(&map, &RangeSet::from(0..20)),
+            RangeSet::from_ranges([0..5, 10..12, 14..20, 50..52, 100..105])
+        );
+        assert_eq!(
+            map_ranges(&map, &RangeSet::from_ranges([8..9, 30..31])),
+            RangeSet::from_ranges([30..31, 103..104])
+        );
+    }
+
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "46");
+        assert_eq!(process_part2(example()), "46");
     }
 
     #[test]
@@ -1,32 +1,27 @@
-use nom::bytes::complete::tag;
-use nom::character::complete::{newline, space1};
-use nom::multi::separated_list1;
-use nom::IResult;
+use aoc_utils::{
+    parse::{labelled, parse_all},
+    Error,
+};
+use nom::{
+    character::complete::{digit1, line_ending, space1},
+    multi::separated_list1,
+    sequence::separated_pair,
+};
 
-fn parse_races(input: &str) -> IResult<&str, Vec<(f64, f64)>> {
-    let (input, _) = tag("Time: ")(input)?;
-    let (input, _) = space1(input)?;
-    let (input, times) = separated_list1(space1, nom::number::complete::double)(input)?;
-    let (input, _) = newline(input)?;
-    let (input, _) = tag("Distance: ")(input)?;
-    let (input, _) = space1(input)?;
-    let (input, distances) = separated_list1(space1, nom::number::complete::double)(input)?;
-    Ok((input, times.into_iter().zip(distances).collect()))
-}
-
-fn parse_single_race(input: &str) -> (f64, f64) {
-    let numbers_in_lines = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .filter(|c| c.is_ascii_digit())
-                .collect::<String>()
-        })
-        .collect::<Vec<String>>();
-    (
-        numbers_in_lines[0].parse().unwrap(),
-        numbers_in_lines[1].parse().unwrap(),
-    )
+/// The time and record distance of every race, with their digits as written.
+fn parse_races(input: &str) -> Result<Vec<(&str, &str)>, Error> {
+    let (times, distances) = parse_all(
+        separated_pair(
+            labelled("Time:", separated_list1(space1, digit1)),
+            line_ending,
+            labelled("Distance:", separated_list1(space1, digit1)),
+        ),
+        input,
+    )?;
+    if times.len() != distances.len() {
+        return Err(Error::invalid("every race needs a time and a distance"));
+    }
+    Ok(times.into_iter().zip(distances).collect())
 }
 
 fn ways_to_win_race(total_time: f64, distance_to_beat: f64) -> f64 {
@@ -42,36 +37,50 @@ fn ways_to_win_race(total_time: f64, distance_to_beat: f64) -> f64 {
     root2.floor() - root1.ceil() + 1f64
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_, races) = parse_races(input).unwrap();
+/// The product of the ways to win each race.
+fn ways_to_win_races(races: &[(&str, &str)]) -> f64 {
     races
-        .into_iter()
+        .iter()
         .map(|(total_time, distance_to_beat)| {
-            ways_to_win_race(total_time, distance_to_beat)
+            ways_to_win_race(parse_digits(total_time), parse_digits(distance_to_beat))
         })
-        .product::<f64>()
-        .to_string()
+        .product()
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (total_time, distance_to_beat) = parse_single_race(input);
-    ways_to_win_race(total_time, distance_to_beat).to_string()
+/// The ways to win the one race written with spaces between its digits.
+fn ways_to_win_kerned_race(races: &[(&str, &str)]) -> f64 {
+    let (total_time, distance_to_beat): (String, String) = races.iter().copied().unzip();
+    ways_to_win_race(parse_digits(&total_time), parse_digits(&distance_to_beat))
+}
+
+fn parse_digits(digits: &str) -> f64 {
+    digits.parse().expect("digits always form a number")
+}
+
+aoc_utils::solution! {
+    Day06: 2023 day 6;
+    type Parsed<'a> = Vec<(&'a str, &'a str)>;
+    parse: |input| parse_races(input);
+    part1: |races| Ok(ways_to_win_races(races).to_string());
+    part2: |races| Ok(ways_to_win_kerned_race(races).to_string());
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
 
-    const EXAMPLE_INPUT: &str = "Time:      7  15   30
-Distance:  9  40  200";
+    fn example() -> &'static str {
+        inputs::example(2023, 6, 1).unwrap()
+    }
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(EXAMPLE_INPUT), "288");
+        assert_eq!(process_part1(example()), "288");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(EXAMPLE_INPUT), "71503");
+        assert_eq!(process_part2(example()), "71503");
     }
 }
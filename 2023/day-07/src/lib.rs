@@ -1 +1,230 @@
+use aoc_utils::{
+    parse::{integer, lines, parse_all},
+    Error,
+};
+use itertools::Itertools;
+use nom::{
+    bytes::complete::take, character::complete::space1, combinator::verify,
+    sequence::separated_pair,
+};
 
+/// Every card that can appear in a hand.
+const CARDS: &str = "23456789TJQKA";
+
+/// How many cards make a hand.
+const HAND_SIZE: usize = 5;
+
+/// How cards are compared: the strength of a card is its position in `order`, and
+/// the `wildcard`, if any, counts as whichever card makes the hand strongest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    pub order: &'static str,
+    pub wildcard: Option<char>,
+}
+
+impl Rules {
+    pub const STANDARD: Rules = Rules {
+        order: "23456789TJQKA",
+        wildcard: None,
+    };
+
+    /// `J` is a joker: the weakest card on its own, but wild when typing a hand.
+    pub const JOKERS: Rules = Rules {
+        order: "J23456789TQKA",
+        wildcard: Some('J'),
+    };
+
+    fn strength(&self, card: char) -> Option<u8> {
+        self.order.find(card).map(|i| i as u8)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandType {
+    fn from_cards(cards: &str, rules: &Rules) -> HandType {
+        let wildcards = cards.chars().filter(|c| Some(*c) == rules.wildcard).count();
+        let mut counts = cards
+            .chars()
+            .filter(|c| Some(*c) != rules.wildcard)
+            .counts()
+            .into_values()
+            .sorted_unstable_by(|a, b| b.cmp(a))
+            .collect_vec();
+        // Wildcards are best spent on the most common card.
+        match counts.first_mut() {
+            Some(most) => *most += wildcards,
+            None => counts.push(wildcards),
+        }
+
+        match counts[..] {
+            [5] => HandType::FiveOfAKind,
+            [4, ..] => HandType::FourOfAKind,
+            [3, 2] => HandType::FullHouse,
+            [3, ..] => HandType::ThreeOfAKind,
+            [2, 2, ..] => HandType::TwoPair,
+            [2, ..] => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+}
+
+/// A hand of cards. Hands order by type first, then by the strength of each card
+/// from left to right.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hand {
+    hand_type: HandType,
+    strengths: Vec<u8>,
+}
+
+impl Hand {
+    /// `None` unless there are five cards, each of them part of the ruleset's
+    /// `order`.
+    pub fn new(cards: &str, rules: &Rules) -> Option<Hand> {
+        if cards.chars().count() != HAND_SIZE {
+            return None;
+        }
+        Some(Hand {
+            hand_type: HandType::from_cards(cards, rules),
+            strengths: cards
+                .chars()
+                .map(|c| rules.strength(c))
+                .collect::<Option<_>>()?,
+        })
+    }
+
+    pub fn hand_type(&self) -> HandType {
+        self.hand_type
+    }
+}
+
+fn parse_hands(input: &str) -> Result<Vec<(&str, u32)>, Error> {
+    let cards = verify(take(HAND_SIZE), |cards: &str| {
+        cards.chars().all(|card| CARDS.contains(card))
+    });
+    parse_all(lines(separated_pair(cards, space1, integer)), input)
+}
+
+/// Sum of every bid multiplied by the rank of its hand, the weakest hand being
+/// ranked 1.
+pub fn total_winnings(hands: &[(&str, u32)], rules: &Rules) -> Result<u32, Error> {
+    let hands = hands
+        .iter()
+        .map(|(cards, bid)| {
+            Hand::new(cards, rules)
+                .map(|hand| (hand, bid))
+                .ok_or_else(|| Error::invalid(format!("{cards} isn't a hand of {}", rules.order)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(hands
+        .into_iter()
+        .sorted()
+        .enumerate()
+        .map(|(rank, (_, bid))| (rank as u32 + 1) * bid)
+        .sum())
+}
+
+aoc_utils::solution! {
+    Day07: 2023 day 7;
+    type Parsed<'a> = Vec<(&'a str, u32)>;
+    parse: |input| parse_hands(input);
+    part1: |hands| total_winnings(hands, &Rules::STANDARD);
+    part2: |hands| total_winnings(hands, &Rules::JOKERS);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_utils::inputs;
+    use rstest::rstest;
+    use HandType::*;
+
+    fn example() -> &'static str {
+        inputs::example(2023, 7, 1).unwrap()
+    }
+
+    #[rstest]
+    #[case("32T3K", OnePair)]
+    #[case("T55J5", ThreeOfAKind)]
+    #[case("KK677", TwoPair)]
+    #[case("KTJJT", TwoPair)]
+    #[case("QQQJA", ThreeOfAKind)]
+    #[case("AAAAA", FiveOfAKind)]
+    #[case("23456", HighCard)]
+    #[case("22233", FullHouse)]
+    #[trace]
+    fn standard_hand_types(#[case] cards: &str, #[case] expected: HandType) {
+        assert_eq!(
+            Hand::new(cards, &Rules::STANDARD).unwrap().hand_type(),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("32T3K", OnePair)]
+    #[case("T55J5", FourOfAKind)]
+    #[case("KK677", TwoPair)]
+    #[case("KTJJT", FourOfAKind)]
+    #[case("QQQJA", FourOfAKind)]
+    #[case("22J33", FullHouse)]
+    #[case("2JJ45", ThreeOfAKind)]
+    #[case("JJJJJ", FiveOfAKind)]
+    #[trace]
+    fn joker_hand_types(#[case] cards: &str, #[case] expected: HandType) {
+        assert_eq!(
+            Hand::new(cards, &Rules::JOKERS).unwrap().hand_type(),
+            expected
+        );
+    }
+
+    #[test]
+    fn hands_compare_card_by_card() {
+        let rules = Rules::STANDARD;
+        assert!(Hand::new("33332", &rules) > Hand::new("2AAAA", &rules));
+        assert!(Hand::new("77888", &rules) > Hand::new("77788", &rules));
+        // A joker is the weakest card when breaking ties.
+        let rules = Rules::JOKERS;
+        assert!(Hand::new("JKKK2", &rules) < Hand::new("QQQQ2", &rules));
+        assert_eq!(Hand::new("1KKK2", &rules), None);
+        assert_eq!(Hand::new("KKK2", &rules), None);
+        assert_eq!(Hand::new("KKK222", &rules), None);
+    }
+
+    #[test]
+    fn rejects_hands_without_five_cards() {
+        for input in ["32T3 765", "32T3KK 765"] {
+            assert!(try_process_part1(input).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn reports_malformed_hands() {
+        assert_eq!(
+            try_process_part1("32T3K 765\r\nT55X5 684\r\n").map_err(|err| err.to_string()),
+            Err("line 2, column 1: unexpected \"T55X5 684\"".to_string())
+        );
+    }
+
+    #[rstest]
+    #[case(example(), "6440")]
+    #[trace]
+    fn test_part1(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(process_part1(input).as_str(), expected);
+    }
+
+    #[rstest]
+    #[case(example(), "5905")]
+    #[trace]
+    fn test_part2(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(process_part2(input).as_str(), expected);
+    }
+}
@@ -1,12 +1,16 @@
 use std::collections::HashMap;
 
-use aoc_utils::lcm;
+use aoc_utils::{
+    number_theory::crt,
+    parse::{blank_line, lines, parse_all},
+    Error,
+};
 use itertools::{FoldWhile, Itertools};
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{self, alphanumeric1, multispace1},
-    multi::{many1, separated_list1},
+    character::complete::{self, alphanumeric1},
+    multi::many1,
     sequence::{separated_pair, terminated},
     IResult, Parser,
 };
@@ -14,11 +18,14 @@ use Direction::*;
 use FoldWhile::*;
 
 #[derive(Debug, Clone, Copy)]
-enum Direction {
+pub enum Direction {
     Left,
     Right,
 }
 
+/// Maps each node to its left and right neighbours.
+pub type Network<'a> = HashMap<&'a str, (&'a str, &'a str)>;
+
 fn parse_node(input: &str) -> IResult<&str, (&str, (&str, &str))> {
     let (input, origin) = terminated(alphanumeric1, tag(" = ("))(input)?;
     let (input, (left, right)) = separated_pair(alphanumeric1, tag(", "), alphanumeric1)(input)?;
@@ -26,22 +33,38 @@ fn parse_node(input: &str) -> IResult<&str, (&str, (&str, &str))> {
     Ok((input, (origin, (left, right))))
 }
 
-fn parse_graph(input: &str) -> IResult<&str, (Vec<Direction>, HashMap<&str, (&str, &str)>)> {
+fn parse_graph(input: &str) -> IResult<&str, (Vec<Direction>, Network<'_>)> {
     let (input, steps) = many1(alt((
         complete::char('R').map(|_| Right),
         complete::char('L').map(|_| Left),
     )))(input)?;
-    let (input, _) = multispace1(input)?;
-    let (input, nodes) = separated_list1(multispace1, parse_node)(input)?;
+    let (input, _) = blank_line(input)?;
+    let (input, nodes) = lines(parse_node)(input)?;
     Ok((input, (steps, nodes.into_iter().collect())))
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (input, (steps, graph)) = parse_graph(input).unwrap();
-    debug_assert_eq!(input, "");
+/// The steps and the network, which must have every node that another leads to.
+fn parse_network(input: &str) -> Result<(Vec<Direction>, Network<'_>), Error> {
+    let (steps, graph) = parse_all(parse_graph, input)?;
+    if let Some(missing) = graph
+        .values()
+        .flat_map(|(left, right)| [left, right])
+        .find(|node| !graph.contains_key(*node))
+    {
+        return Err(Error::invalid(format!(
+            "node {missing} isn't in the network"
+        )));
+    }
+    Ok((steps, graph))
+}
+
+fn steps_to_zzz(steps: &[Direction], graph: &Network<'_>) -> Result<usize, Error> {
+    if !graph.contains_key("AAA") {
+        return Err(Error::invalid("node AAA isn't in the network"));
+    }
 
-    steps
-        .into_iter()
+    Ok(steps
+        .iter()
         .cycle()
         .fold_while(("AAA", 0), |(current_node, total_steps), next_step| {
             if current_node == "ZZZ" {
@@ -55,96 +78,134 @@ pub fn process_part1(input: &str) -> String {
             }
         })
         .into_inner()
-        .1
-        .to_string()
+        .1)
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (input, (steps, graph)) = parse_graph(input).unwrap();
-    debug_assert_eq!(input, "");
-
+/// Steps until every ghost, starting on each node ending in A, stands on a
+/// node ending in Z at once.
+fn ghost_steps(steps: &[Direction], graph: &Network<'_>) -> Result<i128, Error> {
     let starting_nodes: Vec<&str> = graph
         .keys()
         .filter(|node| node.ends_with('A'))
         .cloned()
         .collect();
-    let cycle_lengths: Vec<usize> = starting_nodes
+    if starting_nodes.is_empty() {
+        return Err(Error::invalid("no node ends in A"));
+    }
+
+    // Every ghost eventually walks in a loop, which it stays in forever.
+    let walks = starting_nodes
         .into_iter()
         .map(|starting_node| {
-            let mut seen_at: HashMap<&str, usize> = HashMap::new();
-            steps
-                .iter()
-                .cycle()
-                .fold_while(
-                    (starting_node, 0),
-                    |(current_node, total_steps), next_step| {
-                        let current_node_seen_at =
-                            seen_at.entry(current_node).or_insert(total_steps);
-                        if current_node.ends_with('Z') && *current_node_seen_at < total_steps {
-                            Done((current_node, total_steps - *current_node_seen_at))
-                        } else {
-                            let (left, right) = graph.get(current_node).unwrap();
-                            match next_step {
-                                Left => Continue((left, total_steps + 1)),
-                                Right => Continue((right, total_steps + 1)),
-                            }
-                        }
-                    },
-                )
-                .into_inner()
-                .1
+            aoc_utils::find_cycle_by(
+                (starting_node, 0),
+                |(current_node, step_idx)| {
+                    let (left, right) = graph.get(current_node).unwrap();
+                    let next_node = match steps[step_idx] {
+                        Left => *left,
+                        Right => *right,
+                    };
+                    (next_node, (step_idx + 1) % steps.len())
+                },
+                |state| *state,
+                |(node, _)| *node,
+            )
         })
-        .collect();
-    lcm(cycle_lengths.as_slice()).to_string()
+        .collect_vec();
+
+    // Until they are all in their loops, just check where they all are.
+    let loops_entered_by = walks.iter().map(|walk| walk.start()).max().unwrap_or(0);
+    if let Some(total_steps) = (0..loops_entered_by)
+        .find(|&total_steps| walks.iter().all(|walk| walk.at(total_steps).ends_with('Z')))
+    {
+        return Ok(total_steps as i128);
+    }
+
+    // After that, each ghost stands on a Z node at fixed offsets within its
+    // loop. Each offset is one congruence (steps mod loop length).
+    let ghost_congruences = walks
+        .iter()
+        .map(|walk| {
+            (walk.start()..walk.start() + walk.period())
+                .filter(|total_steps| walk.history()[*total_steps].ends_with('Z'))
+                .map(|total_steps| (total_steps as i128, walk.period() as i128))
+                .collect_vec()
+        })
+        .collect_vec();
+
+    let loops_entered_by = loops_entered_by as i128;
+    ghost_congruences
+        .into_iter()
+        .multi_cartesian_product()
+        .filter_map(crt)
+        .map(|(total_steps, period)| {
+            if total_steps < loops_entered_by {
+                total_steps + (loops_entered_by - total_steps + period - 1) / period * period
+            } else {
+                total_steps
+            }
+        })
+        .min()
+        .ok_or_else(|| Error::invalid("the ghosts never all stand on Z nodes at once"))
+}
+
+aoc_utils::solution! {
+    Day08: 2023 day 8;
+    type Parsed<'a> = (Vec<Direction>, Network<'a>);
+    parse: |input| parse_network(input);
+    part1: |(steps, graph)| steps_to_zzz(steps, graph);
+    part2: |(steps, graph)| ghost_steps(steps, graph);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
     use rstest::rstest;
 
+    fn example(n: u8) -> &'static str {
+        inputs::example(2023, 8, n).unwrap()
+    }
+
     #[rstest]
-    #[case(
-        "RL
-
-AAA = (BBB, CCC)
-BBB = (DDD, EEE)
-CCC = (ZZZ, GGG)
-DDD = (DDD, DDD)
-EEE = (EEE, EEE)
-GGG = (GGG, GGG)
-ZZZ = (ZZZ, ZZZ)",
-        "2"
-    )]
-    #[case(
-        "LLR
-
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)",
-        "6"
-    )]
+    #[case(1, "2")]
+    #[case(2, "6")]
     #[trace]
-    fn test_fn(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(process_part1(input).as_str(), expected);
+    fn test_fn(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(process_part1(example(n)).as_str(), expected);
     }
 
     #[rstest]
-    #[case(
-        "LR
-
-        11A = (11B, XXX)
-        11B = (XXX, 11Z)
-        11Z = (11B, XXX)
-        22A = (22B, XXX)
-        22B = (22C, 22C)
-        22C = (22Z, 22Z)
-        22Z = (22B, 22B)
-        XXX = (XXX, XXX)",
-        "6"
-    )]
+    #[case(3, "6")]
+    #[case(4, "5")]
     #[trace]
-    fn test_fn2(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(process_part2(input).as_str(), expected);
+    fn test_fn2(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(process_part2(example(n)).as_str(), expected);
+    }
+
+    #[test]
+    fn finds_z_nodes_before_the_ghosts_loop() {
+        // 11A's ghost passes 11Z once on its way to looping at 11B.
+        let input = "L
+
+11A = (11Z, XXX)
+11Z = (11B, XXX)
+11B = (11B, XXX)
+22A = (22Z, XXX)
+22Z = (22Z, XXX)
+XXX = (XXX, XXX)";
+        assert_eq!(process_part2(input), "1");
+    }
+
+    #[test]
+    fn needs_a_starting_node() {
+        let input = "L
+
+BBB = (ZZZ, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+        assert_eq!(
+            try_process_part2(input).unwrap_err(),
+            Error::invalid("no node ends in A")
+        );
     }
 }
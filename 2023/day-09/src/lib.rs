@@ -1,22 +1,7 @@
-use nom::{
-    character::{
-        self,
-        complete::{newline, space1},
-    },
-    multi::separated_list1,
-    IResult,
-};
+use aoc_utils::parse::{integer_lines, parse_all};
 
-fn parse_sequence(input: &str) -> IResult<&str, Vec<i64>> {
-    separated_list1(space1, character::complete::i64)(input)
-}
-
-fn parse_sequences(input: &str) -> IResult<&str, Vec<Vec<i64>>> {
-    separated_list1(newline, parse_sequence)(input)
-}
-
-fn predict_value(seq: Vec<i64>, fwd: bool) -> i64 {
-    let mut sequences = vec![seq];
+fn predict_value(seq: &[i64], fwd: bool) -> i64 {
+    let mut sequences = vec![seq.to_vec()];
     while !sequences.last().unwrap().iter().all(|v| v == &0) {
         sequences.push(
             sequences
@@ -27,7 +12,7 @@ fn predict_value(seq: Vec<i64>, fwd: bool) -> i64 {
                 .collect(),
         );
     }
-    for idx in (0..sequences.len() - 2).rev() {
+    for idx in (0..sequences.len().saturating_sub(2)).rev() {
         if fwd {
             let next_value = *sequences[idx].last().unwrap() + *sequences[idx + 1].last().unwrap();
             sequences.get_mut(idx).unwrap().push(next_value)
@@ -43,53 +28,42 @@ fn predict_value(seq: Vec<i64>, fwd: bool) -> i64 {
     }
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (input, sequences) = parse_sequences(input).unwrap();
-    debug_assert_eq!(input, "");
-    sequences
-        .into_iter()
-        .map(|seq| predict_value(seq, true))
-        .sum::<i64>()
-        .to_string()
+/// The sum of the values predicted after each sequence, or before it unless `fwd`.
+fn sum_of_predictions(sequences: &[Vec<i64>], fwd: bool) -> i64 {
+    sequences.iter().map(|seq| predict_value(seq, fwd)).sum()
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (input, sequences) = parse_sequences(input).unwrap();
-    debug_assert_eq!(input, "");
-    sequences
-        .into_iter()
-        .map(|seq| predict_value(seq, false))
-        .sum::<i64>()
-        .to_string()
+aoc_utils::solution! {
+    Day09: 2023 day 9;
+    type Parsed<'a> = Vec<Vec<i64>>;
+    parse: |input| parse_all(integer_lines, input);
+    part1: |sequences| Ok(sum_of_predictions(sequences, true));
+    part2: |sequences| Ok(sum_of_predictions(sequences, false));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
+    use aoc_utils::parse::integer_list;
     use rstest::rstest;
 
+    fn example(n: u8) -> &'static str {
+        inputs::example(2023, 9, n).unwrap()
+    }
+
     #[rstest]
-    #[case(
-        "0 3 6 9 12 15
-1 3 6 10 15 21
-10 13 16 21 30 45",
-        "114"
-    )]
+    #[case(1, "114")]
     #[trace]
-    fn test_part1(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(process_part1(input).as_str(), expected);
+    fn test_part1(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(process_part1(example(n)).as_str(), expected);
     }
 
     #[rstest]
-    #[case(
-        "0 3 6 9 12 15
-1 3 6 10 15 21
-10 13 16 21 30 45",
-        "2"
-    )]
+    #[case(1, "2")]
     #[trace]
-    fn test_part2(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(process_part2(input).as_str(), expected);
+    fn test_part2(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(process_part2(example(n)).as_str(), expected);
     }
 
     #[rstest]
@@ -101,7 +75,7 @@ mod tests {
     #[trace]
     fn test_predict_next_value(#[case] input: &str, #[case] expected: &str) {
         assert_eq!(
-            predict_value(parse_sequence(input).unwrap().1, true)
+            predict_value(&parse_all(integer_list, input).unwrap(), true)
                 .to_string()
                 .as_str(),
             expected
@@ -115,7 +89,7 @@ mod tests {
     #[trace]
     fn test_predict_prev_value(#[case] input: &str, #[case] expected: &str) {
         assert_eq!(
-            predict_value(parse_sequence(input).unwrap().1, false)
+            predict_value(&parse_all(integer_list, input).unwrap(), false)
                 .to_string()
                 .as_str(),
             expected
@@ -1,5 +1,10 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::HashMap;
 
+use aoc_utils::{
+    geometry,
+    parse::{parse_all, sparse_grid},
+    Error,
+};
 use itertools::Itertools;
 
 fn pipe_neighbors(x: i32, y: i32, ch: char) -> Vec<(i32, i32)> {
@@ -14,172 +19,95 @@ fn pipe_neighbors(x: i32, y: i32, ch: char) -> Vec<(i32, i32)> {
     }
 }
 
-pub fn process_part1(input: &str) -> String {
-    let mut graph: HashMap<(i32, i32), char> = HashMap::new();
-    for (y, line) in input.lines().enumerate() {
-        for (x, char) in line.char_indices() {
-            graph.insert((x as i32, y as i32), char);
-        }
-    }
-
-    let starting_position = *graph.iter().find(|(_, char)| **char == 'S').unwrap().0;
-    let starting_pipes = graph
+fn start_position(graph: &HashMap<(i32, i32), char>) -> Result<(i32, i32), Error> {
+    graph
         .iter()
-        .filter(|((x, y), ch)| pipe_neighbors(*x, *y, **ch).contains(&starting_position))
-        .map(|(p, _)| (*p, 1))
-        .collect_vec();
-
-    let mut seen: HashSet<(i32, i32)> = HashSet::new();
-    let mut max_distance = 1;
-
-    aoc_utils::complete_bfs(starting_pipes.into_iter(), |((x, y), distance)| {
-        let ch = graph.get(&(x, y)).unwrap();
-        if distance > max_distance {
-            max_distance = distance;
-        }
-        Some(
-            pipe_neighbors(x, y, *ch)
-                .into_iter()
-                .filter(|p| graph.get(p).is_some() && seen.insert(*p))
-                .map(|p| (p, distance + 1))
-                .collect_vec(),
-        )
-    });
+        .find(|(_, char)| **char == 'S')
+        .map(|(p, _)| *p)
+        .ok_or_else(|| Error::invalid("there is no S"))
+}
 
-    max_distance.to_string()
+/// How many steps along the loop the point farthest from `S` is.
+fn farthest_point(graph: &HashMap<(i32, i32), char>) -> Result<usize, Error> {
+    // The farthest point is halfway round the loop.
+    Ok(find_loop(graph)?.len() / 2)
 }
 
-pub fn process_part2(input: &str) -> String {
-    let mut graph: HashMap<(i32, i32), char> = HashMap::new();
-    for (y, line) in input.lines().enumerate() {
-        for (x, char) in line.char_indices() {
-            graph.insert((x as i32, y as i32), char);
+/// Follows the pipes from `first`, a neighbour of `start`, for as long as each
+/// connects back to the one before, returning the positions visited if they
+/// lead back to `start`.
+fn follow_pipes(
+    graph: &HashMap<(i32, i32), char>,
+    start: (i32, i32),
+    first: (i32, i32),
+) -> Option<Vec<(i32, i32)>> {
+    let mut path = vec![start];
+    let (mut prev, mut current) = (start, first);
+    while current != start {
+        let neighbors = pipe_neighbors(current.0, current.1, *graph.get(&current)?);
+        if !neighbors.contains(&prev) || path.len() > graph.len() {
+            return None;
         }
+        path.push(current);
+        let next = neighbors.into_iter().find(|p| *p != prev)?;
+        (prev, current) = (current, next);
     }
+    Some(path)
+}
 
-    let starting_position = *graph.iter().find(|(_, char)| **char == 'S').unwrap().0;
-    let starting_pipes = graph
-        .iter()
-        .filter(|((x, y), ch)| pipe_neighbors(*x, *y, **ch).contains(&starting_position))
-        .map(|(p, c)| (*p, *c, 1))
-        .collect_vec();
-    let mut seen: HashSet<(i32, i32)> = HashSet::new();
-    let mut queue = VecDeque::from(starting_pipes.clone());
-    while let Some(((x, y), ch, distance)) = queue.pop_front() {
-        for neighbor in pipe_neighbors(x, y, ch) {
-            if let Some(ch) = graph.get(&neighbor) {
-                if seen.insert(neighbor) {
-                    queue.push_back((neighbor, *ch, distance + 1));
-                }
-            }
-        }
-    }
+/// Positions of the loop through `S`, in the order they are visited. Pipes that
+/// point at `S` without being part of the loop are skipped.
+fn find_loop(graph: &HashMap<(i32, i32), char>) -> Result<Vec<(i32, i32)>, Error> {
+    let (x, y) = start_position(graph)?;
+    [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)]
+        .into_iter()
+        .find_map(|first| follow_pipes(graph, (x, y), first))
+        .ok_or_else(|| Error::invalid("the pipes through S don't form a loop"))
+}
 
-    let starting_pipes_positions = starting_pipes.iter().map(|(p, _, _)| p).collect_vec();
-    let potential_starting_characters = ['|', '-', 'L', 'J', 'F', '7'];
-    let starting_character = potential_starting_characters
-        .iter()
-        .find(|symbol| {
-            pipe_neighbors(starting_position.0, starting_position.1, **symbol)
-                .iter()
-                .all(|p| starting_pipes_positions.contains(&p))
-        })
-        .unwrap();
-    graph.insert(starting_position, *starting_character);
-    graph = graph
+/// How many tiles the loop encloses.
+fn enclosed_tiles(graph: &HashMap<(i32, i32), char>) -> Result<i64, Error> {
+    let vertices = find_loop(graph)?
         .into_iter()
-        .map(|(p, c)| if seen.contains(&p) { (p, c) } else { (p, '.') })
-        .collect();
-    (0..input.lines().count())
-        .map(|y| ray_trace(y as i32, &graph))
-        .sum::<usize>()
-        .to_string()
+        .map(|(x, y)| (x as i64, y as i64))
+        .collect_vec();
+    Ok(geometry::interior_points(&vertices))
 }
 
-fn ray_trace(y: i32, graph: &HashMap<(i32, i32), char>) -> usize {
-    let flip_on_chars = ['F', '|', '7'];
-    let mut x = 0;
-    let mut is_inside = false;
-    let mut symbols_inside = 0;
-    while let Some(ch) = graph.get(&(x, y)) {
-        if flip_on_chars.contains(ch) {
-            is_inside = !is_inside;
-        } else if *ch == '.' && is_inside {
-            symbols_inside += 1;
-        }
-        x += 1;
-    }
-    symbols_inside
+aoc_utils::solution! {
+    Day10: 2023 day 10;
+    type Parsed<'a> = HashMap<(i32, i32), char>;
+    parse: |input| parse_all(sparse_grid(Some), input);
+    part1: |graph| farthest_point(graph);
+    part2: |graph| enclosed_tiles(graph);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
     use rstest::rstest;
 
+    fn example(n: u8) -> &'static str {
+        inputs::example(2023, 10, n).unwrap()
+    }
+
     #[rstest]
-    #[case(
-        "-L|F7
-7S-7|
-L|7||
--L-J|
-L|-JF",
-        "4"
-    )]
-    #[case(
-        "7-F7-
-.FJ|7
-SJLL7
-|F--J
-LJ.LJ",
-        "8"
-    )]
+    #[case(1, "4")]
+    #[case(2, "8")]
+    #[case(3, "4")]
     #[trace]
-    fn test_part1(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(process_part1(input).as_str(), expected);
+    fn test_part1(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(process_part1(example(n)).as_str(), expected);
     }
 
     #[rstest]
-    #[case(
-        "..........
-.S------7.
-.|F----7|.
-.||....||.
-.||....||.
-.|L-7F-J|.
-.|..||..|.
-.L--JL--J.
-..........",
-        "4"
-    )]
-    #[case(
-        ".F----7F7F7F7F-7....
-.|F--7||||||||FJ....
-.||.FJ||||||||L7....
-FJL7L7LJLJ||LJ.L-7..
-L--J.L7...LJS7F-7L7.
-....F-J..F7FJ|L7L7L7
-....L7.F7||L7|.L7L7|
-.....|FJLJ|FJ|F7|.LJ
-....FJL-7.||.||||...
-....L---J.LJ.LJLJ...",
-        "8"
-    )]
-    #[case(
-        "FF7FSF7F7F7F7F7F---7
-L|LJ||||||||||||F--J
-FL-7LJLJ||||||LJL-77
-F--JF--7||LJLJ7F7FJ-
-L---JF-JLJ.||-FJLJJ7
-|F|F-JF---7F7-L7L|7|
-|FFJF7L7F-JF7|JL---7
-7-L-JL7||F7|L7F-7F7|
-L.L7LFJ|||||FJL7||LJ
-L7JLJL-JLJLJL--JLJ.L",
-        "10"
-    )]
+    #[case(4, "4")]
+    #[case(5, "8")]
+    #[case(6, "10")]
+    #[case(3, "1")]
     #[trace]
-    fn test_part2(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(process_part2(input).as_str(), expected);
+    fn test_part2(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(process_part2(example(n)).as_str(), expected);
     }
 }
@@ -1,20 +1,23 @@
+use aoc_utils::{
+    parse::{grid, parse_all},
+    Error,
+};
 use itertools::Itertools;
+use nom::character::complete::one_of;
 
-fn parse_image(input: &str) -> Vec<(usize, usize)> {
-    let mut image: Vec<(usize, usize)> = vec![];
-    for (y, line) in input.lines().enumerate() {
-        for (x, ch) in line.chars().enumerate() {
-            if ch == '#' {
-                image.push((x, y));
-            }
-        }
-    }
-    image
+/// The positions of the galaxies.
+fn parse_image(input: &str) -> Result<Vec<(usize, usize)>, Error> {
+    let image = parse_all(grid(one_of(".#")), input)?;
+    Ok(image
+        .iter()
+        .filter(|(_, ch)| **ch == '#')
+        .map(|((x, y), _)| (x as usize, y as usize))
+        .collect())
 }
 
 fn expand_image(image: &[(usize, usize)], expansion_factor: usize) -> Vec<(usize, usize)> {
-    let max_x = image.iter().max_by_key(|(gx, _)| *gx).unwrap().0;
-    let max_y = image.iter().max_by_key(|(_, gy)| *gy).unwrap().1;
+    let max_x = image.iter().map(|(gx, _)| *gx).max().unwrap_or(0);
+    let max_y = image.iter().map(|(_, gy)| *gy).max().unwrap_or(0);
     let empty_rows = (0..max_y)
         .filter(|y| image.iter().all(|(_, gy)| gy != y))
         .collect_vec();
@@ -32,8 +35,10 @@ fn expand_image(image: &[(usize, usize)], expansion_factor: usize) -> Vec<(usize
         .collect_vec()
 }
 
-pub fn process_part1(input: &str) -> String {
-    expand_image(&parse_image(input), 2)
+/// The sum of the shortest paths between every pair of galaxies, once every
+/// empty row and column has been replaced by `expansion_factor` of them.
+fn sum_of_distances(image: &[(usize, usize)], expansion_factor: usize) -> u64 {
+    expand_image(image, expansion_factor)
         .into_iter()
         .combinations(2)
         .map(|galaxy_pair| {
@@ -42,33 +47,32 @@ pub fn process_part1(input: &str) -> String {
             };
             (*x1 as i64 - *x2 as i64).unsigned_abs() + (*y1 as i64 - *y2 as i64).unsigned_abs()
         })
-        .sum::<u64>()
-        .to_string()
+        .sum()
 }
 
-pub fn process_part2(input: &str) -> String {
-    expand_image(&parse_image(input), 1_000_000)
-        .into_iter()
-        .combinations(2)
-        .map(|galaxy_pair| {
-            let [(x1, y1), (x2, y2)] = galaxy_pair.as_slice() else {
-                panic!("unexpected galaxy pair: {:?}", galaxy_pair)
-            };
-            (*x1 as i64 - *x2 as i64).unsigned_abs() + (*y1 as i64 - *y2 as i64).unsigned_abs()
-        })
-        .sum::<u64>()
-        .to_string()
+aoc_utils::solution! {
+    Day11: 2023 day 11;
+    type Parsed<'a> = Vec<(usize, usize)>;
+    parse: |input| parse_image(input);
+    part1: |image| Ok(sum_of_distances(image, 2));
+    part2: |image| Ok(sum_of_distances(image, 1_000_000));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
     use rstest::rstest;
 
+    #[test]
+    fn example_part1() {
+        assert_eq!(process_part1(inputs::example(2023, 11, 1).unwrap()), "374");
+    }
+
     #[rstest]
     #[case(
         "#.#
-        ...
+...
 #.#",
         "24"
     )]
@@ -85,19 +89,6 @@ mod tests {
 ..#",
         "6"
     )]
-    #[case(
-        "...#......
-.......#..
-#.........
-..........
-......#...
-.#........
-.........#
-..........
-.......#..
-#...#.....",
-        "374"
-    )]
     #[trace]
     fn test_part1(#[case] input: &str, #[case] expected: &str) {
         assert_eq!(process_part1(input).as_str(), expected);
@@ -1,10 +1,13 @@
 use std::collections::HashMap;
 
-use itertools::Itertools;
+use aoc_utils::{
+    parse::{lines, parse_all},
+    Error,
+};
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{self, newline, space1},
+    character::complete::{self, space1},
     multi::{many1, separated_list1},
     sequence::separated_pair,
     IResult, Parser,
@@ -12,13 +15,16 @@ use nom::{
 use Record::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Record {
+pub enum Record {
     Operational,
     Damaged,
     Unknown,
 }
 
-fn parse_row(input: &str) -> IResult<&str, (Vec<Record>, Vec<usize>)> {
+/// The condition records of one row and the sizes of its damaged groups.
+pub type Row = (Vec<Record>, Vec<usize>);
+
+fn parse_row(input: &str) -> IResult<&str, Row> {
     separated_pair(
         many1(alt((
             complete::char('#').map(|_| Damaged),
@@ -30,8 +36,8 @@ fn parse_row(input: &str) -> IResult<&str, (Vec<Record>, Vec<usize>)> {
     )(input)
 }
 
-fn parse_rows(input: &str) -> IResult<&str, Vec<(Vec<Record>, Vec<usize>)>> {
-    separated_list1(newline, parse_row)(input)
+fn parse_rows(input: &str) -> Result<Vec<Row>, Error> {
+    parse_all(lines(parse_row), input)
 }
 
 fn calculate_arrangements<'a>(
@@ -80,61 +86,52 @@ fn calculate_arrangements<'a>(
     permutations
 }
 
-fn expand_row(records: Vec<Record>, groups: Vec<usize>) -> (Vec<Record>, Vec<usize>) {
-    let new_records = itertools::Itertools::intersperse(
-        std::iter::repeat(records.into_iter()).take(5),
-        vec![Unknown].into_iter(),
-    )
-    .flatten()
-    .collect_vec();
-    let new_groups = std::iter::repeat(groups.into_iter())
-        .take(5)
-        .flatten()
-        .collect_vec();
-    (new_records, new_groups)
+fn expand_row(records: Vec<Record>, groups: Vec<usize>) -> Row {
+    (vec![records; 5].join(&Unknown), groups.repeat(5))
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (input, rows) = parse_rows(input).unwrap();
-    debug_assert_eq!(input, "");
-    rows.into_iter()
+fn total_arrangements(rows: &[Row]) -> usize {
+    rows.iter()
         .map(|(records, groups)| {
             calculate_arrangements(&mut HashMap::new(), records.as_slice(), groups.as_slice())
         })
-        .sum::<usize>()
-        .to_string()
+        .sum()
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (input, rows) = parse_rows(input).unwrap();
-    debug_assert_eq!(input, "");
-    rows.into_iter()
-        .map(|(records, groups)| expand_row(records, groups))
-        .map(|(records, groups)| {
-            calculate_arrangements(&mut HashMap::new(), records.as_slice(), groups.as_slice())
-        })
-        .sum::<usize>()
-        .to_string()
+/// The arrangements once every row has been unfolded to five copies of itself.
+fn total_unfolded_arrangements(rows: &[Row]) -> usize {
+    let rows = rows
+        .iter()
+        .map(|(records, groups)| expand_row(records.clone(), groups.clone()))
+        .collect::<Vec<_>>();
+    total_arrangements(&rows)
+}
+
+aoc_utils::solution! {
+    Day12: 2023 day 12;
+    type Parsed<'a> = Vec<Row>;
+    parse: |input| parse_rows(input);
+    part1: |rows| Ok(total_arrangements(rows));
+    part2: |rows| Ok(total_unfolded_arrangements(rows));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
+    use itertools::Itertools;
+    use proptest::prelude::*;
     use rstest::rstest;
 
+    fn example(n: u8) -> &'static str {
+        inputs::example(2023, 12, n).unwrap()
+    }
+
     #[rstest]
-    #[case(
-        "???.### 1,1,3
-.??..??...?##. 1,1,3
-?#?#?#?#?#?#?#? 1,3,1,6
-????.#...#... 4,1,1
-????.######..#####. 1,6,5
-?###???????? 3,2,1",
-        "21"
-    )]
+    #[case(1, "21")]
     #[trace]
-    fn test_part1(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(process_part1(input).as_str(), expected);
+    fn test_part1(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(process_part1(example(n)).as_str(), expected);
     }
 
     #[rstest]
@@ -166,4 +163,91 @@ mod tests {
     fn test_part2(#[case] input: &str, #[case] expected: &str) {
         assert_eq!(process_part2(input).as_str(), expected);
     }
+
+    /// Rows of up to `len` springs. Half of them are a picture with some of it
+    /// hidden, so they have at least one arrangement, and the other half are
+    /// random records with random groups.
+    fn row(len: usize) -> impl Strategy<Value = String> {
+        let picture = prop::collection::vec((any::<bool>(), any::<bool>()), 1..=len)
+            .prop_filter("no damaged springs", |cells| cells.iter().any(|c| c.0))
+            .prop_map(|cells| {
+                let records: String = cells
+                    .iter()
+                    .map(|&(damaged, hidden)| match (damaged, hidden) {
+                        (_, true) => '?',
+                        (true, false) => '#',
+                        (false, false) => '.',
+                    })
+                    .collect();
+                let groups = cells
+                    .split(|c| !c.0)
+                    .filter(|group| !group.is_empty())
+                    .map(|group| group.len())
+                    .join(",");
+                format!("{records} {groups}")
+            });
+        let random = (
+            prop::collection::vec(prop::sample::select(vec!['.', '#', '?']), 1..=len),
+            prop::collection::vec(1..=3usize, 1..=3),
+        )
+            .prop_map(|(records, groups)| {
+                format!(
+                    "{} {}",
+                    records.iter().collect::<String>(),
+                    groups.iter().join(",")
+                )
+            });
+        prop_oneof![picture, random]
+    }
+
+    /// Tries every way of filling in the unknown springs.
+    fn count_by_brute_force(row: &str) -> usize {
+        let (records, groups) = row.split_once(' ').unwrap();
+        let groups: Vec<usize> = groups.split(',').map(|g| g.parse().unwrap()).collect();
+        let unknowns = records.matches('?').count();
+        (0..1u32 << unknowns)
+            .filter(|bits| {
+                let mut unknown = 0;
+                let filled: String = records
+                    .chars()
+                    .map(|c| match c {
+                        '?' => {
+                            unknown += 1;
+                            if bits >> (unknown - 1) & 1 == 1 {
+                                '#'
+                            } else {
+                                '.'
+                            }
+                        }
+                        c => c,
+                    })
+                    .collect();
+                filled
+                    .split('.')
+                    .filter(|group| !group.is_empty())
+                    .map(str::len)
+                    .eq(groups.iter().copied())
+            })
+            .count()
+    }
+
+    proptest! {
+        #[test]
+        fn arrangements_match_brute_force(rows in prop::collection::vec(row(14), 1..=3)) {
+            let expected: usize = rows.iter().map(|row| count_by_brute_force(row)).sum();
+            prop_assert_eq!(process_part1(&rows.join("\n")), expected.to_string());
+        }
+    }
+
+    proptest! {
+        // Unfolded rows have five times as many unknowns, so they're kept tiny.
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn unfolded_arrangements_match_brute_force(row in row(2)) {
+            let (records, groups) = row.split_once(' ').unwrap();
+            let unfolded = format!("{} {}", [records; 5].join("?"), [groups; 5].join(","));
+            prop_assert_eq!(process_part2(&row), count_by_brute_force(&unfolded).to_string());
+        }
+    }
 }
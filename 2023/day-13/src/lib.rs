@@ -1,18 +1,19 @@
+use aoc_utils::{
+    parse::{blocks, grid, parse_all},
+    Error,
+};
 use itertools::Itertools;
+use nom::character::complete::one_of;
 
-fn parse_patterns(input: &str) -> Vec<Vec<Vec<char>>> {
-    input
-        .split("\n\n")
-        .map(|pattern| {
-            pattern
-                .lines()
-                .map(|line| line.chars().collect_vec())
-                .collect_vec()
-        })
-        .collect_vec()
+fn parse_patterns(input: &str) -> Result<Vec<Vec<Vec<char>>>, Error> {
+    let patterns = parse_all(blocks(grid(one_of(".#"))), input)?;
+    Ok(patterns
+        .iter()
+        .map(|pattern| pattern.rows().map(<[char]>::to_vec).collect_vec())
+        .collect_vec())
 }
 
-fn find_reflection(pattern: &Vec<Vec<char>>) -> (usize, usize) {
+fn find_reflection(pattern: &[Vec<char>]) -> (usize, usize) {
     for col in 1..pattern[0].len() {
         let mut left = col as i64 - 1;
         let mut right = col;
@@ -48,15 +49,19 @@ fn find_reflection(pattern: &Vec<Vec<char>>) -> (usize, usize) {
     (0, 0)
 }
 
-fn find_reflection_with_smudge(pattern: &Vec<Vec<char>>) -> (usize, usize) {
+fn find_reflection_with_smudge(pattern: &[Vec<char>]) -> (usize, usize) {
     for col in 1..pattern[0].len() {
         let mut left = col as i64 - 1;
         let mut right = col;
         let mut differences = 0;
         while left >= 0 && right < pattern[0].len() {
-            differences += get_column(pattern, left as usize).iter().zip(get_column(pattern, right)).filter(|(l,r)| *l != r).count();
+            differences += get_column(pattern, left as usize)
+                .iter()
+                .zip(get_column(pattern, right))
+                .filter(|(l, r)| *l != r)
+                .count();
             if differences > 1 {
-                break
+                break;
             }
             left -= 1;
             right += 1;
@@ -70,9 +75,13 @@ fn find_reflection_with_smudge(pattern: &Vec<Vec<char>>) -> (usize, usize) {
         let mut down = row;
         let mut differences = 0;
         while up >= 0 && down < pattern.len() {
-            differences += pattern[up as usize].iter().zip(pattern[down].iter()).filter(|(l,r)| l != r).count();
+            differences += pattern[up as usize]
+                .iter()
+                .zip(pattern[down].iter())
+                .filter(|(l, r)| l != r)
+                .count();
             if differences > 1 {
-                break
+                break;
             }
             up -= 1;
             down += 1;
@@ -84,88 +93,53 @@ fn find_reflection_with_smudge(pattern: &Vec<Vec<char>>) -> (usize, usize) {
     (0, 0)
 }
 
-fn get_column(pattern: &Vec<Vec<char>>, column: usize) -> Vec<char> {
+fn get_column(pattern: &[Vec<char>], column: usize) -> Vec<char> {
     pattern.iter().map(|row| row[column]).collect_vec()
 }
 
-pub fn process_part1(input: &str) -> String {
-    let patterns = parse_patterns(input);
+/// The columns left of each pattern's line of reflection plus 100 times the
+/// rows above it, with the line found by `find`.
+fn summarize(patterns: &[Vec<Vec<char>>], find: fn(&[Vec<char>]) -> (usize, usize)) -> usize {
     patterns
         .iter()
         .map(|pattern| {
-            let (horizontal, vertical) = find_reflection(pattern);
+            let (horizontal, vertical) = find(pattern);
             horizontal * 100 + vertical
         })
-        .sum::<usize>()
-        .to_string()
+        .sum()
 }
 
-pub fn process_part2(input: &str) -> String {
-    let patterns = parse_patterns(input);
-    patterns
-        .iter()
-        .map(|pattern| {
-            let (horizontal, vertical)= find_reflection_with_smudge(pattern);
-            horizontal * 100 + vertical
-        })
-        .sum::<usize>()
-        .to_string()
+aoc_utils::solution! {
+    Day13: 2023 day 13;
+    type Parsed<'a> = Vec<Vec<Vec<char>>>;
+    parse: |input| parse_patterns(input);
+    part1: |patterns| Ok(summarize(patterns, find_reflection));
+    part2: |patterns| Ok(summarize(patterns, find_reflection_with_smudge));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
     use rstest::rstest;
 
+    fn example(n: u8) -> &'static str {
+        inputs::example(2023, 13, n).unwrap()
+    }
+
     #[rstest]
-    #[case(
-        "#.##..##.
-..#.##.#.
-##......#
-##......#
-..#.##.#.
-..##..##.
-#.#.##.#.",
-        "5"
-    )]
-    #[case(
-        "#...##..#
-#....#..#
-..##..###
-#####.##.
-#####.##.
-..##..###
-#....#..#",
-        "400"
-    )]
+    #[case(1, "5")]
+    #[case(2, "400")]
     #[trace]
-    fn test_part1(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(process_part1(input).as_str(), expected);
+    fn test_part1(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(process_part1(example(n)).as_str(), expected);
     }
 
     #[rstest]
-    #[case(
-        "#.##..##.
-..#.##.#.
-##......#
-##......#
-..#.##.#.
-..##..##.
-#.#.##.#.",
-        "300"
-    )]
-    #[case(
-        "#...##..#
-#....#..#
-..##..###
-#####.##.
-#####.##.
-..##..###
-#....#..#",
-        "100"
-    )]
+    #[case(1, "300")]
+    #[case(2, "100")]
     #[trace]
-    fn test_part2(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(process_part2(input).as_str(), expected);
+    fn test_part2(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(process_part2(example(n)).as_str(), expected);
     }
 }
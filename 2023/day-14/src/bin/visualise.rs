@@ -0,0 +1,47 @@
+//! Tilts the platform through 10 spin cycles, or as many as the first argument
+//! says, and writes every tilt to `day-14.gif` or the path given as the second
+//! argument.
+
+use std::env;
+
+use aoc_utils::{
+    inputs,
+    render::{Animation, Image, Rgb},
+};
+use day_14::{parse_input, spin_tilts};
+
+const SPACE: Rgb = [30, 30, 45];
+const CUBE: Rgb = [110, 110, 120];
+const ROUND: Rgb = [200, 200, 210];
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let spin_cycles = args.first().map_or(10, |cycles| {
+        cycles
+            .parse()
+            .unwrap_or_else(|err| panic!("{cycles} isn't a number of spin cycles: {err}"))
+    });
+    let path = args.get(1).map_or("day-14.gif", String::as_str);
+
+    let input = inputs::input(2023, 14).unwrap_or_else(|err| panic!("{err}"));
+    let platform = parse_input(input).unwrap_or_else(|err| panic!("{err}"));
+    let (_, fixed, (width, height)) = &platform;
+
+    let mut background = Image::filled(*width, *height, SPACE);
+    for &position in fixed {
+        background.set(position, CUBE);
+    }
+
+    let mut animation = Animation::new(25);
+    for round in spin_tilts(&platform, spin_cycles) {
+        let mut image = background.clone();
+        for position in round {
+            image.set(position, ROUND);
+        }
+        animation.push(image.scaled(4));
+    }
+    animation
+        .write_gif(path)
+        .unwrap_or_else(|err| panic!("cannot write {path}: {err}"));
+    println!("{} frames written to {path}", animation.len());
+}
@@ -1,24 +1,35 @@
 use std::collections::HashSet;
 
+use aoc_utils::{
+    parse::{grid, parse_all},
+    Error,
+};
 use either::Either;
 use itertools::Itertools;
+use nom::character::complete::one_of;
 use Direction::*;
 
-fn parse_input(input: &str) -> (Vec<(usize, usize)>, HashSet<(usize, usize)>) {
+pub type Position = (usize, usize);
+
+/// The round rocks, which roll, the cube rocks, which stay put, and the width
+/// and height of the platform.
+pub type Platform = (Vec<Position>, HashSet<Position>, (usize, usize));
+
+pub fn parse_input(input: &str) -> Result<Platform, Error> {
+    let platform = parse_all(grid(one_of(".#O")), input)?;
     let mut movable = Vec::new();
     let mut fixed = HashSet::new();
-    for (y, line) in input.lines().enumerate() {
-        for (x, ch) in line.char_indices() {
-            match ch {
-                '#' => {
-                    fixed.insert((x, y));
-                }
-                'O' => movable.push((x, y)),
-                _ => continue,
-            };
-        }
+    for ((x, y), ch) in platform.iter() {
+        let position = (x as usize, y as usize);
+        match ch {
+            '#' => {
+                fixed.insert(position);
+            }
+            'O' => movable.push(position),
+            _ => continue,
+        };
     }
-    (movable, fixed)
+    Ok((movable, fixed, (platform.width(), platform.height())))
 }
 
 #[derive(PartialEq, Eq, Debug, Hash, Clone, Copy)]
@@ -87,66 +98,58 @@ fn cycle(
         })
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (max_x, max_y) = aoc_utils::grid_bounds(input);
-    let (movable, fixed) = parse_input(input);
-    tilt(&movable, &fixed, North, (max_x, max_y))
-        .into_iter()
-        .map(|(_, y)| max_y - y)
-        .sum::<usize>()
-        .to_string()
-}
-
-pub fn process_part2(input: &str) -> String {
-    let (max_x, max_y) = aoc_utils::grid_bounds(input);
-    let (mut movable, fixed) = parse_input(input);
-
-    let mut seen: HashSet<Vec<(usize, usize)>> = HashSet::new();
-    seen.insert(movable.clone());
-
-    let mut states = vec![movable.clone()];
-
-    let mut it = 0;
-    loop {
-        it += 1;
-        movable = cycle(&movable, &fixed, (max_x, max_y));
-        if seen.contains(&movable) {
-            break;
-        }
-        seen.insert(movable.clone());
-        states.push(movable.clone());
+/// Where the round rocks are after every tilt of the first `spin_cycles`,
+/// starting with where they are before the first.
+pub fn spin_tilts((movable, fixed, bounds): &Platform, spin_cycles: usize) -> Vec<Vec<Position>> {
+    let tilts = [North, West, South, East].into_iter().cycle();
+    let mut positions = vec![movable.clone()];
+    for dir in tilts.take(4 * spin_cycles) {
+        let tilted = tilt(&positions[positions.len() - 1], fixed, dir, *bounds);
+        positions.push(tilted);
     }
+    positions
+}
 
-    let start_of_loop_index = states.iter().position(|s| s == &movable).unwrap();
-    let final_state_index =
-        (1_000_000_000 - start_of_loop_index) % (it - start_of_loop_index) + start_of_loop_index;
+/// The load on the north support beams once the platform is tilted north.
+fn north_load((movable, fixed, bounds): &Platform) -> usize {
+    tilt(movable, fixed, North, *bounds)
+        .into_iter()
+        .map(|(_, y)| bounds.1 - y)
+        .sum()
+}
 
-    states[final_state_index]
+/// The load on the north support beams after a billion spin cycles.
+fn load_after_spin_cycles((movable, fixed, bounds): &Platform) -> usize {
+    let spin_cycles =
+        aoc_utils::find_cycle(movable.clone(), |movable| cycle(&movable, fixed, *bounds));
+    spin_cycles
+        .at(1_000_000_000)
         .iter()
-        .map(|(_, y)| max_y - *y)
-        .sum::<usize>()
-        .to_string()
+        .map(|(_, y)| bounds.1 - *y)
+        .sum()
+}
+
+aoc_utils::solution! {
+    Day14: 2023 day 14;
+    type Parsed<'a> = Platform;
+    parse: |input| parse_input(input);
+    part1: |platform| Ok(north_load(platform));
+    part2: |platform| Ok(load_after_spin_cycles(platform));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
     use rstest::rstest;
 
-    const INPUT: &str = "O....#....
-O.OO#....#
-.....##...
-OO.#O....O
-.O.....O#.
-O.#..O.#.#
-..O..#O..O
-.......O..
-#....###..
-#OO..#....";
+    fn example() -> &'static str {
+        inputs::example(2023, 14, 1).unwrap()
+    }
 
     #[test]
     fn test_part1() {
-        assert_eq!(process_part1(INPUT).as_str(), "136");
+        assert_eq!(process_part1(example()).as_str(), "136");
     }
 
     #[rstest]
@@ -191,13 +194,11 @@ O.#..O.#.#
     )]
     #[trace]
     fn test_cycle(#[case] cycles: usize, #[case] expected: &str) {
-        let max_y = INPUT.lines().count();
-        let max_x = INPUT.lines().next().unwrap().len();
-        let (mut movable, fixed) = parse_input(INPUT);
+        let (mut movable, fixed, bounds) = parse_input(example()).unwrap();
         for _ in 0..cycles {
-            movable = cycle(&movable, &fixed, (max_x, max_y));
+            movable = cycle(&movable, &fixed, bounds);
         }
-        let actual = grid_to_string(&movable, &fixed, (max_x, max_y));
+        let actual = grid_to_string(&movable, &fixed, bounds);
         println!("{}", actual);
         println!("-----");
         println!("{}", expected);
@@ -220,20 +221,30 @@ O..#.OO...
     )]
     #[trace]
     fn test_tilt(#[case] dir: Direction, #[case] expected: &str) {
-        let max_y = INPUT.lines().count();
-        let max_x = INPUT.lines().next().unwrap().len();
-        let (mut movable, fixed) = parse_input(INPUT);
-        movable = tilt(&movable, &fixed, dir, (max_x, max_y));
-        let actual = grid_to_string(&movable, &fixed, (max_x, max_y));
+        let (mut movable, fixed, bounds) = parse_input(example()).unwrap();
+        movable = tilt(&movable, &fixed, dir, bounds);
+        let actual = grid_to_string(&movable, &fixed, bounds);
         println!("{}", actual);
         println!("-----");
         println!("{}", expected);
         assert_eq!(actual.as_str(), expected);
     }
 
+    #[test]
+    fn spin_tilts_follow_each_cycle() {
+        let platform = parse_input(example()).unwrap();
+        let (movable, fixed, bounds) = &platform;
+        let tilts = spin_tilts(&platform, 2);
+        assert_eq!(tilts.len(), 9);
+        assert_eq!(tilts[0], *movable);
+        assert_eq!(tilts[1], tilt(movable, fixed, North, *bounds));
+        assert_eq!(tilts[4], cycle(movable, fixed, *bounds));
+        assert_eq!(tilts[8], cycle(&tilts[4], fixed, *bounds));
+    }
+
     #[test]
     fn test_part2() {
-        assert_eq!(process_part2(INPUT).as_str(), "64");
+        assert_eq!(process_part2(example()).as_str(), "64");
     }
 
     fn grid_to_string(
@@ -241,7 +252,7 @@ O..#.OO...
         fixed: &HashSet<(usize, usize)>,
         (max_x, max_y): (usize, usize),
     ) -> String {
-        let movable: HashSet<(usize, usize)> = movable.to_owned().into_iter().collect();
+        let movable: HashSet<(usize, usize)> = movable.iter().copied().collect();
         let mut s = String::with_capacity(max_x * max_y);
         for y in 0..max_y {
             for x in 0..max_x {
@@ -1,34 +1,51 @@
+use aoc_utils::{
+    parse::{integer, parse_all},
+    Error,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, char},
+    combinator::consumed,
+    multi::separated_list1,
+    sequence::{pair, preceded},
+    IResult, Parser,
+};
+
 #[derive(Debug, Clone, Copy, PartialEq)]
-enum Op {
+pub enum Op {
     Add(u32),
     Remove,
 }
 
 use Op::*;
 
-fn parse_step(input: &str) -> (&str, Op) {
-    let label_ends_at = input.chars().position(|c| c == '=' || c == '-').unwrap();
-    let label = &input[..label_ends_at];
-    let op = match input.chars().nth(label_ends_at).unwrap() {
-        '=' => Add(input
-            .chars()
-            .nth(label_ends_at + 1)
-            .unwrap()
-            .to_digit(10)
-            .unwrap()),
-        '-' => Remove,
-        _ => unreachable!(),
-    };
-    (label, op)
+fn parse_step(input: &str) -> IResult<&str, (&str, Op)> {
+    pair(
+        alpha1,
+        alt((
+            preceded(char('='), integer).map(Add),
+            char('-').map(|_| Remove),
+        )),
+    )(input)
 }
 
-pub fn process_part1(input: &str) -> String {
-    input.split(',').map(hash).sum::<usize>().to_string()
+/// A step as written, with the label and operation it's made of.
+pub type Step<'a> = (&'a str, (&'a str, Op));
+
+fn parse_steps(input: &str) -> Result<Vec<Step<'_>>, Error> {
+    parse_all(separated_list1(tag(","), consumed(parse_step)), input)
 }
 
-pub fn process_part2(input: &str) -> String {
+/// The sum of the hashes of every step as written.
+fn sum_of_hashes(steps: &[Step<'_>]) -> usize {
+    steps.iter().map(|(step, _)| hash(step)).sum()
+}
+
+/// The focusing power of the lenses once every step has been carried out.
+fn focusing_power(steps: &[Step<'_>]) -> usize {
     let mut boxes: Vec<Vec<(String, u32)>> = vec![vec![]; 256];
-    for (label, op) in input.split(',').map(parse_step) {
+    for &(_, (label, op)) in steps {
         let box_idx = hash(label);
         let lens_idx = boxes[box_idx]
             .iter()
@@ -42,6 +59,7 @@ pub fn process_part2(input: &str) -> String {
             (Remove, None) => (),
         };
     }
+
     boxes
         .into_iter()
         .enumerate()
@@ -51,8 +69,7 @@ pub fn process_part2(input: &str) -> String {
                 .map(|(lens_idx, (_, lens))| (box_idx + 1) * (lens_idx + 1) * (lens as usize))
                 .sum::<usize>()
         })
-        .sum::<usize>()
-        .to_string()
+        .sum()
 }
 
 fn hash(s: &str) -> usize {
@@ -64,16 +81,29 @@ fn hash(s: &str) -> usize {
     })
 }
 
+aoc_utils::solution! {
+    Day15: 2023 day 15;
+    type Parsed<'a> = Vec<Step<'a>>;
+    parse: |input| parse_steps(input);
+    part1: |steps| Ok(sum_of_hashes(steps));
+    part2: |steps| Ok(focusing_power(steps));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
     use rstest::rstest;
 
+    fn example(n: u8) -> &'static str {
+        inputs::example(2023, 15, n).unwrap()
+    }
+
     #[rstest]
-    #[case("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7", "1320")]
+    #[case(1, "1320")]
     #[trace]
-    fn test_part1(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(process_part1(input).as_str(), expected);
+    fn test_part1(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(process_part1(example(n)).as_str(), expected);
     }
 
     #[rstest]
@@ -84,9 +114,9 @@ mod tests {
     }
 
     #[rstest]
-    #[case("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7", "145")]
+    #[case(1, "145")]
     #[trace]
-    fn test_part2(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(process_part2(input).as_str(), expected);
+    fn test_part2(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(process_part2(example(n)).as_str(), expected);
     }
 }
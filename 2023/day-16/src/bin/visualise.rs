@@ -0,0 +1,51 @@
+//! Follows the beam of part 1 into the contraption and writes how it spreads to
+//! `day-16.gif` or the path given as the first argument.
+
+use std::env;
+
+use aoc_utils::{
+    inputs,
+    render::{Animation, Image, Rgb},
+    Direction,
+};
+use day_16::{beam_fronts, parse_input};
+
+const EMPTY: Rgb = [20, 20, 30];
+const DEVICE: Rgb = [100, 100, 120];
+const ENERGIZED: Rgb = [200, 120, 40];
+const FRONT: Rgb = [255, 240, 180];
+const FRAMES: usize = 200;
+
+fn main() {
+    let path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "day-16.gif".to_string());
+
+    let input = inputs::input(2023, 16).unwrap_or_else(|err| panic!("{err}"));
+    let (tiles, (width, height)) = parse_input(input).unwrap_or_else(|err| panic!("{err}"));
+    let fronts = beam_fronts(&tiles, (width, height), ((0, 0), Direction::Right));
+
+    let pixel = |(x, y): (i32, i32)| (x as usize, y as usize);
+    let mut image = Image::filled(width, height, EMPTY);
+    for &position in tiles.keys() {
+        image.set(pixel(position), DEVICE);
+    }
+
+    let mut animation = Animation::new(4);
+    let per_frame = fronts.len().div_ceil(FRAMES).max(1);
+    for steps in fronts.chunks(per_frame) {
+        for &(position, _) in steps.iter().flatten() {
+            image.set(pixel(position), ENERGIZED);
+        }
+        // Only the frame shows the front of the beam, so it moves on next frame.
+        let mut frame = image.clone();
+        for &(position, _) in steps.last().into_iter().flatten() {
+            frame.set(pixel(position), FRONT);
+        }
+        animation.push(frame.scaled(4));
+    }
+    animation
+        .write_gif(&path)
+        .unwrap_or_else(|err| panic!("cannot write {path}: {err}"));
+    println!("{} frames written to {path}", animation.len());
+}
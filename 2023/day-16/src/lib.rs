@@ -1,55 +1,52 @@
 use std::collections::{HashMap, HashSet};
 
+use aoc_utils::{
+    parse::{grid, parse_all},
+    Direction, Error,
+};
 use itertools::Itertools;
+use nom::{character::complete::one_of, combinator::map};
 use rayon::prelude::*;
 use Direction::*;
 use Mirrors::*;
 use Splitters::*;
 use Tile::*;
 
-#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
 #[derive(Debug, PartialEq, Clone, Copy)]
-enum Mirrors {
+pub enum Mirrors {
     Backslash,    // \
     Forwardslash, // /
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
-enum Splitters {
+pub enum Splitters {
     Vertical,
     Horizontal,
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
-enum Tile {
+pub enum Tile {
     Mirror(Mirrors),
     Splitter(Splitters),
 }
 
-fn parse_input(input: &str) -> HashMap<(i32, i32), Tile> {
-    let mut tiles = HashMap::new();
-    for (y, line) in input.lines().enumerate() {
-        for (x, ch) in line.char_indices() {
-            let tile = match ch {
-                '|' => Some(Splitter(Vertical)),
-                '-' => Some(Splitter(Horizontal)),
-                '/' => Some(Mirror(Forwardslash)),
-                '\\' => Some(Mirror(Backslash)),
-                _ => None,
-            };
-            if let Some(tile) = tile {
-                tiles.insert((x as i32, y as i32), tile);
-            };
-        }
-    }
-    tiles
+/// The mirrors and splitters, and the width and height of the contraption.
+pub type Contraption = (HashMap<(i32, i32), Tile>, (usize, usize));
+
+pub fn parse_input(input: &str) -> Result<Contraption, Error> {
+    let tile = map(one_of(".|-/\\"), |ch| match ch {
+        '|' => Some(Splitter(Vertical)),
+        '-' => Some(Splitter(Horizontal)),
+        '/' => Some(Mirror(Forwardslash)),
+        '\\' => Some(Mirror(Backslash)),
+        _ => None,
+    });
+    let contraption = parse_all(grid(tile), input)?;
+    let tiles = contraption
+        .iter()
+        .filter_map(|(p, tile)| tile.map(|tile| (p, tile)))
+        .collect();
+    Ok((tiles, (contraption.width(), contraption.height())))
 }
 
 fn next_states(
@@ -57,86 +54,68 @@ fn next_states(
     colliding_tile: Option<&Tile>,
     dir: Direction,
 ) -> Vec<((i32, i32), Direction)> {
-    match (dir, colliding_tile) {
+    let next_dirs = match (colliding_tile, dir.is_horizontal()) {
         // Straight
-        (Up, None) | (Up, Some(Splitter(Vertical))) => vec![((x, y - 1), dir)],
-        (Down, None) | (Down, Some(Splitter(Vertical))) => vec![((x, y + 1), dir)],
-        (Left, None) | (Left, Some(Splitter(Horizontal))) => vec![((x - 1, y), dir)],
-        (Right, None) | (Right, Some(Splitter(Horizontal))) => vec![((x + 1, y), dir)],
-        // Turns
-        (Up, Some(Mirror(Backslash))) | (Down, Some(Mirror(Forwardslash))) => {
-            vec![((x - 1, y), Left)]
+        (None, _) | (Some(Splitter(Vertical)), false) | (Some(Splitter(Horizontal)), true) => {
+            vec![dir]
         }
-        (Up, Some(Mirror(Forwardslash))) | (Down, Some(Mirror(Backslash))) => {
-            vec![((x + 1, y), Right)]
-        }
-        (Left, Some(Mirror(Forwardslash))) | (Right, Some(Mirror(Backslash))) => {
-            vec![((x, y + 1), Down)]
+        // Turns
+        (Some(Mirror(Backslash)), true) | (Some(Mirror(Forwardslash)), false) => {
+            vec![dir.turn_cw()]
         }
-        (Left, Some(Mirror(Backslash))) | (Right, Some(Mirror(Forwardslash))) => {
-            vec![((x, y - 1), Up)]
+        (Some(Mirror(Backslash)), false) | (Some(Mirror(Forwardslash)), true) => {
+            vec![dir.turn_ccw()]
         }
         // Splits
-        (Up, Some(Splitter(Horizontal))) | (Down, Some(Splitter(Horizontal))) => {
-            vec![((x - 1, y), Left), ((x + 1, y), Right)]
-        }
-        (Left, Some(Splitter(Vertical))) | (Right, Some(Splitter(Vertical))) => {
-            vec![((x, y - 1), Up), ((x, y + 1), Down)]
-        }
-    }
+        (Some(Splitter(_)), _) => vec![dir.turn_cw(), dir.turn_ccw()],
+    };
+    next_dirs
+        .into_iter()
+        .map(|d| (d.step((x, y)), d))
+        .collect_vec()
 }
 
 fn is_within_bounds((max_x, max_y): (usize, usize), (x, y): (i32, i32)) -> bool {
     x >= 0 && x < max_x as i32 && y >= 0 && y < max_y as i32
 }
 
-fn start_to_energized(
+/// The beam spreading out from `start`, one step at a time: the tiles it
+/// reaches on each step and the directions it goes through them in. A tile
+/// already passed through in the same direction ends that part of the beam.
+pub fn beam_fronts(
     tiles: &HashMap<(i32, i32), Tile>,
     bounds: (usize, usize),
     start: ((i32, i32), Direction),
-) -> usize {
-    let mut seen: HashSet<((i32, i32), Direction)> = HashSet::new();
-    seen.insert(start);
-    aoc_utils::complete_bfs(vec![start].into_iter(), |((x, y), dir)| {
-        let states = next_states((x, y), tiles.get(&(x, y)), dir);
-        // println!(
-        // "({:?}, {:?}) + {:?} -> {:?}",
-        // (x, y),
-        // dir,
-        // tiles.get(&(x, y)),
-        // &states
-        // );
-        Some(
-            states
-                .into_iter()
-                .filter(|(p, d)| is_within_bounds(bounds, *p) && seen.insert((*p, *d)))
-                .collect_vec(),
-        )
-    });
-    // let m: HashMap<_, _> = seen.into_iter().collect();
-    // for y in 0..bounds.1 {
-    //     for x in 0..bounds.0 {
-    //         if m.contains_key(&(x as i32, y as i32)) {
-    //             print!("#");
-    //         } else {
-    //             print!(".");
-    //         }
-    //     }
-    //     print!("\n");
-    // }
-    seen.into_iter().unique_by(|(p, _)| *p).count()
+) -> Vec<Vec<((i32, i32), Direction)>> {
+    let mut seen: HashSet<((i32, i32), Direction)> = HashSet::from([start]);
+    let mut fronts = vec![];
+    let mut front = vec![start];
+    while !front.is_empty() {
+        let next = front
+            .iter()
+            .flat_map(|&(p, dir)| next_states(p, tiles.get(&p), dir))
+            .filter(|(p, d)| is_within_bounds(bounds, *p) && seen.insert((*p, *d)))
+            .collect_vec();
+        fronts.push(std::mem::replace(&mut front, next));
+    }
+    fronts
 }
 
-pub fn process_part1(input: &str) -> String {
-    let tiles = parse_input(input);
-    let bounds = aoc_utils::grid_bounds(input);
-    let start = ((0, 0), Right);
-    start_to_energized(&tiles, bounds, start).to_string()
+fn start_to_energized(
+    tiles: &HashMap<(i32, i32), Tile>,
+    bounds: (usize, usize),
+    start: ((i32, i32), Direction),
+) -> usize {
+    beam_fronts(tiles, bounds, start)
+        .into_iter()
+        .flatten()
+        .unique_by(|(p, _)| *p)
+        .count()
 }
 
-pub fn process_part2(input: &str) -> String {
-    let tiles = parse_input(input);
-    let (max_x, max_y) = aoc_utils::grid_bounds(input);
+/// The most tiles a beam can energize coming in from any edge.
+fn most_energized((tiles, (max_x, max_y)): &Contraption) -> Result<usize, Error> {
+    let (max_x, max_y) = (*max_x, *max_y);
     let mut potential_starts = vec![];
     for x in 0..max_x {
         potential_starts.push(((x as i32, 0), Down));
@@ -146,54 +125,43 @@ pub fn process_part2(input: &str) -> String {
         potential_starts.push(((0, y as i32), Right));
         potential_starts.push(((max_x as i32 - 1, y as i32), Left));
     }
+
     potential_starts
         .into_par_iter()
-        .map(|start| start_to_energized(&tiles, (max_x, max_y), start))
+        .map(|start| start_to_energized(tiles, (max_x, max_y), start))
         .max()
-        .unwrap()
-        .to_string()
+        .ok_or_else(|| Error::invalid("the contraption is empty"))
+}
+
+aoc_utils::solution! {
+    Day16: 2023 day 16;
+    type Parsed<'a> = Contraption;
+    parse: |input| parse_input(input);
+    part1: |(tiles, bounds)| Ok(start_to_energized(tiles, *bounds, ((0, 0), Right)));
+    part2: |contraption| most_energized(contraption);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
     use rstest::rstest;
 
+    fn example(n: u8) -> &'static str {
+        inputs::example(2023, 16, n).unwrap()
+    }
+
     #[rstest]
-    #[case(
-        r".|...\....
-|.-.\.....
-.....|-...
-........|.
-..........
-.........\
-..../.\\..
-.-.-/..|..
-.|....-|.\
-..//.|....",
-        "46"
-    )]
+    #[case(1, "46")]
     #[trace]
-    fn test_part1(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(process_part1(input).as_str(), expected);
+    fn test_part1(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(process_part1(example(n)).as_str(), expected);
     }
 
     #[rstest]
-    #[case(
-        r".|...\....
-|.-.\.....
-.....|-...
-........|.
-..........
-.........\
-..../.\\..
-.-.-/..|..
-.|....-|.\
-..//.|....",
-        "51"
-    )]
+    #[case(1, "51")]
     #[trace]
-    fn test_part2(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(process_part2(input).as_str(), expected);
+    fn test_part2(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(process_part2(example(n)).as_str(), expected);
     }
 }
@@ -1,8 +1,9 @@
+use aoc_utils::inputs;
 use criterion::{criterion_group, criterion_main, Criterion};
 use day_17::*;
 
 fn criterion_benchmark_part1(c: &mut Criterion) {
-    let input = include_str!("../input.txt");
+    let input = inputs::input(2023, 17).unwrap_or_else(|err| panic!("{err}"));
 
     let mut group = c.benchmark_group("day_17::part1");
     group.bench_with_input("part1", input, |b, input| b.iter(|| process_part1(input)));
@@ -11,7 +12,7 @@ fn criterion_benchmark_part1(c: &mut Criterion) {
 }
 
 fn criterion_benchmark_part2(c: &mut Criterion) {
-    let input = include_str!("../input.txt");
+    let input = inputs::input(2023, 17).unwrap_or_else(|err| panic!("{err}"));
 
     let mut group = c.benchmark_group("day_17::part2");
     group.bench_with_input("part2", input, |b, input| b.iter(|| process_part2(input)));
@@ -1,6 +1,7 @@
+use aoc_utils::inputs;
 use day_17::process_part1;
 
 fn main() {
-    let file = include_str!("../../input.txt");
-    println!("{}", process_part1(file));
+    let input = inputs::input(2023, 17).unwrap_or_else(|err| panic!("{err}"));
+    println!("{}", process_part1(input));
 }
@@ -1,75 +1,27 @@
-use std::collections::{BinaryHeap, HashMap};
+use aoc_utils::{
+    parse::{digit_grid, parse_all},
+    Direction, Error, Grid,
+};
 use Direction::*;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-struct State((i32, i32), Direction, u32);
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(other.2.cmp(&self.2))
-    }
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.2.cmp(&self.2)
-    }
-}
-
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    pub fn turn_cw(&self) -> Direction {
-        match &self {
-            Up => Right,
-            Down => Left,
-            Left => Up,
-            Right => Down,
-        }
-    }
-
-    pub fn turn_ccw(&self) -> Direction {
-        match &self {
-            Up => Left,
-            Down => Right,
-            Left => Down,
-            Right => Up,
-        }
-    }
-
-    pub fn delta(&self) -> (i32, i32) {
-        match &self {
-            Up => (0, -1),
-            Down => (0, 1),
-            Left => (-1, 0),
-            Right => (1, 0),
-        }
-    }
-}
+type Crucible = ((i32, i32), Direction);
 
 fn next_steps(
-    grid: &HashMap<(i32, i32), u32>,
-    heat_so_far: u32,
+    grid: &Grid<u32>,
     (x, y): (i32, i32),
     new_dir: Direction,
     is_ultra_crucible: bool,
-) -> Vec<((i32, i32), Direction, u32)> {
-    let (min_steps, max_steps) = if is_ultra_crucible { (4, 10) } else { (1, 4) };
+) -> Vec<(Crucible, u32)> {
+    let (min_steps, max_steps) = if is_ultra_crucible { (4, 10) } else { (1, 3) };
     let mut next_states = vec![];
     let (dx, dy) = new_dir.delta();
-    let mut nh = heat_so_far;
+    let mut heat = 0;
     for total_steps in 1..=max_steps {
         let np = (x + dx * total_steps, y + dy * total_steps);
-        if let Some(h) = grid.get(&np) {
-            nh += h;
+        if let Some(h) = grid.get(np) {
+            heat += h;
             if total_steps >= min_steps {
-                next_states.push((np, new_dir, nh));
+                next_states.push(((np, new_dir), heat));
             }
         } else {
             break;
@@ -78,121 +30,55 @@ fn next_steps(
     next_states
 }
 
-fn parse_grid(input: &str) -> HashMap<(i32, i32), u32> {
-    let mut map = HashMap::new();
-    for (y, line) in input.lines().enumerate() {
-        for (x, d) in line.char_indices() {
-            map.insert((x as i32, y as i32), d.to_digit(10).unwrap());
-        }
-    }
-    map
+fn parse_grid(input: &str) -> Result<Grid<u32>, Error> {
+    parse_all(digit_grid, input)
 }
 
-fn djikstras(
-    grid: &HashMap<(i32, i32), u32>,
-    (max_x, max_y): (usize, usize),
-    is_ultra_crucible: bool,
-) -> u32 {
-    let mut min_heat = HashMap::new();
-    min_heat.insert(((0, 0), Up), 0);
-
-    let mut heap = BinaryHeap::new();
-    heap.push(State((0, 0), Up, 0));
-    heap.push(State((0, 0), Left, 0));
-
-    while let Some(State(p, prev_dir, heat_loss)) = heap.pop() {
-        if p == (max_x as i32 - 1, max_y as i32 - 1) {
-            return heat_loss;
-        }
-
-        let best_so_far = min_heat.entry((p, prev_dir)).or_insert(u32::MAX);
-        if heat_loss > *best_so_far {
-            continue;
-        }
-
-        let next_states = next_steps(grid, heat_loss, p, prev_dir.turn_cw(), is_ultra_crucible)
-            .into_iter()
-            .chain(next_steps(
-                grid,
-                heat_loss,
-                p,
-                prev_dir.turn_ccw(),
-                is_ultra_crucible,
-            ));
-
-        for (p, d, h) in next_states {
-            let best_so_far = min_heat.entry((p, d)).or_insert(u32::MAX);
-            if h < *best_so_far {
-                *best_so_far = h;
-                heap.push(State(p, d, h));
-            }
-        }
-    }
-    0
+fn least_heat_loss(grid: &Grid<u32>, is_ultra_crucible: bool) -> Result<u32, Error> {
+    let (max_x, max_y) = grid.bounds();
+    let target = (max_x as i32 - 1, max_y as i32 - 1);
+    aoc_utils::dijkstra(
+        [((0, 0), Up), ((0, 0), Left)].into_iter(),
+        |&(p, prev_dir)| {
+            let mut next = next_steps(grid, p, prev_dir.turn_cw(), is_ultra_crucible);
+            next.extend(next_steps(grid, p, prev_dir.turn_ccw(), is_ultra_crucible));
+            next
+        },
+        |(p, _)| *p == target,
+    )
+    .ok_or_else(|| Error::invalid("the factory can't be reached"))
 }
 
-pub fn process_part1(input: &str) -> String {
-    djikstras(&parse_grid(input), aoc_utils::grid_bounds(input), false).to_string()
-}
-
-pub fn process_part2(input: &str) -> String {
-    djikstras(&parse_grid(input), aoc_utils::grid_bounds(input), true).to_string()
+aoc_utils::solution! {
+    Day17: 2023 day 17;
+    type Parsed<'a> = Grid<u32>;
+    parse: |input| parse_grid(input);
+    part1: |grid| least_heat_loss(grid, false);
+    part2: |grid| least_heat_loss(grid, true);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
     use rstest::rstest;
 
+    fn example(n: u8) -> &'static str {
+        inputs::example(2023, 17, n).unwrap()
+    }
+
     #[rstest]
-    #[case(
-        "2413432311323
-3215453535623
-3255245654254
-3446585845452
-4546657867536
-1438598798454
-4457876987766
-3637877979653
-4654967986887
-4564679986453
-1224686865563
-2546548887735
-4322674655533",
-        "102"
-    )]
+    #[case(1, "102")]
     #[trace]
-    fn test_part1(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(process_part1(input).as_str(), expected);
+    fn test_part1(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(process_part1(example(n)).as_str(), expected);
     }
 
     #[rstest]
-    #[case(
-        "2413432311323
-3215453535623
-3255245654254
-3446585845452
-4546657867536
-1438598798454
-4457876987766
-3637877979653
-4654967986887
-4564679986453
-1224686865563
-2546548887735
-4322674655533",
-        "94"
-    )]
-    #[case(
-        "111111111111
-999999999991
-999999999991
-999999999991
-999999999991",
-        "71"
-    )]
+    #[case(1, "94")]
+    #[case(2, "71")]
     #[trace]
-    fn test_part2(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(process_part2(input).as_str(), expected);
+    fn test_part2(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(process_part2(example(n)).as_str(), expected);
     }
 }
@@ -1,7 +1,7 @@
+use aoc_utils::inputs;
 use day_18::process_part1;
-use std::fs;
 
 fn main() {
-    let file = fs::read_to_string("./input.txt").unwrap();
-    println!("{}", process_part1(file.as_str()));
+    let input = inputs::input(2023, 18).unwrap_or_else(|err| panic!("{err}"));
+    println!("{}", process_part1(input));
 }
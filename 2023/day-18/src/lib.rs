@@ -1,142 +1,181 @@
-use itertools::Itertools;
-use Direction::*;
+use aoc_utils::{
+    geometry,
+    parse::{integer, lines, parse_all},
+    Direction,
+};
+use nom::{
+    bytes::complete::{tag, take_while_m_n},
+    character::complete::{one_of, space1},
+    combinator::{map_opt, map_res},
+    sequence::{delimited, pair, separated_pair},
+    IResult, Parser,
+};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    pub fn to_delta(self) -> (i64, i64) {
-        match self {
-            Up => (0, -1),
-            Down => (0, 1),
-            Left => (-1, 0),
-            Right => (1, 0),
-        }
-    }
-}
-
-fn parse_input(input: &str) -> Vec<(Direction, i64)> {
-    input
-        .lines()
-        .map(|l| {
-            let (dir, digits, _) = l.split(' ').collect_tuple().unwrap();
-            (
-                match dir {
-                    "U" => Up,
-                    "D" => Down,
-                    "L" => Left,
-                    "R" => Right,
-                    _ => unreachable!(),
-                },
-                digits.parse::<i64>().unwrap(),
-            )
-        })
-        .collect_vec()
-}
-
-fn parse_hexadecimals(input: &str) -> Vec<(Direction, i64)> {
-    input
-        .lines()
-        .map(|l| {
-            let hex_digits = &l[l.len() - 7..l.len()];
-            let steps = i64::from_str_radix(&hex_digits[0..hex_digits.len() - 2], 16).unwrap();
-            let dir = [Right, Down, Left, Up][usize::from_str_radix(
-                &hex_digits[hex_digits.len() - 2..hex_digits.len() - 1],
-                16,
-            )
-            .unwrap()];
-            (dir, steps)
-        })
-        .collect_vec()
-}
-
-fn instructions_to_area(dirs_and_steps: &[(Direction, i64)]) -> i64 {
-    let mut pts = Vec::new();
-    pts.push((0, 0));
-
-    let mut num_boundary_points = 0;
-    let (mut cx, mut cy) = (0, 0);
-    for (dir, steps) in dirs_and_steps.iter() {
-        let (dx, dy) = dir.to_delta();
-        num_boundary_points += steps;
-        (cx, cy) = (cx + dx * steps, cy + dy * steps);
-        pts.push((cx, cy));
-    }
-
-    // Shoelace formula, https://en.wikipedia.org/wiki/Shoelace_formula#Other_formulas_2
-    let area = (0..pts.len() - 1)
-        .map(|i| (pts[i].1 + pts[i + 1].1) * (pts[i].0 - pts[i + 1].0))
-        .sum::<i64>()
-        .abs()
-        / 2;
-
-    // Pick's theorem, https://en.wikipedia.org/wiki/Pick%27s_theorem
-    let num_interior_points = area - num_boundary_points / 2 + 1;
+/// The instruction of a line, followed by the one hidden in its color code.
+pub type Instructions = ((Direction, i64), (Direction, i64));
 
-    num_interior_points + num_boundary_points
+fn parse_line(input: &str) -> IResult<&str, Instructions> {
+    let direction = |directions| map_opt(one_of(directions), Direction::from_char);
+    let steps = map_res(
+        take_while_m_n(5, 5, |c: char| c.is_ascii_hexdigit()),
+        |hex| i64::from_str_radix(hex, 16),
+    );
+    separated_pair(
+        separated_pair(direction("UDLR"), space1, integer),
+        space1,
+        delimited(
+            tag("(#"),
+            pair(steps, direction("0123")).map(|(s, d)| (d, s)),
+            tag(")"),
+        ),
+    )(input)
 }
 
-pub fn process_part1(input: &str) -> String {
-    instructions_to_area(&parse_input(input)).to_string()
+fn instructions_to_area(dirs_and_steps: impl Iterator<Item = (Direction, i64)>) -> i64 {
+    let vertices = geometry::vertices_from_path((0, 0), dirs_and_steps);
+    geometry::lattice_points(&vertices)
 }
 
-pub fn process_part2(input: &str) -> String {
-    instructions_to_area(&parse_hexadecimals(input)).to_string()
+aoc_utils::solution! {
+    Day18: 2023 day 18;
+    type Parsed<'a> = Vec<Instructions>;
+    parse: |input| parse_all(lines(parse_line), input);
+    part1: |plan| Ok(instructions_to_area(plan.iter().map(|(plain, _)| *plain)));
+    part2: |plan| Ok(instructions_to_area(plan.iter().map(|(_, hex)| *hex)));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
+    use proptest::prelude::*;
     use rstest::rstest;
+    use std::collections::HashSet;
+
+    fn example(n: u8) -> &'static str {
+        inputs::example(2023, 18, n).unwrap()
+    }
 
     #[rstest]
-    #[case(
-        "R 6 (#70c710)
-D 5 (#0dc571)
-L 2 (#5713f0)
-D 2 (#d2c081)
-R 2 (#59c680)
-D 2 (#411b91)
-L 5 (#8ceee2)
-U 2 (#caa173)
-L 1 (#1b58a2)
-U 2 (#caa171)
-R 2 (#7807d2)
-U 3 (#a77fa3)
-L 2 (#015232)
-U 2 (#7a21e3)",
-        "62"
-    )]
+    #[case(1, "62")]
     #[trace]
-    fn test_part1(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(process_part1(input).as_str(), expected);
+    fn test_part1(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(process_part1(example(n)).as_str(), expected);
     }
 
     #[rstest]
-    #[case(
-        "R 6 (#70c710)
-D 5 (#0dc571)
-L 2 (#5713f0)
-D 2 (#d2c081)
-R 2 (#59c680)
-D 2 (#411b91)
-L 5 (#8ceee2)
-U 2 (#caa173)
-L 1 (#1b58a2)
-U 2 (#caa171)
-R 2 (#7807d2)
-U 3 (#a77fa3)
-L 2 (#015232)
-U 2 (#7a21e3)",
-        "952408144115"
-    )]
+    #[case(1, "952408144115")]
     #[trace]
-    fn test_part2(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(process_part2(input).as_str(), expected);
+    fn test_part2(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(process_part2(example(n)).as_str(), expected);
+    }
+
+    /// Dig plans that trace a simple loop: a row of columns, each spanning
+    /// `bottom..=top` around the x axis, walked along their tops and back along
+    /// their bottoms. The loop is then turned and maybe walked the other way.
+    fn dig_plan() -> impl Strategy<Value = Vec<(Direction, i64)>> {
+        let columns = prop::collection::vec((1..4i64, -4..=0i64, 1..=4i64), 1..8);
+        (columns, 0..4usize, any::<bool>()).prop_map(|(columns, turns, reversed)| {
+            let mut x = 0;
+            let mut vertices = vec![(0, columns[0].2)];
+            for (i, (width, _, top)) in columns.iter().enumerate() {
+                x += width;
+                vertices.push((x, *top));
+                if let Some((_, _, next_top)) = columns.get(i + 1) {
+                    vertices.push((x, *next_top));
+                }
+            }
+            for (i, (width, bottom, _)) in columns.iter().enumerate().rev() {
+                vertices.push((x, *bottom));
+                x -= width;
+                vertices.push((x, *bottom));
+                if i > 0 {
+                    vertices.push((x, columns[i - 1].1));
+                }
+            }
+            vertices.push(vertices[0]);
+
+            let mut plan: Vec<(Direction, i64)> = vertices
+                .windows(2)
+                .filter(|w| w[0] != w[1])
+                .map(|w| {
+                    let (dx, dy) = (w[1].0 - w[0].0, w[1].1 - w[0].1);
+                    let direction = match (dx.signum(), dy.signum()) {
+                        (1, _) => Direction::Right,
+                        (-1, _) => Direction::Left,
+                        (_, 1) => Direction::Up,
+                        _ => Direction::Down,
+                    };
+                    (direction, dx.abs() + dy.abs())
+                })
+                .collect();
+            for (direction, _) in plan.iter_mut() {
+                for _ in 0..turns {
+                    *direction = direction.turn_cw();
+                }
+            }
+            if reversed {
+                plan.reverse();
+                for (direction, _) in plan.iter_mut() {
+                    *direction = direction.reverse();
+                }
+            }
+            plan
+        })
+    }
+
+    /// Writes `plan` out twice, once plainly and once in the color codes.
+    fn write_plan(plan: &[(Direction, i64)]) -> String {
+        plan.iter()
+            .map(|(direction, steps)| {
+                let letter = match direction {
+                    Direction::Up => 'U',
+                    Direction::Right => 'R',
+                    Direction::Down => 'D',
+                    Direction::Left => 'L',
+                };
+                format!("{letter} {steps} (#{steps:05x}{})", direction.index())
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Digs the trench cube by cube and counts everything that can't be reached
+    /// from outside of it.
+    fn flood_fill_area(plan: &[(Direction, i64)]) -> i64 {
+        let mut position = (0, 0);
+        let mut trench = HashSet::from([position]);
+        for (direction, steps) in plan {
+            for _ in 0..*steps {
+                position = direction.step(position);
+                trench.insert(position);
+            }
+        }
+        let min_x = trench.iter().map(|p| p.0).min().unwrap() - 1;
+        let max_x = trench.iter().map(|p| p.0).max().unwrap() + 1;
+        let min_y = trench.iter().map(|p| p.1).min().unwrap() - 1;
+        let max_y = trench.iter().map(|p| p.1).max().unwrap() + 1;
+
+        let mut outside = HashSet::from([(min_x, min_y)]);
+        let mut queue = vec![(min_x, min_y)];
+        while let Some(p) = queue.pop() {
+            for next in Direction::ALL.map(|d| d.step(p)) {
+                let in_box = (min_x..=max_x).contains(&next.0) && (min_y..=max_y).contains(&next.1);
+                if in_box && !trench.contains(&next) && outside.insert(next) {
+                    queue.push(next);
+                }
+            }
+        }
+        ((max_x - min_x + 1) * (max_y - min_y + 1)) as i64 - outside.len() as i64
+    }
+
+    proptest! {
+        #[test]
+        fn area_matches_flood_fill(plan in dig_plan()) {
+            let input = write_plan(&plan);
+            let expected = flood_fill_area(&plan).to_string();
+            prop_assert_eq!(process_part1(&input), expected.clone());
+            prop_assert_eq!(process_part2(&input), expected);
+        }
     }
 }
@@ -1,6 +1,7 @@
+use aoc_utils::inputs;
 use day_19::process_part2;
 
 fn main() {
-    let input = include_str!("../../input.txt");
+    let input = inputs::input(2023, 19).unwrap_or_else(|err| panic!("{err}"));
     println!("{}", process_part2(input));
 }
@@ -1,9 +1,14 @@
-use std::{collections::HashMap, ops::RangeInclusive};
+use std::collections::HashMap;
+
+use aoc_utils::{
+    parse::{blank_line, lines, parse_all},
+    Error, HyperRect,
+};
 
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{self, alpha1, newline},
+    character::complete::{self, alpha1},
     multi::separated_list1,
     sequence::separated_pair,
     IResult, Parser,
@@ -18,6 +23,8 @@ enum Op {
 #[derive(Clone, Debug)]
 struct Rule(usize, Op, u32, String);
 
+type PartRange = HyperRect<u32, 4>;
+
 impl Rule {
     pub fn matches(&self, p: [u32; 4]) -> bool {
         match self.1 {
@@ -26,25 +33,20 @@ impl Rule {
         }
     }
 
-    pub fn constrain(
-        &self,
-        mut p: [RangeInclusive<u32>; 4],
-        want_match: bool,
-    ) -> [RangeInclusive<u32>; 4] {
-        match (self.1, want_match) {
-            (Op::Less, true) => p[self.0] = *p[self.0].start()..=(self.2 - 1).min(*p[self.0].end()),
-            (Op::Greater, true) => {
-                p[self.0] = (self.2 + 1).max(*p[self.0].start())..=*p[self.0].end()
+    /// Splits `part` into the ratings that match this rule and the ones that don't.
+    pub fn split(&self, part: &PartRange) -> (Option<PartRange>, Option<PartRange>) {
+        match self.1 {
+            Op::Less => part.split_at(self.0, self.2),
+            Op::Greater => {
+                let (below, above) = part.split_at(self.0, self.2 + 1);
+                (above, below)
             }
-            (Op::Less, false) => p[self.0] = (self.2).max(*p[self.0].start())..=*p[self.0].end(),
-            (Op::Greater, false) => p[self.0] = *p[self.0].start()..=self.2.min(*p[self.0].end()),
-        };
-        p
+        }
     }
 }
 
 #[derive(Clone, Debug)]
-struct Workflow(String, Vec<Rule>, String);
+pub struct Workflow(String, Vec<Rule>, String);
 
 impl Workflow {
     pub fn run(&self, part: [u32; 4]) -> String {
@@ -55,20 +57,30 @@ impl Workflow {
         }
     }
 
-    pub fn potential_outcomes(
-        &self,
-        part: [RangeInclusive<u32>; 4],
-    ) -> Vec<([RangeInclusive<u32>; 4], String)> {
+    /// Where this workflow can send a part.
+    pub fn targets(&self) -> impl Iterator<Item = &str> {
+        self.1
+            .iter()
+            .map(|rule| &rule.3)
+            .chain([&self.2])
+            .map(String::as_str)
+    }
+
+    pub fn potential_outcomes(&self, part: PartRange) -> Vec<(PartRange, String)> {
         let mut outcomes = vec![];
-        let fallback = self.1.iter().fold(part, |part, rule| {
-            let matching = rule.constrain(part.clone(), true);
-            if matching.iter().all(|r| !r.is_empty()) {
-                outcomes.push((matching, rule.3.clone()));
+        let mut remaining = Some(part);
+        for rule in self.1.iter() {
+            let Some(part) = remaining else {
+                break;
             };
+            let (matching, not_matching) = rule.split(&part);
+            if let Some(matching) = matching {
+                outcomes.push((matching, rule.3.clone()));
+            }
             // not matching, so it goes to the next rule
-            rule.constrain(part, false)
-        });
-        if fallback.iter().all(|r| !r.is_empty()) {
+            remaining = not_matching;
+        }
+        if let Some(fallback) = remaining {
             outcomes.push((fallback, self.2.clone()));
         }
         outcomes
@@ -116,12 +128,11 @@ fn parse_workflow(input: &str) -> IResult<&str, Workflow> {
     Ok((input, Workflow(label.to_owned(), rules, dest.to_owned())))
 }
 
-fn parse_input(input: &str) -> IResult<&str, (HashMap<String, Workflow>, Vec<[u32; 4]>)> {
-    let (input, (workflows, parts)) = separated_pair(
-        separated_list1(newline, parse_workflow),
-        tag("\n\n"),
-        separated_list1(newline, parse_part),
-    )(input)?;
+type Workflows = HashMap<String, Workflow>;
+
+fn parse_input(input: &str) -> IResult<&str, (Workflows, Vec<[u32; 4]>)> {
+    let (input, (workflows, parts)) =
+        separated_pair(lines(parse_workflow), blank_line, lines(parse_part))(input)?;
     Ok((
         input,
         (
@@ -131,28 +142,66 @@ fn parse_input(input: &str) -> IResult<&str, (HashMap<String, Workflow>, Vec<[u3
     ))
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (input, (workflows, parts)) = parse_input(input).unwrap();
-    debug_assert_eq!(input, "");
+/// The workflows and parts, once every workflow a part can be sent to is known
+/// to exist and no part can be sent round in circles.
+fn parse_workflows(input: &str) -> Result<(Workflows, Vec<[u32; 4]>), Error> {
+    let (workflows, parts) = parse_all(parse_input, input)?;
+    if !workflows.contains_key("in") {
+        return Err(Error::invalid("there is no workflow in"));
+    }
+    if let Some(missing) = workflows
+        .values()
+        .flat_map(Workflow::targets)
+        .find(|target| !matches!(*target, "A" | "R") && !workflows.contains_key(*target))
+    {
+        return Err(Error::invalid(format!("workflow {missing} isn't defined")));
+    }
+    if let Some(looping) = find_loop(&workflows, "in", &mut HashMap::new()) {
+        return Err(Error::invalid(format!(
+            "workflow {looping} can send parts back to itself"
+        )));
+    }
+    Ok((workflows, parts))
+}
+
+/// A workflow that parts reaching `label` can be sent back to, found depth
+/// first. `visiting` is true for the workflows on the current path.
+fn find_loop<'a>(
+    workflows: &'a Workflows,
+    label: &'a str,
+    visiting: &mut HashMap<&'a str, bool>,
+) -> Option<&'a str> {
+    if let Some(&on_path) = visiting.get(label) {
+        return on_path.then_some(label);
+    }
+    visiting.insert(label, true);
+    let looping = workflows[label]
+        .targets()
+        .filter(|target| workflows.contains_key(*target))
+        .find_map(|target| find_loop(workflows, target, visiting));
+    visiting.insert(label, false);
+    looping
+}
 
+fn sum_accepted_ratings(workflows: &Workflows, parts: &[[u32; 4]]) -> u32 {
     parts
-        .into_iter()
+        .iter()
+        .copied()
         .filter_map(|part| {
             let mut curr_wf_label: String = "in".into();
             while curr_wf_label != "R" && curr_wf_label != "A" {
-                curr_wf_label = workflows.get(&curr_wf_label).unwrap().run(part);
+                curr_wf_label = workflows[&curr_wf_label].run(part);
             }
             (curr_wf_label == "A").then(|| part.iter().sum::<u32>())
         })
         .sum::<u32>()
-        .to_string()
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (input, (workflows, _)) = parse_input(input).unwrap();
-    debug_assert_eq!(input, "");
-
-    let initial = vec![([1..=4000, 1..=4000, 1..=4000, 1..=4000], "in".to_string())];
+fn count_accepted_combinations(workflows: &Workflows) -> u64 {
+    let initial = vec![(
+        HyperRect::new([1..4001, 1..4001, 1..4001, 1..4001]),
+        "in".to_string(),
+    )];
     let mut accepted = vec![];
 
     aoc_utils::complete_bfs(initial.into_iter(), |(part, workflow)| {
@@ -160,66 +209,59 @@ pub fn process_part2(input: &str) -> String {
             accepted.push(part);
             return None;
         };
-        (workflow != "R").then(|| workflows.get(&workflow).unwrap().potential_outcomes(part))
+        (workflow != "R").then(|| workflows[&workflow].potential_outcomes(part))
     });
 
     accepted
         .into_iter()
-        .map(|accepted| accepted.into_iter().map(|r| r.count()).product::<usize>())
-        .sum::<usize>()
-        .to_string()
+        .map(|accepted| accepted.volume::<u64>())
+        .sum::<u64>()
+}
+
+aoc_utils::solution! {
+    Day19: 2023 day 19;
+    type Parsed<'a> = (Workflows, Vec<[u32; 4]>);
+    parse: |input| parse_workflows(input);
+    part1: |(workflows, parts)| Ok(sum_accepted_ratings(workflows, parts));
+    part2: |(workflows, _)| Ok(count_accepted_combinations(workflows));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
     use rstest::rstest;
 
+    fn example(n: u8) -> &'static str {
+        inputs::example(2023, 19, n).unwrap()
+    }
+
     #[rstest]
-    #[case(
-        "px{a<2006:qkq,m>2090:A,rfg}
-pv{a>1716:R,A}
-lnx{m>1548:A,A}
-rfg{s<537:gd,x>2440:R,A}
-qs{s>3448:A,lnx}
-qkq{x<1416:A,crn}
-crn{x>2662:A,R}
-in{s<1351:px,qqz}
-qqz{s>2770:qs,m<1801:hdj,R}
-gd{a>3333:R,R}
-hdj{m>838:A,pv}
-
-{x=787,m=2655,a=1222,s=2876}
-{x=1679,m=44,a=2067,s=496}
-{x=2036,m=264,a=79,s=2244}
-{x=2461,m=1339,a=466,s=291}
-{x=2127,m=1623,a=2188,s=1013}",
-        "19114"
-    )]
+    #[case(1, "19114")]
+    #[trace]
+    fn test_part1(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(&process_part1(example(n)), expected);
+    }
+
+    #[rstest]
+    #[case(1, "167409079868000")]
     #[trace]
-    fn test_part1(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(&process_part1(input), expected);
+    fn test_part2(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(&process_part2(example(n)), expected);
     }
 
     #[rstest]
+    #[case("ab{x>1:A,R}", "there is no workflow in")]
+    #[case("in{x>1:ab,R}", "workflow ab isn't defined")]
+    #[case("in{x>1:A,ab}", "workflow ab isn't defined")]
     #[case(
-        "px{a<2006:qkq,m>2090:A,rfg}
-pv{a>1716:R,A}
-lnx{m>1548:A,A}
-rfg{s<537:gd,x>2440:R,A}
-qs{s>3448:A,lnx}
-qkq{x<1416:A,crn}
-crn{x>2662:A,R}
-in{s<1351:px,qqz}
-qqz{s>2770:qs,m<1801:hdj,R}
-gd{a>3333:R,R}
-hdj{m>838:A,pv}
-
-{x=2127,m=1623,a=2188,s=1013}",
-        "167409079868000"
+        "in{x>1:ab,R}\nab{m<5:in,A}",
+        "workflow in can send parts back to itself"
     )]
     #[trace]
-    fn test_part2(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(&process_part2(input), expected);
+    fn rejects_malformed_workflows(#[case] workflows: &str, #[case] message: &str) {
+        let input = format!("{workflows}\n\n{{x=1,m=2,a=3,s=4}}");
+        let error = try_process_part1(&input).unwrap_err();
+        assert!(error.to_string().contains(message), "{error}");
     }
 }
@@ -1,10 +1,14 @@
 use std::collections::{HashMap, HashSet};
 
+use aoc_utils::{
+    parse::{lines, parse_all},
+    Error,
+};
 use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{self, alpha1, newline},
+    character::complete::{self, alpha1, line_ending},
     multi::separated_list1,
     sequence::preceded,
     IResult, Parser,
@@ -14,7 +18,7 @@ const HIGH: u8 = 1u8;
 const LOW: u8 = 0u8;
 
 #[derive(Debug, Clone)]
-enum Module<'a> {
+pub enum Module<'a> {
     FlipFlop(u8),
     Conjunction(HashMap<&'a str, u8>),
     Noop,
@@ -25,7 +29,12 @@ fn parse_broadcaster(input: &str) -> IResult<&str, Vec<&str>> {
     preceded(tag("broadcaster -> "), separated_list1(tag(", "), alpha1))(input)
 }
 
-fn parse_module(input: &str) -> IResult<&str, (&str, Module, Vec<&str>)> {
+/// Each module by name, with its state and the modules it sends pulses to.
+pub type Modules<'a> = HashMap<&'a str, (Module<'a>, Vec<&'a str>)>;
+
+type ModuleDefinition<'a> = (&'a str, Module<'a>, Vec<&'a str>);
+
+fn parse_module(input: &str) -> IResult<&str, ModuleDefinition<'_>> {
     let (input, module_type) = alt((
         complete::char('%').map(|_| FlipFlop(LOW)),
         complete::char('&').map(|_| Conjunction(HashMap::new())),
@@ -35,16 +44,17 @@ fn parse_module(input: &str) -> IResult<&str, (&str, Module, Vec<&str>)> {
     Ok((input, (name, module_type, outputs)))
 }
 
-fn parse_input(input: &str) -> IResult<&str, (Vec<&str>, Vec<(&str, Module, Vec<&str>)>)> {
+fn parse_input(input: &str) -> IResult<&str, (Vec<&str>, Vec<ModuleDefinition<'_>>)> {
     let (input, broadcast_outputs) = parse_broadcaster(input)?;
-    let (input, _) = newline(input)?;
-    let (input, modules) = separated_list1(newline, parse_module)(input)?;
+    let (input, _) = line_ending(input)?;
+    let (input, modules) = lines(parse_module)(input)?;
     Ok((input, (broadcast_outputs, modules)))
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (input, (broadcast_outputs, modules)) = parse_input(input).unwrap();
-    debug_assert_eq!(input, "");
+/// Every module with the modules it sends pulses to. Conjunction modules start
+/// out remembering a low pulse from each of their inputs.
+fn parse_modules(input: &str) -> Result<Modules<'_>, Error> {
+    let (broadcast_outputs, modules) = parse_all(parse_input, input)?;
 
     let conjunction_module_names: HashSet<&str> = modules
         .iter()
@@ -80,6 +90,14 @@ pub fn process_part1(input: &str) -> String {
         }
     }
 
+    Ok(module_map)
+}
+
+/// The number of low pulses times the number of high pulses sent in 1000
+/// button presses.
+fn pulse_product(modules: &Modules<'_>) -> u64 {
+    let mut module_map = modules.clone();
+
     let mut low_signal_count: u64 = 0;
     let mut high_signal_count: u64 = 0;
 
@@ -96,7 +114,7 @@ pub fn process_part1(input: &str) -> String {
                     high_signal_count += 1;
                 }
 
-                return match module_map.get_mut(&destination) {
+                match module_map.get_mut(&destination) {
                     Some((FlipFlop(state), outputs)) if signal == LOW => {
                         *state = if *state == LOW { HIGH } else { LOW };
                         Some(
@@ -118,50 +136,16 @@ pub fn process_part1(input: &str) -> String {
                             .collect_vec(),
                     ),
                     _ => None,
-                };
+                }
             },
         );
     }
-    (low_signal_count * high_signal_count).to_string()
+    low_signal_count * high_signal_count
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (input, (broadcast_outputs, modules)) = parse_input(input).unwrap();
-    debug_assert_eq!(input, "");
-
-    let conjunction_module_names: HashSet<&str> = modules
-        .iter()
-        .filter_map(|(n, m, _)| match *m {
-            Conjunction(_) => Some(*n),
-            _ => None,
-        })
-        .collect();
-
-    let mut conjunction_module_inputs: HashMap<&str, Vec<&str>> = conjunction_module_names
-        .iter()
-        .map(|n| (*n, vec![]))
-        .collect();
-
-    let mut module_map: HashMap<&str, (Module, Vec<&str>)> = HashMap::new();
-    module_map.insert("broadcaster", (Noop, broadcast_outputs));
-
-    for (name, module, outputs) in modules.into_iter() {
-        for n in outputs
-            .iter()
-            .filter(|n| conjunction_module_names.contains(*n))
-        {
-            conjunction_module_inputs.get_mut(n).unwrap().push(name);
-        }
-        module_map.insert(name, (module, outputs));
-    }
-
-    // turn into input -> last signal in the final map
-    for (name, inputs) in conjunction_module_inputs.into_iter() {
-        let (ref mut m, _) = module_map.get_mut(&name).unwrap();
-        if let Conjunction(mem) = m {
-            *mem = inputs.into_iter().map(|i| (i, LOW)).collect()
-        }
-    }
+/// The fewest button presses before a single low pulse reaches rx.
+fn presses_until_rx(modules: &Modules<'_>) -> Result<usize, Error> {
+    let mut module_map = modules.clone();
 
     let initial_state = ("broadcaster", LOW, "button");
 
@@ -170,7 +154,7 @@ pub fn process_part2(input: &str) -> String {
     let (feeder, (_, _)) = module_map
         .iter()
         .find(|(_, (_, o))| o.contains(&"rx"))
-        .unwrap();
+        .ok_or_else(|| Error::invalid("no module sends pulses to rx"))?;
     let feeder = *feeder;
 
     for presses in 1.. {
@@ -184,7 +168,7 @@ pub fn process_part2(input: &str) -> String {
                     cycle_lengths.entry(origin).or_insert(presses);
                 }
 
-                return match module_map.get_mut(&destination) {
+                match module_map.get_mut(&destination) {
                     Some((FlipFlop(state), outputs)) if signal == LOW => {
                         *state = if *state == LOW { HIGH } else { LOW };
                         Some(
@@ -206,7 +190,7 @@ pub fn process_part2(input: &str) -> String {
                             .collect_vec(),
                     ),
                     _ => None,
-                };
+                }
             },
         );
         if !seen.is_empty() && seen.values().all(|v| *v > 10) {
@@ -214,34 +198,34 @@ pub fn process_part2(input: &str) -> String {
         }
     }
 
-    aoc_utils::lcm(cycle_lengths.into_values().collect_vec().as_slice()).to_string()
+    aoc_utils::number_theory::lcm_all(cycle_lengths.into_values())
+        .ok_or_else(|| Error::invalid("the button presses are too many to count"))
+}
+
+aoc_utils::solution! {
+    Day20: 2023 day 20;
+    type Parsed<'a> = Modules<'a>;
+    parse: |input| parse_modules(input);
+    part1: |modules| Ok(pulse_product(modules));
+    part2: |modules| presses_until_rx(modules);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_utils::inputs;
     use rstest::rstest;
 
+    fn example(n: u8) -> &'static str {
+        inputs::example(2023, 20, n).unwrap()
+    }
+
     #[rstest]
-    #[case(
-        "broadcaster -> a, b, c
-%a -> b
-%b -> c
-%c -> inv
-&inv -> a",
-        "32000000"
-    )]
-    #[case(
-        "broadcaster -> a
-%a -> inv, con
-&inv -> b
-%b -> con
-&con -> output",
-        "11687500"
-    )]
+    #[case(1, "32000000")]
+    #[case(2, "11687500")]
     #[trace]
-    fn test_part1(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(process_part1(input).as_str(), expected);
+    fn test_part1(#[case] n: u8, #[case] expected: &str) {
+        assert_eq!(process_part1(example(n)).as_str(), expected);
     }
 
     #[rstest]
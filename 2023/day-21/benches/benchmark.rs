@@ -1,26 +1,21 @@
-use criterion::{
-    criterion_group, criterion_main, Criterion,
-};
+use aoc_utils::inputs;
+use criterion::{criterion_group, criterion_main, Criterion};
 use day_21::*;
 
 fn criterion_benchmark_part1(c: &mut Criterion) {
-    let input = include_str!("../input.txt");
+    let input = inputs::input(2023, 21).unwrap_or_else(|err| panic!("{err}"));
 
     let mut group = c.benchmark_group("day_21::part1");
-    group.bench_with_input("part1", input, |b, input| {
-        b.iter(|| process_part1(input))
-    });
+    group.bench_with_input("part1", input, |b, input| b.iter(|| process_part1(input)));
 
     group.finish();
 }
 
 fn criterion_benchmark_part2(c: &mut Criterion) {
-    let input = include_str!("../input.txt");
+    let input = inputs::input(2023, 21).unwrap_or_else(|err| panic!("{err}"));
 
     let mut group = c.benchmark_group("day_21::part2");
-    group.bench_with_input("part2", input, |b, input| {
-        b.iter(|| process_part2(input))
-    });
+    group.bench_with_input("part2", input, |b, input| b.iter(|| process_part2(input)));
 
     group.finish();
 }
@@ -30,4 +25,4 @@ criterion_group!(
     criterion_benchmark_part1,
     criterion_benchmark_part2
 );
-criterion_main!(benches);
\ No newline at end of file
+criterion_main!(benches);
@@ -1,101 +1,405 @@
-use std::collections::{HashMap, HashSet, VecDeque};
-
-fn parse_input(input: &str) -> ((i32, i32), HashSet<(i32, i32)>) {
-    let mut rocks = HashSet::new();
-    let mut starting_position = (0, 0);
-    for (y, line) in input.lines().enumerate() {
-        for (x, ch) in line.char_indices() {
-            if ch == '#' {
-                rocks.insert((x as i32, y as i32));
-            } else if ch == 'S' {
-                starting_position = (x as i32, y as i32);
-            }
+use aoc_utils::{
+    parse::{char_grid, parse_all},
+    Direction, Error, Grid, SearchResult,
+};
+use std::collections::{HashMap, HashSet};
+
+type Walk = SearchResult<(i32, i32), ((i32, i32), usize)>;
+
+const STEPS_ON_INFINITE_MAP: usize = 26501365;
+
+fn parse_input(input: &str) -> Result<((i32, i32), Grid<char>), Error> {
+    let garden = parse_all(char_grid, input)?;
+    let starting_position = garden
+        .find(|ch| *ch == 'S')
+        .ok_or_else(|| Error::invalid("the garden has no starting position"))?;
+    Ok((starting_position, garden))
+}
+
+fn walk(garden: &Grid<char>, start: (i32, i32), max_steps: usize) -> Walk {
+    aoc_utils::tracked_bfs(
+        [(start, 0)].into_iter(),
+        |(p, _)| *p,
+        |(p, steps)| {
+            (steps < max_steps).then(|| {
+                garden
+                    .neighbors4(p)
+                    .filter(|next| garden[*next] != '#')
+                    .map(|next| (next, steps + 1))
+                    .collect()
+            })
+        },
+        |_| false,
+    )
+}
+
+fn reachable_plots(garden: &Grid<char>, start: (i32, i32)) -> usize {
+    let num_steps = if garden.width() < 20 { 6 } else { 64 };
+
+    walk(garden, start, num_steps)
+        .distances()
+        .filter(|(_, s)| s % 2 == 0)
+        .count()
+}
+
+/// How many plots of the endlessly tiled garden are each number of steps away
+/// from `start`. Walking outwards one layer at a time, a plot's neighbours are
+/// in the layer before, its own layer or the next one, so only the last two
+/// layers need to be remembered.
+fn plots_by_distance(garden: &Grid<char>, start: (i32, i32)) -> impl Iterator<Item = usize> + '_ {
+    let (width, height) = (garden.width() as i32, garden.height() as i32);
+    let mut previous = HashSet::new();
+    let mut current = HashSet::from([start]);
+    std::iter::from_fn(move || {
+        let count = current.len();
+        let next = current
+            .iter()
+            .flat_map(|p| Direction::ALL.map(|d| d.step(*p)))
+            .filter(|&(x, y)| garden[(x.rem_euclid(width), y.rem_euclid(height))] != '#')
+            .filter(|p| !previous.contains(p) && !current.contains(p))
+            .collect();
+        previous = std::mem::replace(&mut current, next);
+        Some(count)
+    })
+}
+
+/// The plots reachable in exactly `steps`, which are the ones an even number
+/// of steps closer, since the elf can always step back and forth. The one
+/// exception is a start walled in on all sides, where the elf can't step at all.
+fn reachable_in(layers: &[usize], steps: usize) -> usize {
+    if steps > 0 && layers[1] == 0 {
+        return 0;
+    }
+    layers[..=steps].iter().skip(steps % 2).step_by(2).sum()
+}
+
+/// The walk from `start` over the square of `(2 * radius + 1)²` copies of the
+/// garden around the starting one, carried on past the square on the guess that
+/// each further copy in a direction is the same number of steps further than
+/// the one before it, as the outermost copies of the square are.
+struct Tiling<'a> {
+    garden: &'a Grid<char>,
+    start: (i32, i32),
+    radius: i32,
+    /// Steps to each plot of the square, walking only inside it.
+    distances: Grid<Option<usize>>,
+    /// Steps from one copy to the next going right, left, down and up.
+    offsets: [usize; 4],
+}
+
+impl<'a> Tiling<'a> {
+    fn new(garden: &'a Grid<char>, start: (i32, i32), radius: i32) -> Option<Self> {
+        let (width, height) = (garden.width() as i32, garden.height() as i32);
+        let side = 2 * radius as usize + 1;
+        let square = Grid::new(
+            garden.width() * side,
+            garden.height() * side,
+            (0..height * side as i32)
+                .flat_map(|y| (0..width * side as i32).map(move |x| (x, y)))
+                .map(|(x, y)| garden[(x % width, y % height)])
+                .collect(),
+        );
+        let mut distances = Grid::filled(square.width(), square.height(), None);
+        let origin = (radius * width, radius * height);
+        for (p, steps) in walk(
+            &square,
+            (start.0 + origin.0, start.1 + origin.1),
+            usize::MAX,
+        )
+        .distances()
+        {
+            distances[*p] = Some(steps);
         }
+        let mut tiling = Tiling {
+            garden,
+            start,
+            radius,
+            distances,
+            offsets: [0; 4],
+        };
+        for (i, (dx, dy)) in [(1, 0), (-1, 0), (0, 1), (0, -1)].into_iter().enumerate() {
+            let copy =
+                |n: i32| tiling.distance((start.0 + n * dx * width, start.1 + n * dy * height));
+            tiling.offsets[i] = copy(radius)?.checked_sub(copy(radius - 1)?)?;
+        }
+        Some(tiling)
+    }
+
+    /// The steps to `p` anywhere on the endless map, going by the square and
+    /// the offsets past it, or `None` if `p` can't be reached that way.
+    fn distance(&self, (x, y): (i32, i32)) -> Option<usize> {
+        let (width, height) = (self.garden.width() as i32, self.garden.height() as i32);
+        let (copy_x, copy_y) = (x.div_euclid(width), y.div_euclid(height));
+        let (inside_x, inside_y) = (
+            copy_x.clamp(-self.radius, self.radius),
+            copy_y.clamp(-self.radius, self.radius),
+        );
+        let beyond = |copy: i32, inside: i32, [after, before]: [usize; 2]| {
+            (copy - inside).unsigned_abs() as usize * if copy > inside { after } else { before }
+        };
+        let steps = self.distances[(
+            x - (copy_x - inside_x - self.radius) * width,
+            y - (copy_y - inside_y - self.radius) * height,
+        )]?;
+        Some(
+            steps
+                + beyond(copy_x, inside_x, [self.offsets[0], self.offsets[1]])
+                + beyond(copy_y, inside_y, [self.offsets[2], self.offsets[3]]),
+        )
+    }
+
+    /// Whether [`Tiling::distance`] is the true number of steps everywhere.
+    ///
+    /// Any count of steps that is 0 at the start and one more than its nearest
+    /// neighbour at every other plot is the true one: the nearest neighbours
+    /// lead back to the start in that many steps, and counting along the
+    /// shortest walk never gets ahead of it. Past the square every copy is
+    /// its inner neighbour plus a fixed offset, so checking the square and
+    /// one ring of copies around it checks every copy further out.
+    fn is_exact(&self) -> bool {
+        let (width, height) = (self.garden.width() as i32, self.garden.height() as i32);
+        let reach = self.radius + 1;
+        (-reach * height..(reach + 1) * height)
+            .flat_map(|y| (-reach * width..(reach + 1) * width).map(move |x| (x, y)))
+            .filter(|&(x, y)| self.garden[(x.rem_euclid(width), y.rem_euclid(height))] != '#')
+            .all(|p| {
+                let expected = if p == self.start {
+                    Some(0)
+                } else {
+                    Direction::ALL
+                        .iter()
+                        .filter_map(|d| self.distance(d.step(p)))
+                        .min()
+                        .map(|steps| steps + 1)
+                };
+                self.distance(p) == expected
+            })
+    }
+
+    /// The plots reachable in exactly `steps`: those in the square, those in
+    /// the copies straight out from its sides, which follow each plot of an
+    /// edge copy one offset at a time, and those in the four corners, which
+    /// follow each plot of a corner copy by both of its offsets.
+    fn reachable_in(&self, steps: usize) -> usize {
+        let (width, height) = (self.garden.width() as i32, self.garden.height() as i32);
+        let radius = self.radius;
+        let copy = |(x, y): (i32, i32)| {
+            (x * width..(x + 1) * width)
+                .flat_map(move |px| (y * height..(y + 1) * height).map(move |py| (px, py)))
+                .filter_map(|p| self.distance(p))
+        };
+        let square: usize = (-radius..=radius)
+            .flat_map(|x| (-radius..=radius).map(move |y| (x, y)))
+            .flat_map(copy)
+            .filter(|&from| from <= steps && from % 2 == steps % 2)
+            .count();
+        let sides: usize = [
+            (radius, 0, 0),
+            (-radius, 0, 1),
+            (0, radius, 2),
+            (0, -radius, 3),
+        ]
+        .into_iter()
+        .flat_map(|(x, y, i)| {
+            (-radius..=radius)
+                .map(move |n| if x == 0 { (n, y) } else { (x, n) })
+                .flat_map(copy)
+                .map(move |from| straight_on(from, self.offsets[i], steps))
+        })
+        .sum();
+        let corners: usize = [(1, 1, 0, 2), (-1, 1, 1, 2), (1, -1, 0, 3), (-1, -1, 1, 3)]
+            .into_iter()
+            .map(|(x, y, i, j)| {
+                let mut by_distance = HashMap::new();
+                for from in copy((x * radius, y * radius)) {
+                    *by_distance.entry(from).or_insert(0) += 1;
+                }
+                let (across, along) = (self.offsets[i], self.offsets[j]);
+                by_distance
+                    .into_iter()
+                    .map(|(from, plots)| {
+                        let copies = steps.saturating_sub(from) / across;
+                        let reached: usize = (1..=copies)
+                            .map(|n| straight_on(from + n * across, along, steps))
+                            .sum();
+                        plots * reached
+                    })
+                    .sum::<usize>()
+            })
+            .sum();
+        square + sides + corners
     }
-    (starting_position, rocks)
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (start, rocks) = parse_input(input);
-    let (max_x, max_y) = aoc_utils::grid_bounds(input);
-    let (max_x, max_y) = (max_x as i32, max_y as i32);
-    let num_steps = if max_x < 20 { 6 } else { 64 };
+/// How many of the copies `offset` steps apart straight on from a plot `from`
+/// steps away, not counting the plot itself, are reachable in exactly `steps`.
+fn straight_on(from: usize, offset: usize, steps: usize) -> usize {
+    let Some(left) = steps.checked_sub(from) else {
+        return 0;
+    };
+    let copies = left / offset;
+    match (offset % 2, left % 2) {
+        (0, 0) => copies,
+        (0, _) => 0,
+        (_, 0) => copies / 2,
+        _ => copies.div_ceil(2),
+    }
+}
 
-    let mut visited = HashMap::new();
-    let mut q = VecDeque::new();
-    q.push_back((start, 0));
+/// Counts the plots reachable in exactly `steps` on the endlessly tiled garden.
+///
+/// Squares of copies around the start are walked, doubling in size, until the
+/// copies past one provably keep on the same offsets as its outermost ones
+/// (see [`Tiling::is_exact`]). Then the sides grow linearly and the corners
+/// quadratically in `steps`, and are counted a copy at a time. A square that
+/// would reach further than `steps` is no better than walking all the way,
+/// which is what happens if none settles before that.
+fn reachable_plots_on_infinite_map(
+    garden: &Grid<char>,
+    start: (i32, i32),
+    steps: usize,
+) -> Result<usize, Error> {
+    let shortest_side = garden.width().min(garden.height());
+    let settled = std::iter::successors(Some(1), |radius| Some(radius * 2))
+        .take_while(|&radius| radius * shortest_side <= steps)
+        .filter_map(|radius| Tiling::new(garden, start, radius as i32))
+        .find(Tiling::is_exact);
+    if let Some(tiling) = settled {
+        return Ok(tiling.reachable_in(steps));
+    }
+    let layers: Vec<usize> = plots_by_distance(garden, start).take(steps + 1).collect();
+    Ok(reachable_in(&layers, steps))
+}
 
-    while let Some(((cx, cy), steps)) = q.pop_front() {
-        if steps > num_steps {
-            break;
-        }
+aoc_utils::solution! {
+    Day21: 2023 day 21;
+    type Parsed<'a> = ((i32, i32), Grid<char>);
+    parse: |input| parse_input(input);
+    part1: |(start, garden)| Ok(reachable_plots(garden, *start));
+    part2: |(start, garden)| {
+        reachable_plots_on_infinite_map(garden, *start, STEPS_ON_INFINITE_MAP)
+    };
+}
 
-        for (dx, dy) in [(0, 1), (0, -1), (1, 0), (-1, 0)].into_iter() {
-            let (nx, ny) = (cx + dx, cy + dy);
-            if nx >= 0
-                && nx < max_x
-                && ny >= 0
-                && ny < max_y
-                && !rocks.contains(&(nx, ny))
-                && visited.insert((nx, ny), steps + 1).is_none()
-            {
-                q.push_back(((nx, ny), steps + 1));
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_utils::inputs;
+    use proptest::prelude::*;
+
+    fn example() -> &'static str {
+        inputs::example(2023, 21, 1).unwrap()
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(process_part1(example()).as_str(), "16");
+    }
+
+    /// Walks the tiled gardens one step at a time, keeping track of every
+    /// plot within reach in a square around the start.
+    fn reachable_by_brute_force(garden: &Grid<char>, start: (i32, i32), steps: usize) -> usize {
+        let (width, height) = (garden.width() as i32, garden.height() as i32);
+        let side = 2 * steps + 3;
+        let index = |x: i32, y: i32| {
+            (y + steps as i32 + 1) as usize * side + (x + steps as i32 + 1) as usize
+        };
+        let mut reached = vec![false; side * side];
+        reached[index(0, 0)] = true;
+        for step in 1..=steps as i32 {
+            let mut next = vec![false; side * side];
+            // Only plots at most `step` away with the same parity can be reached.
+            for y in -step..=step {
+                let reach = step - y.abs();
+                for x in (-reach..=reach).step_by(2) {
+                    let (gx, gy) = (start.0 + x, start.1 + y);
+                    next[index(x, y)] = garden[(gx.rem_euclid(width), gy.rem_euclid(height))]
+                        != '#'
+                        && [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+                            .iter()
+                            .any(|&(x, y)| reached[index(x, y)]);
+                }
             }
+            reached = next;
         }
+        reached.iter().filter(|&&r| r).count()
     }
 
-    visited
-        .into_values()
-        .filter(|s| s % 2 == 0)
-        .count()
-        .to_string()
-}
+    #[test]
+    fn infinite_example_matches_brute_force() {
+        let (start, garden) = parse_input(example()).unwrap();
+        for steps in [6, 10, 50, 100, 500] {
+            assert_eq!(
+                reachable_plots_on_infinite_map(&garden, start, steps).unwrap(),
+                reachable_by_brute_force(&garden, start, steps),
+                "{steps} steps"
+            );
+        }
+    }
+
+    #[test]
+    fn example_settles_four_copies_out() {
+        let (start, garden) = parse_input(example()).unwrap();
+        let settles = |radius| Tiling::new(&garden, start, radius).is_some_and(|t| t.is_exact());
+        assert!(!settles(3));
+        assert!(settles(4));
+    }
+
+    #[test]
+    fn infinite_example() {
+        let (start, garden) = parse_input(example()).unwrap();
+        for (steps, plots) in [(6, 16), (50, 1594), (1000, 668697), (5000, 16733044)] {
+            assert_eq!(
+                reachable_plots_on_infinite_map(&garden, start, steps).unwrap(),
+                plots,
+                "{steps} steps"
+            );
+        }
+    }
 
-pub fn process_part2(input: &str) -> String {
-    let (start, rocks) = parse_input(input);
-    let (max_x, max_y) = aoc_utils::grid_bounds(input);
-    let (max_x, max_y) = (max_x as i32, max_y as i32);
+    /// Gardens of `sizes` with rocks anywhere and the start anywhere else.
+    fn garden(
+        sizes: impl Strategy<Value = (usize, usize)>,
+    ) -> impl Strategy<Value = (Grid<char>, (i32, i32))> {
+        sizes.prop_flat_map(|(width, height)| {
+            (
+                prop::collection::vec(prop::bool::weighted(0.25), width * height),
+                0..width as i32,
+                0..height as i32,
+            )
+                .prop_map(move |(rocks, x, y)| {
+                    let mut garden =
+                        Grid::new(width, height, rocks).map(|&rock| if rock { '#' } else { '.' });
+                    garden[(x, y)] = 'S';
+                    (garden, (x, y))
+                })
+        })
+    }
 
-    let mut visited = HashMap::new();
-    let mut q = VecDeque::new();
-    q.push_back((start, 0));
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
 
-    while let Some(((cx, cy), steps)) = q.pop_front() {
-        if !visited.insert((cx, cy), steps).is_none() {
-            continue;
+        #[test]
+        fn infinite_map_matches_brute_force(
+            (garden, start) in garden((3..=6usize, 3..=6usize)),
+            steps in 0..=60usize,
+        ) {
+            prop_assert_eq!(
+                reachable_plots_on_infinite_map(&garden, start, steps).unwrap(),
+                reachable_by_brute_force(&garden, start, steps)
+            );
         }
-        for (dx, dy) in [(0, 1), (0, -1), (1, 0), (-1, 0)].into_iter() {
-            let (nx, ny) = (cx + dx, cy + dy);
-            if nx >= 0
-                && nx < max_x
-                && ny >= 0
-                && ny < max_y
-                && !rocks.contains(&(nx, ny))
-                && !visited.contains_key(&(nx, ny))
-            {
-                q.push_back(((nx, ny), steps + 1));
-            }
+
+        #[test]
+        fn extrapolates_like_walking_all_the_way(
+            (garden, start) in garden((3..=7usize).prop_map(|size| (size, size))),
+            steps in 0..=300usize,
+        ) {
+            let layers: Vec<usize> = plots_by_distance(&garden, start).take(steps + 1).collect();
+            prop_assert_eq!(
+                reachable_plots_on_infinite_map(&garden, start, steps).unwrap(),
+                reachable_in(&layers, steps)
+            );
         }
     }
-
-    let even_corners = visited
-        .values()
-        .filter(|s| **s % 2 == 0 && **s > 65)
-        .count();
-    let odd_corners = visited
-        .values()
-        .filter(|s| **s % 2 == 1 && **s > 65)
-        .count();
-
-    let n = (26501365 - max_x as usize / 2) / max_y as usize;
-    assert_eq!(n, 202300);
-
-    let even = n * n;
-    let odd = (n + 1) * (n + 1);
-
-    let total = odd * visited.values().filter(|s| **s % 2 == 1).count()
-        + even * visited.values().filter(|s| **s % 2 == 0).count()
-        - ((n + 1) * odd_corners)
-        + (n * even_corners);
-    total.to_string()
 }
@@ -1,10 +1,12 @@
+use aoc_utils::inputs;
 use criterion::{
     criterion_group, criterion_main, Criterion,
 };
 use {{crate_name}}::*;
 
 fn criterion_benchmark_part1(c: &mut Criterion) {
-    let input = include_str!("../input.txt");
+    let input = inputs::input(2023, {{project-name | remove: "day-" | plus: 0}})
+        .unwrap_or_else(|err| panic!("{err}"));
 
     let mut group = c.benchmark_group("{{crate_name}}::part1");
     group.bench_with_input("part1", input, |b, input| {
@@ -15,7 +17,8 @@ fn criterion_benchmark_part1(c: &mut Criterion) {
 }
 
 fn criterion_benchmark_part2(c: &mut Criterion) {
-    let input = include_str!("../input.txt");
+    let input = inputs::input(2023, {{project-name | remove: "day-" | plus: 0}})
+        .unwrap_or_else(|err| panic!("{err}"));
 
     let mut group = c.benchmark_group("{{crate_name}}::part2");
     group.bench_with_input("part2", input, |b, input| {
@@ -1,6 +1,8 @@
+use aoc_utils::inputs;
 use {{crate_name}}::process_part1;
 
 fn main() {
-    let input = include_str!("../../input.txt");
+    let input = inputs::input(2023, {{project-name | remove: "day-" | plus: 0}})
+        .unwrap_or_else(|err| panic!("{err}"));
     println!("{}", process_part1(input));
 }
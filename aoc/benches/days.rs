@@ -0,0 +1,155 @@
+//! Benchmarks parsing and each part of every registered day whose input is
+//! present, then summarises the median times in `target/criterion/summary.csv`
+//! and `target/criterion/summary.md`.
+//!
+//! Criterion's usual filters apply, e.g. `cargo bench -p aoc -- 2023-day-07`.
+
+use std::{
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use aoc::{
+    days, panic_message,
+    report::{self, DayTimings},
+};
+use aoc_utils::{inputs, Entry, ParsedInput, Part};
+use criterion::{Criterion, SamplingMode};
+
+/// Steps slower than this get fewer, flat samples so that the slowest days
+/// finish in minutes rather than hours.
+const SLOW: Duration = Duration::from_millis(10);
+
+fn output_directory() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../target/criterion")
+}
+
+fn group_name(entry: &Entry) -> String {
+    format!("{}-day-{:02}", entry.year(), entry.day())
+}
+
+/// Runs every step once outside of criterion, so that a failing or panicking
+/// step is skipped instead of aborting the whole run. Returns the parsed input,
+/// the parts that can be benchmarked and the time of the slowest step.
+fn dry_run<'a>(
+    entry: &Entry,
+    input: &'a str,
+) -> Option<(Box<dyn ParsedInput + 'a>, Vec<Part>, Duration)> {
+    let name = format!("{} day {:02}", entry.year(), entry.day());
+    let start = Instant::now();
+    let parsed = match panic::catch_unwind(|| entry.parse(input)) {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(err)) => {
+            println!("{name}: cannot parse input, skipping: {err}");
+            return None;
+        }
+        Err(payload) => {
+            println!(
+                "{name}: parsing panicked, skipping: {}",
+                panic_message(payload)
+            );
+            return None;
+        }
+    };
+    let mut slowest = start.elapsed();
+
+    let mut parts = vec![];
+    for &part in entry.parts() {
+        let start = Instant::now();
+        match panic::catch_unwind(AssertUnwindSafe(|| parsed.solve(part))) {
+            Ok(Ok(_)) => parts.push(part),
+            Ok(Err(err)) => println!("{name} part {part}: failed, skipping: {err}"),
+            Err(payload) => {
+                println!(
+                    "{name} part {part}: panicked, skipping: {}",
+                    panic_message(payload)
+                );
+            }
+        }
+        slowest = slowest.max(start.elapsed());
+    }
+    Some((parsed, parts, slowest))
+}
+
+fn bench_day(c: &mut Criterion, entry: &Entry, input: &str) {
+    let Some((parsed, parts, slowest)) = dry_run(entry, input) else {
+        return;
+    };
+
+    let mut group = c.benchmark_group(group_name(entry));
+    if slowest > SLOW {
+        group.sample_size(10).sampling_mode(SamplingMode::Flat);
+    }
+    group.bench_function("parse", |b| b.iter_with_large_drop(|| entry.parse(input)));
+    for part in parts {
+        group.bench_function(format!("part{part}"), |b| b.iter(|| parsed.solve(part)));
+    }
+    group.finish();
+}
+
+/// The median of a benchmark, if it was measured after `since`. Benchmarks
+/// left out by a filter keep their results from earlier runs, which are ignored.
+fn median(group: &str, bench: &str, since: SystemTime) -> Option<Duration> {
+    let path = output_directory()
+        .join(group)
+        .join(bench)
+        .join("new/estimates.json");
+    if fs::metadata(&path).ok()?.modified().ok()? < since {
+        return None;
+    }
+    let estimates: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    let nanos = estimates["median"]["point_estimate"].as_f64()?;
+    Some(Duration::from_secs_f64(nanos / 1e9))
+}
+
+fn write_summary(timings: &[DayTimings]) {
+    let directory = output_directory();
+    let markdown = report::to_markdown(timings);
+    for (name, contents) in [
+        ("summary.csv", report::to_csv(timings)),
+        ("summary.md", markdown.clone()),
+    ] {
+        let path = directory.join(name);
+        if let Err(err) = fs::write(&path, contents) {
+            eprintln!("cannot write {}: {err}", path.display());
+        }
+    }
+    println!("\n{markdown}");
+}
+
+fn main() {
+    let started = SystemTime::now();
+    let mut criterion = Criterion::default()
+        .configure_from_args()
+        .output_directory(&output_directory());
+
+    let registry = days::registry();
+    for entry in registry.entries() {
+        match inputs::input(entry.year(), entry.day()) {
+            Ok(input) => bench_day(&mut criterion, entry, input),
+            Err(err) => println!("{}: {err}", group_name(entry)),
+        }
+    }
+    criterion.final_summary();
+
+    let timings = registry
+        .entries()
+        .map(|entry| {
+            let group = group_name(entry);
+            DayTimings {
+                year: entry.year(),
+                day: entry.day(),
+                parse: median(&group, "parse", started),
+                part1: median(&group, "part1", started),
+                part2: median(&group, "part2", started),
+            }
+        })
+        .filter(|t| t.parse.is_some())
+        .collect::<Vec<_>>();
+    if !timings.is_empty() {
+        write_summary(&timings);
+    }
+}
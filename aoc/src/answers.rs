@@ -0,0 +1,226 @@
+use std::{collections::BTreeMap, fmt, fs, io, path::Path};
+
+use aoc_utils::{Answer, Part};
+
+const HEADER: &str = "\
+# Accepted answers for the real puzzle inputs, checked by `aoc run`.
+# Record new ones with `aoc run <year> <day|all> --record`.
+";
+
+/// How an answer compares to the accepted one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Match,
+    Mismatch { expected: Answer },
+    Unknown,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Check::Match => write!(f, "match"),
+            Check::Mismatch { expected } => write!(f, "MISMATCH, expected {expected}"),
+            Check::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Accepted answers keyed by year, day and part, stored as a small subset of TOML:
+///
+/// ```toml
+/// [2022.10]
+/// part1 = 13140
+/// part2 = "PLEFULPB"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<(u16, u8, Part), Answer>,
+}
+
+impl Answers {
+    /// Reads the answers at `path`. A file that doesn't exist yet has no answers.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => text
+                .parse()
+                .map_err(|err| format!("{}: {err}", path.display())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(format!("cannot read {}: {err}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: Part) -> Option<&Answer> {
+        self.answers.get(&(year, day, part))
+    }
+
+    pub fn check(&self, year: u16, day: u8, part: Part, answer: &Answer) -> Check {
+        match self.get(year, day, part) {
+            Some(expected) if expected == answer => Check::Match,
+            Some(expected) => Check::Mismatch {
+                expected: expected.clone(),
+            },
+            None => Check::Unknown,
+        }
+    }
+
+    /// Records `answer` as accepted, replacing any previous answer.
+    pub fn insert(&mut self, year: u16, day: u8, part: Part, answer: Answer) {
+        self.answers.insert((year, day, part), answer);
+    }
+}
+
+/// Reads `year.day` written the way `Display` writes it, with the day padded to
+/// two digits, so `2023.1` can't name the same day as `2023.01`.
+fn parse_header(header: &str) -> Option<(u16, u8)> {
+    let (year, day) = header.split_once('.')?;
+    let (year, day) = (year.parse().ok()?, day.parse().ok()?);
+    (format!("{year}.{day:02}") == header).then_some((year, day))
+}
+
+fn parse_value(value: &str) -> Option<Answer> {
+    match value.strip_prefix('"') {
+        Some(quoted) => {
+            let mut text = String::new();
+            let mut chars = quoted.chars();
+            loop {
+                match chars.next()? {
+                    '"' => break,
+                    '\\' => text.push(chars.next().filter(|c| matches!(c, '"' | '\\'))?),
+                    c => text.push(c),
+                }
+            }
+            let rest = chars.as_str().trim_start();
+            (rest.is_empty() || rest.starts_with('#')).then(|| Answer::from(text))
+        }
+        None => {
+            let number = value.split('#').next().unwrap().trim();
+            number.parse().ok().map(Answer::Number)
+        }
+    }
+}
+
+impl std::str::FromStr for Answers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Self::default();
+        let mut table = None;
+        for (number, line) in s.lines().enumerate().map(|(i, l)| (i + 1, l.trim())) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix('[') {
+                let (year, day) = header
+                    .strip_suffix(']')
+                    .and_then(parse_header)
+                    .ok_or(format!("line {number}: expected a `[year.day]` table"))?;
+                table = Some((year, day));
+                continue;
+            }
+            let (year, day) = table.ok_or(format!("line {number}: answer outside a table"))?;
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(format!("line {number}: expected `partN = answer`"))?;
+            let part = key
+                .trim()
+                .strip_prefix("part")
+                .and_then(|n| n.parse().ok())
+                .and_then(Part::from_number)
+                .ok_or(format!("line {number}: unknown key `{}`", key.trim()))?;
+            let answer = parse_value(value.trim())
+                .ok_or(format!("line {number}: invalid answer `{}`", value.trim()))?;
+            if answers.get(year, day, part).is_some() {
+                return Err(format!(
+                    "line {number}: {year} day {day} part {part} is repeated"
+                ));
+            }
+            answers.insert(year, day, part, answer);
+        }
+        Ok(answers)
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{HEADER}")?;
+        let mut table = None;
+        for (&(year, day, part), answer) in &self.answers {
+            if table != Some((year, day)) {
+                table = Some((year, day));
+                write!(f, "\n[{year}.{day:02}]\n")?;
+            }
+            match answer {
+                Answer::Number(n) => writeln!(f, "part{part} = {n}")?,
+                Answer::Text(text) => writeln!(
+                    f,
+                    "part{part} = \"{}\"",
+                    text.replace('\\', "\\\\").replace('"', "\\\"")
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_text() {
+        let mut answers = Answers::default();
+        answers.insert(2023, 17, Part::Two, Answer::Number(94));
+        answers.insert(2023, 17, Part::One, Answer::Number(102));
+        answers.insert(2022, 10, Part::Two, Answer::from("PL\"EF\\"));
+        answers.insert(2023, 5, Part::One, Answer::Number(35));
+        let text = answers.to_string();
+        assert!(text.contains("[2022.10]\npart2 = \"PL\\\"EF\\\\\"\n"));
+        assert!(text.contains("[2023.05]\npart1 = 35\n"));
+        assert!(text.contains("[2023.17]\npart1 = 102\npart2 = 94\n"));
+        assert_eq!(text.parse(), Ok(answers));
+    }
+
+    #[test]
+    fn checks_answers() {
+        let answers: Answers = "[2023.01]\npart1 = 142 # example\n\n[2023.01]\npart2 = \"x\""
+            .parse()
+            .unwrap();
+        assert_eq!(
+            answers.check(2023, 1, Part::One, &Answer::from(142u32)),
+            Check::Match
+        );
+        assert_eq!(
+            answers.check(2023, 1, Part::Two, &Answer::from("y")),
+            Check::Mismatch {
+                expected: Answer::from("x")
+            }
+        );
+        assert_eq!(
+            answers.check(2023, 2, Part::One, &Answer::from(1u32)),
+            Check::Unknown
+        );
+    }
+
+    #[test]
+    fn reports_invalid_lines() {
+        let error = |text: &str| text.parse::<Answers>().unwrap_err();
+        assert_eq!(error("part1 = 1"), "line 1: answer outside a table");
+        assert_eq!(error("[2023]"), "line 1: expected a `[year.day]` table");
+        assert_eq!(error("[2023.1]"), "line 1: expected a `[year.day]` table");
+        assert_eq!(error("[2023.001]"), "line 1: expected a `[year.day]` table");
+        assert_eq!(error("[2023. 01]"), "line 1: expected a `[year.day]` table");
+        assert_eq!(error("[2023.01]\npart3 = 1"), "line 2: unknown key `part3`");
+        assert_eq!(
+            error("[2023.01]\npart1 = 1x"),
+            "line 2: invalid answer `1x`"
+        );
+        assert_eq!(
+            error("[2023.01]\npart1 = 1\npart1 = 2"),
+            "line 3: 2023 day 1 part 1 is repeated"
+        );
+    }
+}
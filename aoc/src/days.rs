@@ -0,0 +1,54 @@
+use aoc_utils::Registry;
+
+/// Every day that can be run.
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+    registry
+        .register::<day_2022_01::Day01>()
+        .register::<day_2022_02::Day02>()
+        .register::<day_2022_03::Day03>()
+        .register::<day_2022_04::Day04>()
+        .register::<day_2022_05::Day05>()
+        .register::<day_2022_06::Day06>()
+        .register::<day_2022_07::Day07>()
+        .register::<day_2022_08::Day08>()
+        .register::<day_2022_09::Day09>()
+        .register::<day_2022_10::Day10>()
+        .register::<day_2022_11::Day11>()
+        .register::<day_2022_12::Day12>()
+        .register::<day_2022_13::Day13>()
+        .register::<day_2022_14::Day14>()
+        .register::<day_2022_15::Day15>()
+        .register::<day_2022_16::Day16>()
+        .register::<day_2022_17::Day17>()
+        .register::<day_2022_18::Day18>()
+        .register::<day_2022_19::Day19>()
+        .register::<day_2022_20::Day20>()
+        .register::<day_2022_21::Day21>()
+        .register::<day_2022_22::Day22>()
+        .register::<day_2022_23::Day23>()
+        .register::<day_2022_24::Day24>()
+        .register::<day_2022_25::Day25>()
+        .register::<day_01::Day01>()
+        .register::<day_02::Day02>()
+        .register::<day_03::Day03>()
+        .register::<day_04::Day04>()
+        .register::<day_05::Day05>()
+        .register::<day_06::Day06>()
+        .register::<day_07::Day07>()
+        .register::<day_08::Day08>()
+        .register::<day_09::Day09>()
+        .register::<day_10::Day10>()
+        .register::<day_11::Day11>()
+        .register::<day_12::Day12>()
+        .register::<day_13::Day13>()
+        .register::<day_14::Day14>()
+        .register::<day_15::Day15>()
+        .register::<day_16::Day16>()
+        .register::<day_17::Day17>()
+        .register::<day_18::Day18>()
+        .register::<day_19::Day19>()
+        .register::<day_20::Day20>()
+        .register::<day_21::Day21>();
+    registry
+}
@@ -0,0 +1,23 @@
+//! The pieces of the runner that the `aoc` binary and the benchmarks share.
+
+use std::{
+    any::Any,
+    path::{Path, PathBuf},
+};
+
+pub mod answers;
+pub mod days;
+pub mod report;
+
+/// The accepted answers for the real puzzle inputs.
+pub fn answers_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../answers.toml")
+}
+
+pub fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
@@ -0,0 +1,328 @@
+use std::{
+    env,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use aoc::{
+    answers::{Answers, Check},
+    answers_path, days, panic_message,
+};
+use aoc_utils::{
+    inputs::{self, InputStore},
+    Entry, Part, Registry,
+};
+
+const USAGE: &str = "Usage: aoc run <year> <day|all> [--part <1|2>] [--input <path>] [--record]";
+
+#[derive(Debug, PartialEq)]
+struct RunArgs {
+    year: u16,
+    /// `None` runs every registered day of the year.
+    day: Option<u8>,
+    /// `None` runs every solved part.
+    part: Option<Part>,
+    input: Option<PathBuf>,
+    /// Accept answers that aren't in the answers file yet.
+    record: bool,
+}
+
+fn parse_args(args: &[String]) -> Result<RunArgs, String> {
+    let (command, rest) = args.split_first().ok_or(USAGE)?;
+    if command != "run" {
+        return Err(format!("unknown command `{command}`\n{USAGE}"));
+    }
+
+    let mut positional = vec![];
+    let mut part = None;
+    let mut input = None;
+    let mut record = false;
+    let mut rest = rest.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                let value = rest.next().ok_or("--part needs a value")?;
+                part = Some(
+                    value
+                        .parse()
+                        .ok()
+                        .and_then(Part::from_number)
+                        .ok_or(format!("invalid part `{value}`, expected 1 or 2"))?,
+                );
+            }
+            "--input" | "-i" => {
+                input = Some(PathBuf::from(rest.next().ok_or("--input needs a path")?));
+            }
+            "--record" => record = true,
+            flag if flag.starts_with('-') => return Err(format!("unknown option `{flag}`")),
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    let [year, day] = positional[..] else {
+        return Err(USAGE.to_string());
+    };
+    let year = year.parse().map_err(|_| format!("invalid year `{year}`"))?;
+    let day = match day {
+        "all" => None,
+        day => Some(day.parse().map_err(|_| format!("invalid day `{day}`"))?),
+    };
+    if day.is_none() && input.is_some() {
+        return Err("--input can only be used when running a single day".to_string());
+    }
+    Ok(RunArgs {
+        year,
+        day,
+        part,
+        input,
+        record,
+    })
+}
+
+/// Runs `f`, returning how long it took and the panic message if it panicked.
+fn timed<T>(f: impl FnOnce() -> T) -> (Result<T, String>, Duration) {
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(f)).map_err(panic_message);
+    (result, start.elapsed())
+}
+
+/// Runs the requested parts of one day and checks their answers, returning a
+/// description of every failure. With `record`, unknown answers are accepted.
+fn run_day(
+    entry: &Entry,
+    parts: &[Part],
+    input_path: &Path,
+    answers: &mut Answers,
+    record: bool,
+) -> Vec<String> {
+    let name = format!("{} day {:02}", entry.year(), entry.day());
+    let input = match inputs::read(input_path) {
+        Ok(input) => input,
+        Err(err) => {
+            println!("{name}: {err}");
+            return vec![format!("{name} (missing input)")];
+        }
+    };
+
+    let (parsed, parse_time) = timed(|| entry.parse(input));
+    let parsed = match parsed {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(err)) => {
+            println!("{name}: cannot parse input: {err}");
+            return vec![format!("{name} (parse)")];
+        }
+        Err(message) => {
+            println!("{name}: parsing panicked after {parse_time:.2?}: {message}");
+            return vec![format!("{name} (parse)")];
+        }
+    };
+
+    let mut failures = vec![];
+    for part in parts {
+        let (answer, solve_time) = timed(|| parsed.solve(*part));
+        match answer {
+            Ok(Ok(answer)) => {
+                let check = answers.check(entry.year(), entry.day(), *part, &answer);
+                let status = match &check {
+                    Check::Unknown if record => {
+                        answers.insert(entry.year(), entry.day(), *part, answer.clone());
+                        "recorded".to_string()
+                    }
+                    check => check.to_string(),
+                };
+                println!(
+                    "{name} part {part}: {answer}  [{status}]  (parse {parse_time:.2?}, solve {solve_time:.2?})"
+                );
+                if let Check::Mismatch { expected } = check {
+                    failures.push(format!("{name} part {part} (expected {expected})"));
+                }
+            }
+            Ok(Err(err)) => {
+                println!("{name} part {part}: failed after {solve_time:.2?}: {err}");
+                failures.push(format!("{name} part {part}"));
+            }
+            Err(message) => {
+                println!("{name} part {part}: panicked after {solve_time:.2?}: {message}");
+                failures.push(format!("{name} part {part}"));
+            }
+        }
+    }
+    failures
+}
+
+fn run(
+    registry: &Registry,
+    store: &InputStore,
+    args: &RunArgs,
+    answers: &mut Answers,
+) -> Result<Vec<String>, String> {
+    let entries = match args.day {
+        Some(day) => vec![registry
+            .get(args.year, day)
+            .ok_or(format!("{} day {day:02} has no solution", args.year))?],
+        None => registry
+            .entries()
+            .filter(|e| e.year() == args.year)
+            .collect(),
+    };
+    if entries.is_empty() {
+        return Err(format!("{} has no solutions", args.year));
+    }
+
+    let mut failures = vec![];
+    for entry in entries {
+        let parts = match args.part {
+            Some(part) if entry.has_part(part) => vec![part],
+            Some(part) => {
+                println!(
+                    "{} day {:02} part {part}: not solved yet",
+                    entry.year(),
+                    entry.day()
+                );
+                continue;
+            }
+            None => entry.parts().to_vec(),
+        };
+        let input_path = args
+            .input
+            .clone()
+            .unwrap_or_else(|| store.input_path(entry.year(), entry.day()));
+        failures.extend(run_day(entry, &parts, &input_path, answers, args.record));
+    }
+    Ok(failures)
+}
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let result = parse_args(&args).and_then(|args| {
+        let path = answers_path();
+        let mut answers = Answers::load(&path)?;
+        let store = InputStore::from_env();
+        let failures = run(&days::registry(), &store, &args, &mut answers)?;
+        if args.record {
+            answers
+                .save(&path)
+                .map_err(|err| format!("cannot write {}: {err}", path.display()))?;
+        }
+        Ok(failures)
+    });
+    match result {
+        Ok(failures) if failures.is_empty() => ExitCode::SUCCESS,
+        Ok(failures) => {
+            eprintln!("failed: {}", failures.join(", "));
+            ExitCode::FAILURE
+        }
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use aoc_utils::Answer;
+
+    use super::*;
+
+    const EXAMPLE: &str = "\
+2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533";
+
+    fn args(line: &str) -> Result<RunArgs, String> {
+        parse_args(&line.split(' ').map(String::from).collect::<Vec<_>>())
+    }
+
+    fn write_input(name: &str, contents: &str) -> PathBuf {
+        let dir = env::temp_dir().join("aoc-runner-test");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn parses_run_arguments() {
+        assert_eq!(
+            args("run 2023 17 --part 2 --input some/path.txt"),
+            Ok(RunArgs {
+                year: 2023,
+                day: Some(17),
+                part: Some(Part::Two),
+                input: Some(PathBuf::from("some/path.txt")),
+                record: false,
+            })
+        );
+        assert_eq!(
+            args("run 2023 all --record"),
+            Ok(RunArgs {
+                year: 2023,
+                day: None,
+                part: None,
+                input: None,
+                record: true,
+            })
+        );
+        assert!(args("run 2023 all --input x").is_err());
+        assert!(args("run 2023 17 --part 3").is_err());
+        assert!(args("run 2023").is_err());
+        assert!(args("walk 2023 17").is_err());
+    }
+
+    #[test]
+    fn reports_failures_per_day() {
+        let registry = days::registry();
+        let entry = registry.get(2023, 17).unwrap();
+        let mut answers = Answers::default();
+        let missing = Path::new("/nonexistent/input.txt");
+        assert_eq!(
+            run_day(entry, &Part::BOTH, missing, &mut answers, false),
+            vec!["2023 day 17 (missing input)".to_string()]
+        );
+
+        // The heat loss map is made of digits only.
+        let garbage = write_input("garbage.txt", "not a number");
+        assert_eq!(
+            run_day(entry, &Part::BOTH, &garbage, &mut answers, false),
+            vec!["2023 day 17 (parse)".to_string()]
+        );
+    }
+
+    #[test]
+    fn checks_and_records_answers() {
+        let registry = days::registry();
+        let entry = registry.get(2023, 17).unwrap();
+        let input = write_input("2023-17.txt", EXAMPLE);
+        let mut answers = Answers::default();
+        answers.insert(2023, 17, Part::One, Answer::Number(101));
+
+        assert_eq!(
+            run_day(entry, &Part::BOTH, &input, &mut answers, false),
+            vec!["2023 day 17 part 1 (expected 101)".to_string()]
+        );
+        assert_eq!(answers.get(2023, 17, Part::Two), None);
+
+        // Recording accepts unknown answers but leaves mismatches alone.
+        assert_eq!(
+            run_day(entry, &Part::BOTH, &input, &mut answers, true).len(),
+            1
+        );
+        assert_eq!(answers.get(2023, 17, Part::One), Some(&Answer::Number(101)));
+        assert_eq!(answers.get(2023, 17, Part::Two), Some(&Answer::Number(94)));
+    }
+}
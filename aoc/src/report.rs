@@ -0,0 +1,140 @@
+use std::{fmt::Write, time::Duration};
+
+/// How long a day may take in total, parsing included.
+pub const BUDGET: Duration = Duration::from_secs(1);
+
+/// Median times of one day. Steps that weren't measured, such as unsolved or
+/// panicking parts, are `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayTimings {
+    pub year: u16,
+    pub day: u8,
+    pub parse: Option<Duration>,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+impl DayTimings {
+    pub fn total(&self) -> Duration {
+        [self.parse, self.part1, self.part2]
+            .into_iter()
+            .flatten()
+            .sum()
+    }
+
+    pub fn over_budget(&self) -> bool {
+        self.total() > BUDGET
+    }
+}
+
+/// One row per day with times in nanoseconds; unmeasured steps are left empty.
+pub fn to_csv(timings: &[DayTimings]) -> String {
+    let nanos = |d: Option<Duration>| d.map(|d| d.as_nanos().to_string()).unwrap_or_default();
+    let mut csv = "year,day,parse_ns,part1_ns,part2_ns,total_ns,over_budget\n".to_string();
+    for t in timings {
+        writeln!(
+            csv,
+            "{},{},{},{},{},{},{}",
+            t.year,
+            t.day,
+            nanos(t.parse),
+            nanos(t.part1),
+            nanos(t.part2),
+            t.total().as_nanos(),
+            t.over_budget()
+        )
+        .unwrap();
+    }
+    csv
+}
+
+/// A table of median times, with the totals of days over [`BUDGET`] in bold.
+pub fn to_markdown(timings: &[DayTimings]) -> String {
+    let time = |d: Option<Duration>| d.map(|d| format!("{d:.2?}")).unwrap_or("-".to_string());
+    let mut md = "| Year | Day | Parse | Part 1 | Part 2 | Total |\n".to_string();
+    md.push_str("|---:|---:|---:|---:|---:|---:|\n");
+    for t in timings {
+        let total = match t.over_budget() {
+            true => format!("**{:.2?}**", t.total()),
+            false => format!("{:.2?}", t.total()),
+        };
+        writeln!(
+            md,
+            "| {} | {:02} | {} | {} | {} | {total} |",
+            t.year,
+            t.day,
+            time(t.parse),
+            time(t.part1),
+            time(t.part2)
+        )
+        .unwrap();
+    }
+    let over = timings.iter().filter(|t| t.over_budget()).count();
+    writeln!(
+        md,
+        "\n{over} of {} days over the {BUDGET:?} budget.",
+        timings.len()
+    )
+    .unwrap();
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timings() -> Vec<DayTimings> {
+        vec![
+            DayTimings {
+                year: 2022,
+                day: 19,
+                parse: Some(Duration::from_micros(15)),
+                part1: Some(Duration::from_millis(700)),
+                part2: Some(Duration::from_millis(400)),
+            },
+            DayTimings {
+                year: 2023,
+                day: 7,
+                parse: Some(Duration::from_micros(120)),
+                part1: Some(Duration::from_nanos(85_500)),
+                part2: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn totals_measured_steps() {
+        let [slow, fast] = &timings()[..] else {
+            unreachable!()
+        };
+        assert_eq!(slow.total(), Duration::from_micros(1_100_015));
+        assert!(slow.over_budget());
+        assert_eq!(fast.total(), Duration::from_nanos(205_500));
+        assert!(!fast.over_budget());
+    }
+
+    #[test]
+    fn writes_csv() {
+        assert_eq!(
+            to_csv(&timings()),
+            "year,day,parse_ns,part1_ns,part2_ns,total_ns,over_budget
+2022,19,15000,700000000,400000000,1100015000,true
+2023,7,120000,85500,,205500,false
+"
+        );
+    }
+
+    #[test]
+    fn writes_markdown() {
+        assert_eq!(
+            to_markdown(&timings()),
+            "| Year | Day | Parse | Part 1 | Part 2 | Total |
+|---:|---:|---:|---:|---:|---:|
+| 2022 | 19 | 15.00µs | 700.00ms | 400.00ms | **1.10s** |
+| 2023 | 07 | 120.00µs | 85.50µs | - | 205.50µs |
+
+1 of 2 days over the 1s budget.
+"
+        );
+    }
+}
@@ -0,0 +1,259 @@
+/// A sequence that wraps around, where any element can be moved some number of
+/// places forwards or backwards in `O(log n)`.
+///
+/// Elements are referred to by their handle, which is the index they were given
+/// when the sequence was built, so they can be followed however they move.
+/// Positions count from wherever the sequence currently starts; since it is
+/// circular, only the order of the elements is meaningful.
+///
+/// It is an implicit treap: a binary tree ordered by position, balanced by
+/// random priorities, with every node knowing the size of its subtree and its
+/// parent so that positions can be found going up as well as down.
+#[derive(Debug, Clone)]
+pub struct CircularSequence<T> {
+    nodes: Vec<Node<T>>,
+    root: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    value: T,
+    priority: u64,
+    size: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+    parent: Option<usize>,
+}
+
+impl<T> FromIterator<T> for CircularSequence<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(values: I) -> Self {
+        let mut sequence = CircularSequence {
+            nodes: vec![],
+            root: None,
+        };
+        for value in values {
+            let handle = sequence.nodes.len();
+            sequence.nodes.push(Node {
+                value,
+                priority: splitmix64(handle as u64),
+                size: 1,
+                left: None,
+                right: None,
+                parent: None,
+            });
+            sequence.root = sequence.merge(sequence.root, Some(handle));
+        }
+        sequence.detach(sequence.root);
+        sequence
+    }
+}
+
+impl<T> CircularSequence<T> {
+    pub fn len(&self) -> usize {
+        self.size(self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// The element with `handle`, wherever it is.
+    pub fn value(&self, handle: usize) -> &T {
+        &self.nodes[handle].value
+    }
+
+    /// Where the element with `handle` currently is.
+    pub fn position(&self, handle: usize) -> usize {
+        let mut position = self.size(self.nodes[handle].left);
+        let mut node = handle;
+        while let Some(parent) = self.nodes[node].parent {
+            if self.nodes[parent].right == Some(node) {
+                position += self.size(self.nodes[parent].left) + 1;
+            }
+            node = parent;
+        }
+        position
+    }
+
+    /// The element at `position`, wrapping around the end.
+    pub fn get(&self, position: usize) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut position = position % self.len();
+        let mut node = self.root?;
+        loop {
+            let left = self.size(self.nodes[node].left);
+            match position.cmp(&left) {
+                std::cmp::Ordering::Less => node = self.nodes[node].left?,
+                std::cmp::Ordering::Equal => return Some(&self.nodes[node].value),
+                std::cmp::Ordering::Greater => {
+                    position -= left + 1;
+                    node = self.nodes[node].right?;
+                }
+            }
+        }
+    }
+
+    /// Takes the element with `handle` out and puts it back `offset` places
+    /// further along, passing over the other `len - 1` elements. Moving by a
+    /// multiple of `len - 1` therefore leaves the order as it was.
+    pub fn move_by(&mut self, handle: usize, offset: i64) {
+        let len = self.len();
+        if len <= 2 {
+            return;
+        }
+        let others = len as i64 - 1;
+        let from = self.position(handle);
+        let (before, rest) = self.split(self.root, from);
+        let (node, after) = self.split(rest, 1);
+        let rest = self.merge(before, after);
+
+        let to = (from as i64 + offset.rem_euclid(others)).rem_euclid(others) as usize;
+        let (before, after) = self.split(rest, to);
+        let before = self.merge(before, node);
+        self.root = self.merge(before, after);
+        self.detach(self.root);
+    }
+
+    /// All elements in order, starting from position 0.
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        let mut stack = vec![];
+        let mut node = self.root;
+        std::iter::from_fn(move || {
+            while let Some(n) = node {
+                stack.push(n);
+                node = self.nodes[n].left;
+            }
+            let n = stack.pop()?;
+            node = self.nodes[n].right;
+            Some(&self.nodes[n].value)
+        })
+    }
+
+    fn size(&self, node: Option<usize>) -> usize {
+        node.map_or(0, |n| self.nodes[n].size)
+    }
+
+    fn detach(&mut self, node: Option<usize>) {
+        if let Some(n) = node {
+            self.nodes[n].parent = None;
+        }
+    }
+
+    fn set_children(&mut self, node: usize, left: Option<usize>, right: Option<usize>) {
+        for child in [left, right].into_iter().flatten() {
+            self.nodes[child].parent = Some(node);
+        }
+        self.nodes[node].left = left;
+        self.nodes[node].right = right;
+        self.nodes[node].size = self.size(left) + self.size(right) + 1;
+    }
+
+    /// Splits the first `count` elements of the tree at `node` off from the rest.
+    fn split(&mut self, node: Option<usize>, count: usize) -> (Option<usize>, Option<usize>) {
+        let Some(n) = node else {
+            return (None, None);
+        };
+        let (left, right) = (self.nodes[n].left, self.nodes[n].right);
+        let left_size = self.size(left);
+        if count <= left_size {
+            let (before, after) = self.split(left, count);
+            self.set_children(n, after, right);
+            self.detach(before);
+            (before, Some(n))
+        } else {
+            let (before, after) = self.split(right, count - left_size - 1);
+            self.set_children(n, left, before);
+            self.detach(after);
+            (Some(n), after)
+        }
+    }
+
+    /// Joins two trees, with all of `a` coming before all of `b`.
+    fn merge(&mut self, a: Option<usize>, b: Option<usize>) -> Option<usize> {
+        let (x, y) = match (a, b) {
+            (None, tree) | (tree, None) => return tree,
+            (Some(x), Some(y)) => (x, y),
+        };
+        if self.nodes[x].priority > self.nodes[y].priority {
+            let right = self.merge(self.nodes[x].right, Some(y));
+            self.set_children(x, self.nodes[x].left, right);
+            Some(x)
+        } else {
+            let left = self.merge(Some(x), self.nodes[y].left);
+            self.set_children(y, left, self.nodes[y].right);
+            Some(y)
+        }
+    }
+}
+
+/// Well-mixed priorities without pulling in a random number generator.
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+
+    /// The same move, done by searching and shifting a `VecDeque`.
+    fn move_slowly(list: &mut VecDeque<usize>, handle: usize, offset: i64) {
+        let from = list.iter().position(|h| *h == handle).unwrap();
+        list.remove(from);
+        let to = (from as i64 + offset).rem_euclid(list.len() as i64) as usize;
+        list.insert(to, handle);
+    }
+
+    #[test]
+    fn finds_elements_by_position_and_handle() {
+        let sequence: CircularSequence<char> = "abcdef".chars().collect();
+        assert_eq!(sequence.len(), 6);
+        assert_eq!(sequence.iter().collect::<String>(), "abcdef");
+        assert_eq!(sequence.get(2), Some(&'c'));
+        assert_eq!(sequence.get(8), Some(&'c'));
+        assert_eq!(sequence.position(4), 4);
+        assert_eq!(sequence.value(4), &'e');
+        assert!(CircularSequence::<char>::from_iter([]).get(0).is_none());
+    }
+
+    #[test]
+    fn moves_like_a_list() {
+        for len in [1, 2, 3, 7, 100] {
+            let mut sequence: CircularSequence<usize> = (0..len).collect();
+            let mut list: VecDeque<usize> = (0..len).collect();
+            let mut seed = len as u64;
+            for _ in 0..500 {
+                seed = splitmix64(seed);
+                let handle = seed as usize % len;
+                let offset = (seed >> 32) as i64 % (5 * len as i64) - 2 * len as i64;
+                sequence.move_by(handle, offset);
+                if len > 2 {
+                    move_slowly(&mut list, handle, offset);
+                }
+                assert_eq!(sequence.iter().copied().collect::<VecDeque<_>>(), list);
+                assert_eq!(
+                    sequence.position(handle),
+                    list.iter().position(|h| *h == handle).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn moves_around_the_other_elements() {
+        let mut sequence: CircularSequence<usize> = (0..4).collect();
+        // Going all the way around the 3 others ends up where it started.
+        sequence.move_by(1, 3);
+        assert_eq!(sequence.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3]);
+        sequence.move_by(1, -3 * 1_000_000_001);
+        assert_eq!(sequence.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3]);
+        sequence.move_by(1, 4);
+        assert_eq!(sequence.iter().copied().collect::<Vec<_>>(), [0, 2, 1, 3]);
+    }
+}
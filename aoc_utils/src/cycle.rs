@@ -0,0 +1,123 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// The outcome of [`find_cycle`] or [`find_cycle_by`]: a record for every step up
+/// to and including the first repeated one.
+#[derive(Debug, Clone)]
+pub struct Cycle<T> {
+    start: usize,
+    period: usize,
+    history: Vec<T>,
+}
+
+impl<T> Cycle<T> {
+    /// The first step that is part of the cycle.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Number of steps after which the cycle repeats.
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// The recorded step that is equivalent to step `n`.
+    pub fn index_at(&self, n: usize) -> usize {
+        if n < self.history.len() {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+
+    /// The record at step `n`, assuming the records themselves repeat.
+    pub fn at(&self, n: usize) -> &T {
+        &self.history[self.index_at(n)]
+    }
+
+    /// The value of `metric` at step `n`, for metrics that grow by the same amount
+    /// every time the cycle repeats (such as the height of a tower).
+    pub fn extrapolate(&self, n: usize, mut metric: impl FnMut(&T) -> i64) -> i64 {
+        if n < self.history.len() {
+            return metric(&self.history[n]);
+        }
+        let growth_per_cycle =
+            metric(&self.history[self.start + self.period]) - metric(&self.history[self.start]);
+        let full_cycles = ((n - self.start) / self.period) as i64;
+        metric(&self.history[self.index_at(n)]) + full_cycles * growth_per_cycle
+    }
+
+    pub fn history(&self) -> &[T] {
+        &self.history
+    }
+}
+
+/// Repeatedly applies `step_fn` to `initial_state` until a state repeats.
+#[inline]
+pub fn find_cycle<S, F>(initial_state: S, step_fn: F) -> Cycle<S>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(S) -> S,
+{
+    find_cycle_by(initial_state, step_fn, S::clone, S::clone)
+}
+
+/// Repeatedly applies `step_fn` to `initial_state` until two states share the same
+/// fingerprint according to `key_fn`, keeping only what `record_fn` extracts from
+/// each state. Useful when states are too large to keep around or compare.
+pub fn find_cycle_by<S, K, T, F, G, H>(
+    initial_state: S,
+    mut step_fn: F,
+    mut key_fn: G,
+    mut record_fn: H,
+) -> Cycle<T>
+where
+    K: Eq + Hash,
+    F: FnMut(S) -> S,
+    G: FnMut(&S) -> K,
+    H: FnMut(&S) -> T,
+{
+    let mut seen = HashMap::new();
+    let mut history = vec![];
+    let mut state = initial_state;
+    loop {
+        history.push(record_fn(&state));
+        if let Some(start) = seen.insert(key_fn(&state), history.len() - 1) {
+            return Cycle {
+                start,
+                period: history.len() - 1 - start,
+                history,
+            };
+        }
+        state = step_fn(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_start_and_period() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 2 -> ...
+        let cycle = find_cycle(0, |n| if n == 4 { 2 } else { n + 1 });
+        assert_eq!(cycle.start(), 2);
+        assert_eq!(cycle.period(), 3);
+        assert_eq!(*cycle.at(1), 1);
+        assert_eq!(*cycle.at(5), 2);
+        assert_eq!(*cycle.at(7), 4);
+        assert_eq!(*cycle.at(1_000_000_000), 2 + (1_000_000_000 - 2) % 3);
+    }
+
+    #[test]
+    fn extrapolates_growing_metric() {
+        // Position on a circular track of length 3 as the key, distance travelled as the metric.
+        let cycle = find_cycle_by(0i64, |n| n + 1, |n| n % 3, |n| *n);
+        assert_eq!(cycle.start(), 0);
+        assert_eq!(cycle.period(), 3);
+        assert_eq!(cycle.extrapolate(2, |n| *n), 2);
+        assert_eq!(
+            cycle.extrapolate(1_000_000_000_000, |n| *n),
+            1_000_000_000_000
+        );
+    }
+}
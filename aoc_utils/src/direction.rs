@@ -0,0 +1,433 @@
+use std::fmt;
+
+/// One of the four orthogonal directions on a grid.
+///
+/// [`Direction::delta`] uses the screen convention where `y` grows downwards, which
+/// is how puzzle inputs are laid out and how [`crate::Grid`] is indexed.
+/// [`Direction::delta_y_up`] uses the mathematical convention instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+/// A change of heading relative to the current one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left,
+    Right,
+    Around,
+}
+
+impl Direction {
+    /// All directions, clockwise starting from [`Direction::Up`].
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_cw(self) -> Direction {
+        Self::from_index(self.index() + 1)
+    }
+
+    pub fn turn_ccw(self) -> Direction {
+        Self::from_index(self.index() + 3)
+    }
+
+    pub fn reverse(self) -> Direction {
+        Self::from_index(self.index() + 2)
+    }
+
+    pub fn turn(self, turn: Turn) -> Direction {
+        match turn {
+            Turn::Left => self.turn_ccw(),
+            Turn::Right => self.turn_cw(),
+            Turn::Around => self.reverse(),
+        }
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+
+    pub fn is_vertical(self) -> bool {
+        !self.is_horizontal()
+    }
+
+    /// Offset of a single step, with `y` growing downwards.
+    pub fn delta(self) -> (i32, i32) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+        }
+    }
+
+    /// Offset of a single step, with `y` growing upwards.
+    pub fn delta_y_up(self) -> (i32, i32) {
+        let (dx, dy) = self.delta();
+        (dx, -dy)
+    }
+
+    /// The position one step away from `(x, y)`, with `y` growing downwards.
+    pub fn step(self, (x, y): (i32, i32)) -> (i32, i32) {
+        let (dx, dy) = self.delta();
+        (x + dx, y + dy)
+    }
+
+    /// The facing value used throughout the puzzles: right is 0, then clockwise.
+    pub fn index(self) -> usize {
+        match self {
+            Direction::Right => 0,
+            Direction::Down => 1,
+            Direction::Left => 2,
+            Direction::Up => 3,
+        }
+    }
+
+    /// Inverse of [`Direction::index`], wrapping around after 3.
+    pub fn from_index(index: usize) -> Direction {
+        [
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+            Direction::Up,
+        ][index % 4]
+    }
+
+    /// Parses any of the common encodings: `U/D/L/R`, `N/E/S/W`, `^v<>` and the
+    /// facing values `0-3` (see [`Direction::index`]).
+    pub fn from_char(c: char) -> Option<Direction> {
+        match c {
+            'U' | 'N' | '^' | '3' => Some(Direction::Up),
+            'R' | 'E' | '>' | '0' => Some(Direction::Right),
+            'D' | 'S' | 'v' | '1' => Some(Direction::Down),
+            'L' | 'W' | '<' | '2' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    /// The arrow used for this direction in puzzle inputs.
+    pub fn to_arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Direction::from_char(c).ok_or(c)
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_arrow())
+    }
+}
+
+impl Turn {
+    /// Parses `L` and `R`.
+    pub fn from_char(c: char) -> Option<Turn> {
+        match c {
+            'L' => Some(Turn::Left),
+            'R' => Some(Turn::Right),
+            _ => None,
+        }
+    }
+}
+
+/// One of the eight directions on a grid, including the diagonals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// All directions, clockwise starting from [`Direction8::Up`].
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|d| *d == self).unwrap()
+    }
+
+    /// Rotates by 45 degrees clockwise.
+    pub fn turn_cw(self) -> Direction8 {
+        Self::ALL[(self.index() + 1) % 8]
+    }
+
+    /// Rotates by 45 degrees counter-clockwise.
+    pub fn turn_ccw(self) -> Direction8 {
+        Self::ALL[(self.index() + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Direction8 {
+        Self::ALL[(self.index() + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    /// Offset of a single step, with `y` growing downwards.
+    pub fn delta(self) -> (i32, i32) {
+        match self {
+            Direction8::Up => (0, -1),
+            Direction8::UpRight => (1, -1),
+            Direction8::Right => (1, 0),
+            Direction8::DownRight => (1, 1),
+            Direction8::Down => (0, 1),
+            Direction8::DownLeft => (-1, 1),
+            Direction8::Left => (-1, 0),
+            Direction8::UpLeft => (-1, -1),
+        }
+    }
+
+    /// Offset of a single step, with `y` growing upwards.
+    pub fn delta_y_up(self) -> (i32, i32) {
+        let (dx, dy) = self.delta();
+        (dx, -dy)
+    }
+
+    /// The position one step away from `(x, y)`, with `y` growing downwards.
+    pub fn step(self, (x, y): (i32, i32)) -> (i32, i32) {
+        let (dx, dy) = self.delta();
+        (x + dx, y + dy)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        match dir {
+            Direction::Up => Direction8::Up,
+            Direction::Right => Direction8::Right,
+            Direction::Down => Direction8::Down,
+            Direction::Left => Direction8::Left,
+        }
+    }
+}
+
+/// A direction on a hex grid with flat-topped cells, so the neighbours are north,
+/// south and the four diagonals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HexDirection {
+    North,
+    NorthEast,
+    SouthEast,
+    South,
+    SouthWest,
+    NorthWest,
+}
+
+impl HexDirection {
+    /// All directions, clockwise starting from [`HexDirection::North`].
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::North,
+        HexDirection::NorthEast,
+        HexDirection::SouthEast,
+        HexDirection::South,
+        HexDirection::SouthWest,
+        HexDirection::NorthWest,
+    ];
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|d| *d == self).unwrap()
+    }
+
+    /// Rotates by 60 degrees clockwise.
+    pub fn turn_cw(self) -> HexDirection {
+        Self::ALL[(self.index() + 1) % 6]
+    }
+
+    /// Rotates by 60 degrees counter-clockwise.
+    pub fn turn_ccw(self) -> HexDirection {
+        Self::ALL[(self.index() + 5) % 6]
+    }
+
+    pub fn reverse(self) -> HexDirection {
+        Self::ALL[(self.index() + 3) % 6]
+    }
+
+    /// Offset of a single step in cube coordinates `(q, r, s)`, which always sum to
+    /// zero. The distance between two cells is the largest absolute coordinate of
+    /// their difference.
+    pub fn delta(self) -> (i32, i32, i32) {
+        match self {
+            HexDirection::North => (0, -1, 1),
+            HexDirection::NorthEast => (1, -1, 0),
+            HexDirection::SouthEast => (1, 0, -1),
+            HexDirection::South => (0, 1, -1),
+            HexDirection::SouthWest => (-1, 1, 0),
+            HexDirection::NorthWest => (-1, 0, 1),
+        }
+    }
+
+    /// Parses `n`, `ne`, `se`, `s`, `sw` and `nw`, in either case.
+    pub fn from_name(name: &str) -> Option<HexDirection> {
+        match name.to_ascii_lowercase().as_str() {
+            "n" => Some(HexDirection::North),
+            "ne" => Some(HexDirection::NorthEast),
+            "se" => Some(HexDirection::SouthEast),
+            "s" => Some(HexDirection::South),
+            "sw" => Some(HexDirection::SouthWest),
+            "nw" => Some(HexDirection::NorthWest),
+            _ => None,
+        }
+    }
+}
+
+/// A direction along one of the three axes in space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction3 {
+    PosX,
+    NegX,
+    PosY,
+    NegY,
+    PosZ,
+    NegZ,
+}
+
+impl Direction3 {
+    pub const ALL: [Direction3; 6] = [
+        Direction3::PosX,
+        Direction3::NegX,
+        Direction3::PosY,
+        Direction3::NegY,
+        Direction3::PosZ,
+        Direction3::NegZ,
+    ];
+
+    pub fn reverse(self) -> Direction3 {
+        match self {
+            Direction3::PosX => Direction3::NegX,
+            Direction3::NegX => Direction3::PosX,
+            Direction3::PosY => Direction3::NegY,
+            Direction3::NegY => Direction3::PosY,
+            Direction3::PosZ => Direction3::NegZ,
+            Direction3::NegZ => Direction3::PosZ,
+        }
+    }
+
+    pub fn delta(self) -> (i32, i32, i32) {
+        match self {
+            Direction3::PosX => (1, 0, 0),
+            Direction3::NegX => (-1, 0, 0),
+            Direction3::PosY => (0, 1, 0),
+            Direction3::NegY => (0, -1, 0),
+            Direction3::PosZ => (0, 0, 1),
+            Direction3::NegZ => (0, 0, -1),
+        }
+    }
+
+    fn from_delta(delta: (i32, i32, i32)) -> Option<Direction3> {
+        Self::ALL.into_iter().find(|d| d.delta() == delta)
+    }
+
+    /// Rotates a quarter turn around `axis`, counter-clockwise when looking down
+    /// from the tip of `axis` (the right-hand rule). Directions parallel to `axis`
+    /// are left unchanged.
+    pub fn rotate(self, axis: Direction3) -> Direction3 {
+        let (ax, ay, az) = axis.delta();
+        let (x, y, z) = self.delta();
+        let cross = (ay * z - az * y, az * x - ax * z, ax * y - ay * x);
+        Self::from_delta(cross).unwrap_or(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning_and_reversing() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_cw().turn_ccw(), dir);
+            assert_eq!(dir.turn_cw().turn_cw(), dir.reverse());
+            assert_eq!(dir.turn(Turn::Around), dir.reverse());
+            assert_eq!(Direction::from_index(dir.index()), dir);
+        }
+        assert_eq!(Direction::Up.turn(Turn::Right), Direction::Right);
+        assert_eq!(Direction::Down.turn(Turn::Right), Direction::Left);
+        assert_eq!(Direction::Up.turn_cw(), Direction::ALL[1]);
+        assert_eq!(Direction8::Up.turn_cw(), Direction8::UpRight);
+        assert_eq!(Direction8::UpLeft.turn_cw(), Direction8::Up);
+        assert_eq!(Direction8::DownRight.reverse(), Direction8::UpLeft);
+        assert_eq!(HexDirection::North.turn_ccw(), HexDirection::NorthWest);
+        assert_eq!(HexDirection::SouthWest.reverse(), HexDirection::NorthEast);
+    }
+
+    #[test]
+    fn deltas_in_both_conventions() {
+        assert_eq!(Direction::Down.delta(), (0, 1));
+        assert_eq!(Direction::Down.delta_y_up(), (0, -1));
+        assert_eq!(Direction::Left.step((3, 3)), (2, 3));
+        assert_eq!(Direction8::UpRight.delta(), (1, -1));
+        assert_eq!(Direction8::UpRight.delta_y_up(), (1, 1));
+        assert_eq!(
+            Direction8::from(Direction::Left).delta(),
+            Direction::Left.delta()
+        );
+        for dir in HexDirection::ALL {
+            let (q, r, s) = dir.delta();
+            assert_eq!(q + r + s, 0);
+        }
+    }
+
+    #[test]
+    fn parsing_common_encodings() {
+        for (chars, expected) in [
+            ("UN^3", Direction::Up),
+            ("RE>0", Direction::Right),
+            ("DSv1", Direction::Down),
+            ("LW<2", Direction::Left),
+        ] {
+            for c in chars.chars() {
+                assert_eq!(Direction::try_from(c), Ok(expected));
+            }
+        }
+        assert_eq!(Direction::try_from('x'), Err('x'));
+        assert_eq!(Turn::from_char('L'), Some(Turn::Left));
+        assert_eq!(HexDirection::from_name("SE"), Some(HexDirection::SouthEast));
+        assert_eq!(Direction::Left.to_string(), "<");
+    }
+
+    #[test]
+    fn rotating_in_space() {
+        assert_eq!(Direction3::PosX.rotate(Direction3::PosZ), Direction3::PosY);
+        assert_eq!(Direction3::PosY.rotate(Direction3::PosZ), Direction3::NegX);
+        assert_eq!(Direction3::PosX.rotate(Direction3::NegZ), Direction3::NegY);
+        assert_eq!(Direction3::PosZ.rotate(Direction3::PosZ), Direction3::PosZ);
+        for dir in Direction3::ALL {
+            let axis = Direction3::PosY;
+            let full_turn = (0..4).fold(dir, |d, _| d.rotate(axis));
+            assert_eq!(full_turn, dir);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::{fmt, path::PathBuf};
+
+/// Why a puzzle input couldn't be solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The input doesn't have the expected format. `line` and `column` are 1-based.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The input is well-formed but doesn't describe a puzzle that can be solved.
+    Invalid(String),
+    /// There is no input file where one was expected.
+    Missing { path: PathBuf },
+    /// The input file exists but couldn't be read.
+    Unreadable { path: PathBuf, message: String },
+}
+
+impl Error {
+    /// A parse error at the start of `remaining`, which must be a suffix of `input`.
+    pub fn at(input: &str, remaining: &str, message: impl Into<String>) -> Error {
+        let consumed = &input[..input.len() - remaining.len()];
+        let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);
+        Error::Parse {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+            message: message.into(),
+        }
+    }
+
+    /// A parse error at the start of `remaining`, describing what was found there.
+    pub fn unexpected(input: &str, remaining: &str) -> Error {
+        let found = remaining.lines().next().unwrap_or_default();
+        let message = match found {
+            "" if remaining.is_empty() => "unexpected end of input".to_string(),
+            "" => "unexpected end of line".to_string(),
+            found => format!("unexpected {found:?}"),
+        };
+        Error::at(input, remaining, message)
+    }
+
+    pub fn invalid(message: impl Into<String>) -> Error {
+        Error::Invalid(message.into())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "line {line}, column {column}: {message}"),
+            Error::Invalid(message) => write!(f, "invalid input: {message}"),
+            Error::Missing { path } => write!(f, "missing input: expected {}", path.display()),
+            Error::Unreadable { path, message } => {
+                write!(f, "cannot read {}: {message}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_parse_errors() {
+        let input = "1 2\n3 x4\n";
+        assert_eq!(
+            Error::unexpected(input, &input[6..]).to_string(),
+            "line 2, column 3: unexpected \"x4\""
+        );
+        assert_eq!(
+            Error::unexpected(input, &input[3..]).to_string(),
+            "line 1, column 4: unexpected end of line"
+        );
+        assert_eq!(
+            Error::unexpected(input, "").to_string(),
+            "line 3, column 1: unexpected end of input"
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{number_theory::gcd, Direction};
+
+/// Where a point lies relative to a polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointLocation {
+    Inside,
+    /// On one of the edges or vertices.
+    Boundary,
+    Outside,
+}
+
+/// Pairs of consecutive vertices, including the edge that closes the polygon.
+fn edges(vertices: &[(i64, i64)]) -> impl Iterator<Item = (&(i64, i64), &(i64, i64))> {
+    vertices.iter().zip(vertices.iter().cycle().skip(1))
+}
+
+/// Twice the area enclosed by `vertices`, using the shoelace formula
+/// (<https://en.wikipedia.org/wiki/Shoelace_formula>). Doubling keeps the result
+/// exact for integer coordinates. The polygon is closed implicitly, so the last
+/// vertex does not have to repeat the first, and either winding order works.
+pub fn double_area(vertices: &[(i64, i64)]) -> i64 {
+    edges(vertices)
+        .map(|((x1, y1), (x2, y2))| x1 * y2 - x2 * y1)
+        .sum::<i64>()
+        .abs()
+}
+
+/// Number of lattice points on the edges of the polygon.
+pub fn boundary_points(vertices: &[(i64, i64)]) -> i64 {
+    edges(vertices)
+        .map(|((x1, y1), (x2, y2))| gcd(x1.abs_diff(*x2), y1.abs_diff(*y2)) as i64)
+        .sum()
+}
+
+/// Number of lattice points strictly inside the polygon, using Pick's theorem
+/// (<https://en.wikipedia.org/wiki/Pick%27s_theorem>). The polygon must be simple.
+pub fn interior_points(vertices: &[(i64, i64)]) -> i64 {
+    (double_area(vertices) - boundary_points(vertices) + 2) / 2
+}
+
+/// Number of lattice points inside or on the boundary of the polygon. When every
+/// lattice point stands for a unit square, as in a dug out trench, this is the
+/// number of squares covered.
+pub fn lattice_points(vertices: &[(i64, i64)]) -> i64 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+/// The corners visited when following `path` from `start`, with `y` growing
+/// downwards. The end point is left out when the path returns to `start`.
+pub fn vertices_from_path(
+    start: (i64, i64),
+    path: impl IntoIterator<Item = (Direction, i64)>,
+) -> Vec<(i64, i64)> {
+    let mut vertices = vec![start];
+    let (mut x, mut y) = start;
+    for (dir, steps) in path {
+        let (dx, dy) = dir.delta();
+        (x, y) = (x + dx as i64 * steps, y + dy as i64 * steps);
+        vertices.push((x, y));
+    }
+    if vertices.len() > 1 && vertices.last() == Some(&start) {
+        vertices.pop();
+    }
+    vertices
+}
+
+/// Locates `point` relative to the polygon. Points on an edge or vertex are
+/// reported as [`PointLocation::Boundary`]; all others are classified by casting a
+/// ray towards positive `x` and counting the edges it crosses.
+pub fn locate(point: (i64, i64), vertices: &[(i64, i64)]) -> PointLocation {
+    let (px, py) = point;
+    let mut inside = false;
+    for (&(x1, y1), &(x2, y2)) in edges(vertices) {
+        let cross = (x2 - x1) * (py - y1) - (y2 - y1) * (px - x1);
+        if cross == 0
+            && (x1.min(x2)..=x1.max(x2)).contains(&px)
+            && (y1.min(y2)..=y1.max(y2)).contains(&py)
+        {
+            return PointLocation::Boundary;
+        }
+        // Counting an edge only when it straddles the ray from one side
+        // (half-open in y) avoids counting a vertex on the ray twice.
+        if (y1 > py) != (y2 > py) {
+            // Sign of the crossing's x position relative to px, without dividing.
+            let crosses_right = if y2 > y1 { cross > 0 } else { cross < 0 };
+            if crosses_right {
+                inside = !inside;
+            }
+        }
+    }
+    if inside {
+        PointLocation::Inside
+    } else {
+        PointLocation::Outside
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Direction::*;
+
+    #[test]
+    fn area_and_lattice_points() {
+        let square = [(0, 0), (4, 0), (4, 4), (0, 4)];
+        assert_eq!(double_area(&square), 32);
+        assert_eq!(boundary_points(&square), 16);
+        assert_eq!(interior_points(&square), 9);
+        assert_eq!(lattice_points(&square), 25);
+
+        let mut reversed = square;
+        reversed.reverse();
+        assert_eq!(double_area(&reversed), 32);
+
+        let triangle = [(0, 0), (4, 0), (0, 2)];
+        assert_eq!(double_area(&triangle), 8);
+        assert_eq!(boundary_points(&triangle), 8);
+        assert_eq!(interior_points(&triangle), 1);
+    }
+
+    #[test]
+    fn vertices_from_run_length_path() {
+        let vertices = vertices_from_path((0, 0), [(Right, 2), (Down, 3), (Left, 2), (Up, 3)]);
+        assert_eq!(vertices, vec![(0, 0), (2, 0), (2, 3), (0, 3)]);
+        assert_eq!(lattice_points(&vertices), 12);
+
+        let open = vertices_from_path((1, 1), [(Up, 1)]);
+        assert_eq!(open, vec![(1, 1), (1, 0)]);
+    }
+
+    #[test]
+    fn point_in_polygon() {
+        // An L shape:
+        // +---+
+        // |   |
+        // |   +---+
+        // |       |
+        // +-------+
+        let l_shape = [(0, 0), (2, 0), (2, 2), (4, 2), (4, 4), (0, 4)];
+        assert_eq!(locate((1, 1), &l_shape), PointLocation::Inside);
+        assert_eq!(locate((3, 3), &l_shape), PointLocation::Inside);
+        assert_eq!(locate((3, 1), &l_shape), PointLocation::Outside);
+        assert_eq!(locate((5, 3), &l_shape), PointLocation::Outside);
+        assert_eq!(locate((-1, 2), &l_shape), PointLocation::Outside);
+        assert_eq!(locate((2, 1), &l_shape), PointLocation::Boundary);
+        assert_eq!(locate((4, 4), &l_shape), PointLocation::Boundary);
+        assert_eq!(locate((3, 2), &l_shape), PointLocation::Boundary);
+        // The ray passes through the vertices at y = 2.
+        assert_eq!(locate((1, 2), &l_shape), PointLocation::Inside);
+        assert_eq!(locate((-1, 4), &l_shape), PointLocation::Outside);
+    }
+}
@@ -0,0 +1,279 @@
+use std::ops::{Index, IndexMut};
+
+const NEIGHBORS_4: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const NEIGHBORS_8: [(i32, i32); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// A dense, row-major 2D grid addressed by `(x, y)` with `y` growing downwards.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "grid of {width}x{height} needs {} cells",
+            width * height
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses a rectangular block of text, mapping every character to a cell.
+    pub fn parse(input: &str, mut cell_fn: impl FnMut(char) -> T) -> Self {
+        let mut width = 0;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.len());
+        for line in input.lines() {
+            let before = cells.len();
+            cells.extend(line.chars().map(&mut cell_fn));
+            let line_width = cells.len() - before;
+            if height == 0 {
+                width = line_width;
+            }
+            assert_eq!(line_width, width, "line {height} has a different width");
+            height += 1;
+        }
+        Grid::new(width, height, cells)
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Same shape as [`crate::grid_bounds`]: `(max_x, max_y)`, both exclusive.
+    #[inline]
+    pub fn bounds(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    #[inline]
+    pub fn in_bounds(&self, (x, y): (i32, i32)) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    #[inline]
+    fn index_of(&self, p: (i32, i32)) -> Option<usize> {
+        self.in_bounds(p)
+            .then(|| p.1 as usize * self.width + p.0 as usize)
+    }
+
+    #[inline]
+    pub fn get(&self, p: (i32, i32)) -> Option<&T> {
+        self.index_of(p).map(|i| &self.cells[i])
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, p: (i32, i32)) -> Option<&mut T> {
+        self.index_of(p).map(|i| &mut self.cells[i])
+    }
+
+    /// Replaces the cell at `p`, returning the previous value if `p` is in bounds.
+    pub fn set(&mut self, p: (i32, i32), value: T) -> Option<T> {
+        self.get_mut(p).map(|cell| std::mem::replace(cell, value))
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width)
+            .take(self.height)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (i32, i32)> {
+        let width = self.width;
+        (0..self.width * self.height).map(move |i| ((i % width) as i32, (i / width) as i32))
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = ((i32, i32), &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ((i32, i32), &mut T)> + '_ {
+        self.positions().zip(self.cells.iter_mut())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> + '_ {
+        self.cells.iter()
+    }
+
+    /// Position of the first cell (in row-major order) matching `pred`.
+    pub fn find(&self, mut pred: impl FnMut(&T) -> bool) -> Option<(i32, i32)> {
+        self.iter().find(|(_, v)| pred(v)).map(|(p, _)| p)
+    }
+
+    /// In-bounds orthogonal neighbours of `p`, clockwise starting from up.
+    pub fn neighbors4(&self, p: (i32, i32)) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.offsets(p, &NEIGHBORS_4)
+    }
+
+    /// In-bounds orthogonal and diagonal neighbours of `p`, clockwise starting from up-left.
+    pub fn neighbors8(&self, p: (i32, i32)) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.offsets(p, &NEIGHBORS_8)
+    }
+
+    fn offsets<'a>(
+        &'a self,
+        (x, y): (i32, i32),
+        deltas: &'a [(i32, i32)],
+    ) -> impl Iterator<Item = (i32, i32)> + 'a {
+        deltas
+            .iter()
+            .map(move |(dx, dy)| (x + dx, y + dy))
+            .filter(|p| self.in_bounds(*p))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T> Index<(i32, i32)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: (i32, i32)) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<(i32, i32)> for Grid<T> {
+    fn index_mut(&mut self, p: (i32, i32)) -> &mut T {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside of the grid"))
+    }
+}
+
+impl std::str::FromStr for Grid<char> {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Grid::parse(s, |c| c))
+    }
+}
+
+impl std::fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for c in row {
+                write!(f, "{c}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "123
+456";
+
+    #[test]
+    fn parse_and_get() {
+        let grid = Grid::parse(INPUT, |c| c.to_digit(10).unwrap());
+        assert_eq!(grid.bounds(), (3, 2));
+        assert_eq!(grid.get((0, 0)), Some(&1));
+        assert_eq!(grid.get((2, 1)), Some(&6));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((-1, 0)), None);
+        assert_eq!(grid[(1, 1)], 5);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid: Grid<char> = INPUT.parse().unwrap();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![['1', '2', '3'], ['4', '5', '6']]
+        );
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["14", "25", "36"]
+        );
+        assert_eq!(grid.to_string(), INPUT);
+    }
+
+    #[test]
+    fn positions_are_row_major() {
+        let grid: Grid<char> = INPUT.parse().unwrap();
+        assert_eq!(
+            grid.positions().collect::<Vec<_>>(),
+            vec![(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)]
+        );
+        assert_eq!(grid.find(|c| *c == '5'), Some((1, 1)));
+    }
+
+    #[test]
+    fn neighbors_stay_in_bounds() {
+        let grid: Grid<char> = INPUT.parse().unwrap();
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbors8((1, 0)).collect::<Vec<_>>(),
+            vec![(2, 0), (2, 1), (1, 1), (0, 1), (0, 0)]
+        );
+    }
+
+    #[test]
+    fn set_and_map() {
+        let mut grid: Grid<char> = INPUT.parse().unwrap();
+        assert_eq!(grid.set((0, 0), '9'), Some('1'));
+        assert_eq!(grid.set((5, 5), '9'), None);
+        let digits = grid.map(|c| c.to_digit(10).unwrap());
+        assert_eq!(digits.values().sum::<u32>(), 9 + 2 + 3 + 4 + 5 + 6);
+    }
+}
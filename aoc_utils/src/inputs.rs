@@ -0,0 +1,169 @@
+//! Puzzle inputs, kept in one place outside of the day crates so that finding
+//! them doesn't depend on the directory a binary, test or benchmark runs from.
+//!
+//! A store has a directory per year. The input of a day is named after the day,
+//! with its examples beside it:
+//!
+//! ```text
+//! inputs/
+//!   2023/
+//!     17.txt
+//!     17.example.txt
+//!     17.example-2.txt
+//! ```
+//!
+//! Files are read once per process and handed out as `&'static str`, like
+//! `include_str!` would, with `\r\n` line endings turned into `\n` and trailing
+//! whitespace removed.
+
+use std::{
+    collections::BTreeMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError},
+};
+
+use crate::{parse::normalize, Error};
+
+/// Environment variable naming the store to use instead of `inputs/` at the root
+/// of the workspace.
+pub const INPUTS_VAR: &str = "AOC_INPUTS";
+
+static CACHE: Mutex<BTreeMap<PathBuf, &'static str>> = Mutex::new(BTreeMap::new());
+
+/// A directory of puzzle inputs laid out by year and day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputStore {
+    root: PathBuf,
+}
+
+impl InputStore {
+    pub fn new(root: impl Into<PathBuf>) -> InputStore {
+        InputStore { root: root.into() }
+    }
+
+    /// The store named by [`INPUTS_VAR`], or else `inputs/` at the root of the
+    /// workspace.
+    pub fn from_env() -> InputStore {
+        match env::var_os(INPUTS_VAR) {
+            Some(root) => InputStore::new(root),
+            None => {
+                let utils = Path::new(env!("CARGO_MANIFEST_DIR"));
+                InputStore::new(utils.parent().unwrap_or(utils).join("inputs"))
+            }
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Where the input of a day is expected, e.g. `inputs/2023/17.txt`.
+    pub fn input_path(&self, year: u16, day: u8) -> PathBuf {
+        self.year_directory(year).join(format!("{day:02}.txt"))
+    }
+
+    /// Where the `n`th example of a day is expected, counting from 1. The first
+    /// example is `17.example.txt`, later ones `17.example-2.txt` and so on.
+    pub fn example_path(&self, year: u16, day: u8, n: u8) -> PathBuf {
+        let name = match n {
+            0 | 1 => format!("{day:02}.example.txt"),
+            n => format!("{day:02}.example-{n}.txt"),
+        };
+        self.year_directory(year).join(name)
+    }
+
+    pub fn input(&self, year: u16, day: u8) -> Result<&'static str, Error> {
+        read(&self.input_path(year, day))
+    }
+
+    pub fn example(&self, year: u16, day: u8, n: u8) -> Result<&'static str, Error> {
+        read(&self.example_path(year, day, n))
+    }
+
+    fn year_directory(&self, year: u16) -> PathBuf {
+        self.root.join(year.to_string())
+    }
+}
+
+/// The input of a day from the default store.
+pub fn input(year: u16, day: u8) -> Result<&'static str, Error> {
+    InputStore::from_env().input(year, day)
+}
+
+/// The `n`th example of a day from the default store, counting from 1.
+pub fn example(year: u16, day: u8, n: u8) -> Result<&'static str, Error> {
+    InputStore::from_env().example(year, day, n)
+}
+
+/// The normalised contents of the file at `path`, which is only read the first
+/// time it is asked for.
+pub fn read(path: &Path) -> Result<&'static str, Error> {
+    let mut cache = CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(contents) = cache.get(path) {
+        return Ok(contents);
+    }
+    let contents = fs::read_to_string(path).map_err(|err| match err.kind() {
+        io::ErrorKind::NotFound => Error::Missing {
+            path: path.to_path_buf(),
+        },
+        _ => Error::Unreadable {
+            path: path.to_path_buf(),
+            message: err.to_string(),
+        },
+    })?;
+    let contents: &'static str = Box::leak(normalize(&contents).into_owned().into_boxed_str());
+    cache.insert(path.to_path_buf(), contents);
+    Ok(contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(name: &str) -> InputStore {
+        let root = env::temp_dir().join("aoc-inputs-test").join(name);
+        fs::create_dir_all(root.join("2023")).unwrap();
+        InputStore::new(root)
+    }
+
+    #[test]
+    fn lays_out_inputs_by_year_and_day() {
+        let store = InputStore::new("inputs");
+        assert_eq!(store.input_path(2023, 7), Path::new("inputs/2023/07.txt"));
+        assert_eq!(
+            store.example_path(2022, 22, 1),
+            Path::new("inputs/2022/22.example.txt")
+        );
+        assert_eq!(
+            store.example_path(2022, 22, 2),
+            Path::new("inputs/2022/22.example-2.txt")
+        );
+    }
+
+    #[test]
+    fn normalises_and_caches_inputs() {
+        let store = store("normalise");
+        fs::write(store.input_path(2023, 1), "1abc2  \r\npqr3stu8vwx\r\n\r\n").unwrap();
+        let input = store.input(2023, 1).unwrap();
+        assert_eq!(input, "1abc2\npqr3stu8vwx");
+
+        // Later changes to the file aren't seen by this process.
+        fs::write(store.input_path(2023, 1), "changed").unwrap();
+        assert!(std::ptr::eq(store.input(2023, 1).unwrap(), input));
+    }
+
+    #[test]
+    fn names_the_missing_path() {
+        let store = store("missing");
+        let path = store.example_path(2023, 2, 3);
+        assert_eq!(
+            store.example(2023, 2, 3),
+            Err(Error::Missing { path: path.clone() })
+        );
+        assert_eq!(
+            store.example(2023, 2, 3).unwrap_err().to_string(),
+            format!("missing input: expected {}", path.display())
+        );
+    }
+}
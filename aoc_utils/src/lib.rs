@@ -1,5 +1,31 @@
 use std::collections::VecDeque;
 
+mod circular;
+mod cycle;
+mod direction;
+mod error;
+pub mod geometry;
+mod grid;
+pub mod inputs;
+pub mod number_theory;
+pub mod ocr;
+pub mod parse;
+mod ranges;
+pub mod render;
+mod shortest_path;
+mod solution;
+mod tracked;
+
+pub use circular::CircularSequence;
+pub use cycle::{find_cycle, find_cycle_by, Cycle};
+pub use direction::{Direction, Direction3, Direction8, HexDirection, Turn};
+pub use error::Error;
+pub use grid::Grid;
+pub use ranges::{HyperRect, RangeBound, RangeSet};
+pub use shortest_path::{astar, astar_with_path, dijkstra, dijkstra_with_path};
+pub use solution::{Answer, Entry, ParsedInput, Part, Registry, Solution};
+pub use tracked::{tracked_bfs, tracked_dfs, SearchResult};
+
 trait TraveralStore<S>: Extend<S> {
     fn next_state(&mut self) -> Option<S>;
 }
@@ -17,20 +43,25 @@ impl<S> TraveralStore<S> for VecDeque<S> {
 }
 
 #[inline]
-fn traverser<S, F, G>(mut ts: impl TraveralStore<S>, mut next_states_fn: F, mut interrupt_fn: G)
+fn traverser<S, F, G>(
+    mut ts: impl TraveralStore<S>,
+    mut next_states_fn: F,
+    mut interrupt_fn: G,
+) -> Option<S>
 where
     F: FnMut(S) -> Option<Vec<S>>,
     G: FnMut(&S) -> bool,
 {
     while let Some(state) = ts.next_state() {
         if interrupt_fn(&state) {
-            return;
+            return Some(state);
         }
 
         if let Some(next_states) = next_states_fn(state) {
             ts.extend(next_states);
         }
     }
+    None
 }
 
 #[inline]
@@ -54,7 +85,7 @@ pub fn interruptable_bfs<S, F, G>(
         VecDeque::from_iter(initial_states),
         next_states_fn,
         terminal_state_fn,
-    )
+    );
 }
 
 #[inline]
@@ -78,25 +109,7 @@ pub fn interruptable_dfs<S, F, G>(
         Vec::from_iter(initial_states),
         next_states_fn,
         terminal_state_fn,
-    )
-}
-
-#[inline]
-pub fn lcm(nums: &[usize]) -> usize {
-    if nums.len() == 1 {
-        return nums[0];
-    }
-    let a = nums[0];
-    let b = lcm(&nums[1..]);
-    a * b / gcd_of_two_numbers(a, b)
-}
-
-#[inline]
-pub fn gcd_of_two_numbers(a: usize, b: usize) -> usize {
-    if b == 0 {
-        return a;
-    }
-    gcd_of_two_numbers(b, a % b)
+    );
 }
 
 #[inline]
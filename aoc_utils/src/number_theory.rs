@@ -0,0 +1,154 @@
+use std::ops::{Div, Rem};
+
+/// Unsigned integer types usable with [`gcd`] and [`lcm`].
+pub trait Unsigned: Copy + Eq + Rem<Output = Self> + Div<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Unsigned for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                #[inline]
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+
+#[inline]
+pub fn gcd<T: Unsigned>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple of `a` and `b`, or `None` if it does not fit in `T`.
+#[inline]
+pub fn lcm<T: Unsigned>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Least common multiple of all `nums` (1 if there are none), or `None` on overflow.
+#[inline]
+pub fn lcm_all<T: Unsigned>(nums: impl IntoIterator<Item = T>) -> Option<T> {
+    nums.into_iter().try_fold(T::ONE, |acc, n| lcm(acc, n))
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y == g == gcd(a, b)`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1, 0);
+    let (mut old_t, mut t) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+        (old_t, t) = (t, old_t - q * t);
+    }
+    if old_r < 0 {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// The `x` in `0..m` with `a * x ≡ 1 (mod m)`, if `a` and `m` are coprime.
+pub fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// `base.pow(exp) % modulus` without overflowing intermediate results.
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1u128;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+/// Solves the system `x ≡ residue (mod modulus)` for every `(residue, modulus)` pair.
+/// The moduli don't have to be coprime. Returns the smallest non-negative `x`
+/// together with the modulus of the combined congruence (the lcm of all moduli),
+/// or `None` if the congruences contradict each other.
+pub fn crt(congruences: impl IntoIterator<Item = (i128, i128)>) -> Option<(i128, i128)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(x, m), (residue, modulus)| {
+            let residue = residue.rem_euclid(modulus);
+            let (g, p, _) = extended_gcd(m, modulus);
+            if (residue - x) % g != 0 {
+                return None;
+            }
+            let combined = m / g * modulus;
+            // x + m * k ≡ residue (mod modulus)  =>  k ≡ (residue - x) / g * p (mod modulus / g)
+            let k = ((residue - x) / g % (modulus / g)) * p % (modulus / g);
+            Some(((x + m * k).rem_euclid(combined), combined))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(0u64, 7), 7);
+        assert_eq!(lcm(4usize, 6), Some(12));
+        assert_eq!(lcm_all([2u64, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all(Vec::<u64>::new()), Some(1));
+        assert_eq!(lcm_all([u64::MAX, u64::MAX - 1]), None);
+    }
+
+    #[test]
+    fn extended_gcd_and_inverse() {
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn modular_exponentiation() {
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, 1_000_000_007), 254_368_884);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+    }
+
+    #[test]
+    fn chinese_remainder_theorem() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Non-coprime moduli.
+        assert_eq!(crt([(3, 4), (1, 6)]), Some((7, 12)));
+        assert_eq!(crt([(0, 4), (1, 6)]), None);
+        assert_eq!(crt([(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt([]), Some((0, 1)));
+    }
+}
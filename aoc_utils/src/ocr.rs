@@ -0,0 +1,292 @@
+//! Reading the block letters that some puzzles draw instead of giving an answer.
+//!
+//! Two fonts show up over the years: a small one that is 6 pixels high and
+//! mostly 4 wide, drawn every 5 columns, and a large one from 2018 that is 10
+//! pixels high and 6 wide, drawn every 8 columns. `#` and `█` are lit pixels,
+//! `.` and spaces are dark ones.
+
+use crate::Error;
+
+/// The fonts letters are drawn in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Font {
+    /// 4x6 letters, 5 columns apart.
+    Small,
+    /// 6x10 letters, 8 columns apart.
+    Large,
+}
+
+impl Font {
+    pub fn height(self) -> usize {
+        match self {
+            Font::Small => 6,
+            Font::Large => 10,
+        }
+    }
+
+    /// How many columns each letter takes up, including the gap after it.
+    pub fn stride(self) -> usize {
+        match self {
+            Font::Small => 5,
+            Font::Large => 8,
+        }
+    }
+
+    fn glyphs(self) -> &'static [(char, &'static str)] {
+        match self {
+            Font::Small => SMALL,
+            Font::Large => LARGE,
+        }
+    }
+}
+
+/// Reads the letters of `screen`, picking the font by how many rows it has.
+pub fn recognize(screen: &str) -> Result<String, Error> {
+    match screen.lines().count() {
+        6 => recognize_with(Font::Small, screen),
+        10 => recognize_with(Font::Large, screen),
+        rows => Err(Error::invalid(format!(
+            "expected letters 6 or 10 rows high, got {rows} rows"
+        ))),
+    }
+}
+
+/// Reads the letters of `screen`, which starts with a letter in its first
+/// column. Gaps the width of a letter are skipped.
+pub fn recognize_with(font: Font, screen: &str) -> Result<String, Error> {
+    let rows = screen
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(x, c)| match c {
+                    '#' | '█' => Ok(true),
+                    '.' | ' ' => Ok(false),
+                    c => Err(Error::invalid(format!(
+                        "unexpected {c:?} at row {}, column {}",
+                        y + 1,
+                        x + 1
+                    ))),
+                })
+                .collect::<Result<Vec<bool>, Error>>()
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    if rows.len() != font.height() {
+        return Err(Error::invalid(format!(
+            "expected letters {} rows high, got {} rows",
+            font.height(),
+            rows.len()
+        )));
+    }
+
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let lit = |x: usize| rows.iter().any(|row| row.get(x) == Some(&true));
+    let mut letters = String::new();
+    for start in (0..width).step_by(font.stride()) {
+        let end = (start + font.stride()).min(width);
+        // Narrow letters like `I` don't fill their columns, so only the lit
+        // ones are compared.
+        let Some(left) = (start..end).find(|&x| lit(x)) else {
+            continue;
+        };
+        let right = (start..end).rfind(|&x| lit(x)).unwrap();
+        let glyph = rows
+            .iter()
+            .map(|row| {
+                (left..=right)
+                    .map(|x| match row.get(x) {
+                        Some(true) => '#',
+                        _ => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let letter = font
+            .glyphs()
+            .iter()
+            .find(|(_, known)| *known == glyph)
+            .map(|(letter, _)| *letter)
+            .ok_or_else(|| {
+                Error::invalid(format!("unknown letter at column {}:\n{glyph}", start + 1))
+            })?;
+        letters.push(letter);
+    }
+
+    if letters.is_empty() {
+        return Err(Error::invalid("there are no letters on the screen"));
+    }
+    Ok(letters)
+}
+
+const SMALL: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const LARGE: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glyphs_fit_their_font() {
+        for font in [Font::Small, Font::Large] {
+            for (letter, glyph) in font.glyphs() {
+                let rows: Vec<&str> = glyph.lines().collect();
+                assert_eq!(rows.len(), font.height(), "{letter}");
+                assert!(rows.iter().all(|row| row.len() == rows[0].len()));
+                assert!(rows[0].len() <= font.stride(), "{letter}");
+                // Glyphs are compared without their dark columns on either side.
+                assert!(rows.iter().any(|row| row.starts_with('#')), "{letter}");
+                assert!(rows.iter().any(|row| row.ends_with('#')), "{letter}");
+            }
+        }
+    }
+
+    #[test]
+    fn reads_small_letters() {
+        let screen = "\
+###..####.#..#.###..#..#.###..#..#.#..#.
+#..#.#....#.#..#..#.#..#.#..#.#.#..#..#.
+#..#.###..##...#..#.####.###..##...####.
+###..#....#.#..###..#..#.#..#.#.#..#..#.
+#....#....#.#..#....#..#.#..#.#.#..#..#.
+#....####.#..#.#....#..#.###..#..#.#..#.";
+        assert_eq!(recognize(screen).unwrap(), "PEKPHBKH");
+
+        // Narrow letters sit anywhere in their columns.
+        let screen = "\
+.###.#...#
+..#..#...#
+..#...#.#.
+..#....#..
+..#....#..
+.###...#..";
+        assert_eq!(recognize_with(Font::Small, screen).unwrap(), "IY");
+    }
+
+    #[test]
+    fn reads_large_letters() {
+        let screen = "\
+#....#..#....#
+#....#..#....#
+.#..#...#....#
+.#..#...#....#
+..##....######
+..##....#....#
+.#..#...#....#
+.#..#...#....#
+#....#..#....#
+#....#..#....#";
+        assert_eq!(recognize(screen).unwrap(), "XH");
+        assert_eq!(
+            recognize(&screen.replace('#', "█").replace('.', " ")).unwrap(),
+            "XH"
+        );
+    }
+
+    #[test]
+    fn reports_unknown_letters() {
+        let screen = "##..\n##..\n....\n....\n....\n....";
+        assert_eq!(
+            recognize(screen),
+            Err(Error::invalid(
+                "unknown letter at column 1:\n##\n##\n..\n..\n..\n.."
+            ))
+        );
+        assert_eq!(
+            recognize("#\n#"),
+            Err(Error::invalid(
+                "expected letters 6 or 10 rows high, got 2 rows"
+            ))
+        );
+        assert_eq!(
+            recognize("......\n".repeat(6).trim()),
+            Err(Error::invalid("there are no letters on the screen"))
+        );
+        assert_eq!(
+            recognize_with(Font::Small, "#x\n#\n#\n#\n#\n#"),
+            Err(Error::invalid("unexpected 'x' at row 1, column 2"))
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use std::{borrow::Cow, collections::HashMap, fmt::Display, str::FromStr};
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{
+        alphanumeric1, anychar, digit1, line_ending, multispace0, none_of, one_of, space0, space1,
+    },
+    combinator::{all_consuming, map, map_opt, map_res, opt, recognize, verify},
+    error::Error,
+    multi::{many1, separated_list0, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated},
+    Finish, IResult, Parser,
+};
+
+use crate::Grid;
+
+/// Runs `parser` on the whole of `input`. Trailing whitespace, such as the final
+/// newline of a puzzle input, is allowed; anything else left over is an error
+/// pointing at where parsing stopped.
+pub fn parse_all<'a, O>(
+    parser: impl Parser<&'a str, O, Error<&'a str>>,
+    input: &'a str,
+) -> Result<O, crate::Error> {
+    all_consuming(terminated(parser, multispace0))(input)
+        .finish()
+        .map(|(_, output)| output)
+        .map_err(|err| crate::Error::unexpected(input, err.input))
+}
+
+/// `input` with `\r\n` line endings turned into `\n` and trailing whitespace
+/// removed from every line, for puzzles that are split up by hand rather than
+/// parsed with [`parse_all`]. Borrows `input` when it is already clean.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let clean = |line: &str| line.len() == line.trim_end().len();
+    if !input.contains('\r') && input.split('\n').all(clean) && !input.ends_with('\n') {
+        return Cow::Borrowed(input);
+    }
+    let lines = input.lines().map(str::trim_end).collect::<Vec<_>>();
+    Cow::Owned(lines.join("\n").trim_end().to_string())
+}
+
+/// Parses every line of `input` with `f`, for puzzles whose lines are simpler to
+/// pick apart by hand. Trailing whitespace is ignored and errors point at the start
+/// of the offending line.
+pub fn map_lines<'a, T, E: Display>(
+    input: &'a str,
+    mut f: impl FnMut(&'a str) -> Result<T, E>,
+) -> Result<Vec<T>, crate::Error> {
+    input
+        .trim_end()
+        .lines()
+        .map(|line| {
+            f(line.trim_end()).map_err(|err| {
+                let offset = line.as_ptr() as usize - input.as_ptr() as usize;
+                crate::Error::at(input, &input[offset..], err.to_string())
+            })
+        })
+        .collect()
+}
+
+/// A signed or unsigned integer of any type that can be parsed from a string.
+pub fn integer<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// Separates list items on a single line: a comma with optional spaces around it,
+/// or just spaces.
+fn list_separator(input: &str) -> IResult<&str, &str> {
+    alt((delimited(space0, tag(","), space0), space1))(input)
+}
+
+/// Integers on a single line, separated by commas and/or spaces.
+pub fn integer_list<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    separated_list1(list_separator, integer)(input)
+}
+
+/// One [`integer_list`] per line.
+pub fn integer_lines<T: FromStr>(input: &str) -> IResult<&str, Vec<Vec<T>>> {
+    lines(integer_list)(input)
+}
+
+/// Rows of whitespace-separated numbers, where rows may be padded with spaces to
+/// align the columns.
+pub fn number_grid<T: FromStr>(input: &str) -> IResult<&str, Vec<Vec<T>>> {
+    lines(delimited(space0, separated_list1(space1, integer), space0))(input)
+}
+
+/// A line ending, `\n` or `\r\n`, after any trailing spaces.
+fn line_break(input: &str) -> IResult<&str, &str> {
+    preceded(space0, line_ending)(input)
+}
+
+/// The end of a line followed by an empty one, which separates blocks of lines.
+pub fn blank_line(input: &str) -> IResult<&str, (&str, &str)> {
+    pair(line_break, line_break)(input)
+}
+
+/// One or more lines, each parsed by `parser`.
+pub fn lines<'a, O>(
+    parser: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(line_break, parser)
+}
+
+/// One or more blocks separated by blank lines, each parsed by `parser`.
+pub fn blocks<'a, O>(
+    parser: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(blank_line, parser)
+}
+
+/// A key followed by `separator` and a list of values separated by commas and/or
+/// spaces, such as `a: b c d` or `a -> b, c, d`. The list may be empty.
+pub fn key_values<'a, K, S, V>(
+    key: impl Parser<&'a str, K, Error<&'a str>>,
+    separator: impl Parser<&'a str, S, Error<&'a str>>,
+    value: impl Parser<&'a str, V, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, Vec<V>)> {
+    separated_pair(
+        key,
+        delimited(space0, separator, space0),
+        separated_list0(list_separator, value),
+    )
+}
+
+/// Lines of alphanumeric names, each followed by `:` or `->` and its neighbours.
+pub fn adjacency_list(input: &str) -> IResult<&str, Vec<(&str, Vec<&str>)>> {
+    lines(key_values(
+        alphanumeric1,
+        alt((tag(":"), tag("->"))),
+        alphanumeric1,
+    ))(input)
+}
+
+/// Rows of cells parsed by `cell`. Fails unless all rows are equally wide.
+pub fn grid<'a, T>(
+    cell: impl Parser<&'a str, T, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Grid<T>> {
+    map(
+        verify(lines(many1(cell)), |rows: &Vec<Vec<T>>| {
+            rows.iter().all(|row| row.len() == rows[0].len())
+        }),
+        |rows| {
+            let (width, height) = (rows[0].len(), rows.len());
+            Grid::new(width, height, rows.into_iter().flatten().collect())
+        },
+    )
+}
+
+/// A rectangle of characters.
+pub fn char_grid(input: &str) -> IResult<&str, Grid<char>> {
+    grid(none_of("\r\n"))(input)
+}
+
+/// A rectangle of single decimal digits, such as a height map.
+pub fn digit_grid(input: &str) -> IResult<&str, Grid<u32>> {
+    grid(map_opt(anychar, |c| c.to_digit(10)))(input)
+}
+
+/// The positions of the characters in a rectangle for which `cell` returns a value,
+/// for puzzles where most of the grid is empty space.
+pub fn sparse_grid<'a, T>(
+    mut cell: impl FnMut(char) -> Option<T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, HashMap<(i32, i32), T>> {
+    move |input| {
+        let (input, chars) = char_grid(input)?;
+        let cells = chars
+            .positions()
+            .filter_map(|p| cell(chars[p]).map(|v| (p, v)))
+            .collect();
+        Ok((input, cells))
+    }
+}
+
+/// `parser` preceded by `prefix` and optional spaces, as in `Card   3`.
+pub fn labelled<'a, O>(
+    prefix: &'a str,
+    parser: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
+    preceded(pair(tag(prefix), space0), parser)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_and_lists() {
+        assert_eq!(integer::<i32>("-12 rest"), Ok((" rest", -12)));
+        assert_eq!(integer::<u64>("+7"), Ok(("", 7)));
+        assert!(integer::<u8>("300").is_err());
+        assert_eq!(
+            integer_lines::<i64>("0 3 -6\n1,2, 3"),
+            Ok(("", vec![vec![0, 3, -6], vec![1, 2, 3]]))
+        );
+        assert_eq!(
+            number_grid::<u32>(" 1  20\n30   4 "),
+            Ok(("", vec![vec![1, 20], vec![30, 4]]))
+        );
+        // A separator that isn't followed by another number is left unconsumed.
+        assert_eq!(integer_list::<u32>("1 2 | 3"), Ok((" | 3", vec![1, 2])));
+    }
+
+    #[test]
+    fn parse_all_rejects_leftovers() {
+        assert_eq!(
+            parse_all(integer_lines::<u32>, "1 2\n3\n"),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+        assert_eq!(
+            parse_all(integer_lines::<u32>, "1 2\n3 x\n")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 3: unexpected \"x\""
+        );
+        assert!(parse_all(integer::<u32>, "").is_err());
+    }
+
+    #[test]
+    fn tolerates_crlf_and_trailing_whitespace() {
+        assert_eq!(
+            parse_all(blocks(integer_lines::<u32>), "1 2 \r\n3\r\n \r\n4\r\n\r\n"),
+            Ok(vec![vec![vec![1, 2], vec![3]], vec![vec![4]]])
+        );
+        assert_eq!(
+            map_lines("1 \r\n2\r\n\r\n", str::parse::<u8>),
+            Ok(vec![1, 2])
+        );
+        assert_eq!(
+            map_lines("1\n-2\n", str::parse::<u8>)
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 1: invalid digit found in string"
+        );
+        assert_eq!(normalize("a \r\n\r\nb\t\r\n"), "a\n\nb");
+        assert!(matches!(normalize("a\n\nb"), Cow::Borrowed("a\n\nb")));
+    }
+
+    #[test]
+    fn blocks_and_adjacency() {
+        assert_eq!(
+            parse_all(blocks(integer_lines::<u32>), "1\n2\n\n3\r\n\r\n4 5\n"),
+            Ok(vec![
+                vec![vec![1], vec![2]],
+                vec![vec![3]],
+                vec![vec![4, 5]]
+            ])
+        );
+        assert_eq!(
+            parse_all(adjacency_list, "jqt: rhn xhk\nbroadcaster -> a, b\nx:"),
+            Ok(vec![
+                ("jqt", vec!["rhn", "xhk"]),
+                ("broadcaster", vec!["a", "b"]),
+                ("x", vec![]),
+            ])
+        );
+        assert_eq!(
+            labelled("Card", integer::<u32>)("Card   3: 1"),
+            Ok((": 1", 3))
+        );
+    }
+
+    #[test]
+    fn character_grids() {
+        let grid = parse_all(char_grid, "#..\n.#.\n").unwrap();
+        assert_eq!(grid.bounds(), (3, 2));
+        assert_eq!(grid[(1, 1)], '#');
+        assert!(parse_all(char_grid, "#..\n.#\n").is_err());
+        assert_eq!(parse_all(digit_grid, "12\r\n34\r\n").unwrap()[(0, 1)], 3);
+        assert!(parse_all(digit_grid, "12\n3x").is_err());
+
+        let walls = parse_all(sparse_grid(|c| (c == '#').then_some(())), "#..\n.#.").unwrap();
+        assert_eq!(walls.len(), 2);
+        assert!(walls.contains_key(&(0, 0)) && walls.contains_key(&(1, 1)));
+    }
+}
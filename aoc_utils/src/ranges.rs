@@ -0,0 +1,305 @@
+use std::ops::{Add, Mul, Range, RangeInclusive, Sub};
+
+/// Integer types that [`RangeSet`] and [`HyperRect`] can be built from.
+pub trait RangeBound: Copy + Ord + Add<Output = Self> + Sub<Output = Self> + From<u8> {}
+
+impl<T> RangeBound for T where T: Copy + Ord + Add<Output = T> + Sub<Output = T> + From<u8> {}
+
+/// A set of integers stored as sorted, disjoint and non-adjacent half-open ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: RangeBound> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: RangeBound> RangeSet<T> {
+    pub fn new() -> Self {
+        RangeSet { ranges: vec![] }
+    }
+
+    /// Builds a set from ranges that may overlap, touch or be empty.
+    pub fn from_ranges(ranges: impl IntoIterator<Item = Range<T>>) -> Self {
+        let mut ranges = ranges
+            .into_iter()
+            .filter(|r| r.start < r.end)
+            .collect::<Vec<_>>();
+        ranges.sort_unstable_by_key(|r| r.start);
+
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        RangeSet { ranges: merged }
+    }
+
+    pub fn ranges(&self) -> impl DoubleEndedIterator<Item = &Range<T>> + '_ {
+        self.ranges.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Total number of integers in the set.
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::from(0), |total, r| total + (r.end - r.start))
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.ranges.last().map(|r| r.end - T::from(1))
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let idx = self.ranges.partition_point(|r| r.end <= *value);
+        self.ranges.get(idx).is_some_and(|r| r.contains(value))
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        *self = Self::from_ranges(self.ranges.drain(..).chain([range]));
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_ranges(self.ranges.iter().chain(other.ranges.iter()).cloned())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        RangeSet { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let mut others = other.ranges.iter().peekable();
+        for range in self.ranges.iter() {
+            let mut start = range.start;
+            while let Some(o) = others.peek() {
+                if o.end <= start {
+                    others.next();
+                    continue;
+                }
+                if o.start >= range.end {
+                    break;
+                }
+                if o.start > start {
+                    ranges.push(start..o.start);
+                }
+                start = o.end;
+                if o.end > range.end {
+                    break;
+                }
+                others.next();
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        RangeSet { ranges }
+    }
+
+    /// Splits into the values below `at` and the values at or above `at`.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let mut below = vec![];
+        let mut above = vec![];
+        for range in self.ranges.iter() {
+            if range.end <= at {
+                below.push(range.clone());
+            } else if range.start >= at {
+                above.push(range.clone());
+            } else {
+                below.push(range.start..at);
+                above.push(at..range.end);
+            }
+        }
+        (RangeSet { ranges: below }, RangeSet { ranges: above })
+    }
+
+    /// Moves every value so that `from` ends up at `to`. Works for unsigned types
+    /// as long as no value would drop below zero.
+    pub fn shift(&self, from: T, to: T) -> Self {
+        let ranges = self
+            .ranges
+            .iter()
+            .map(|r| {
+                if to >= from {
+                    r.start + (to - from)..r.end + (to - from)
+                } else {
+                    r.start - (from - to)..r.end - (from - to)
+                }
+            })
+            .collect();
+        RangeSet { ranges }
+    }
+}
+
+impl<T: RangeBound> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::from_ranges([range])
+    }
+}
+
+impl<T: RangeBound> From<RangeInclusive<T>> for RangeSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        Self::from_ranges([*range.start()..*range.end() + T::from(1)])
+    }
+}
+
+impl<T: RangeBound> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        Self::from_ranges(iter)
+    }
+}
+
+/// An axis-aligned box of half-open ranges in `N` dimensions.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HyperRect<T, const N: usize> {
+    ranges: [Range<T>; N],
+}
+
+impl<T: RangeBound, const N: usize> HyperRect<T, N> {
+    pub fn new(ranges: [Range<T>; N]) -> Self {
+        HyperRect { ranges }
+    }
+
+    pub fn ranges(&self) -> &[Range<T>; N] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.iter().any(|r| r.start >= r.end)
+    }
+
+    pub fn contains(&self, point: &[T; N]) -> bool {
+        self.ranges.iter().zip(point).all(|(r, p)| r.contains(p))
+    }
+
+    /// Number of integer points inside, computed in a (typically wider) type `V`.
+    pub fn volume<V>(&self) -> V
+    where
+        V: From<T> + From<u8> + Mul<Output = V>,
+    {
+        if self.is_empty() {
+            return V::from(0u8);
+        }
+        self.ranges
+            .iter()
+            .fold(V::from(1u8), |v, r| v * V::from(r.end - r.start))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut ranges = self.ranges.clone();
+        for (r, o) in ranges.iter_mut().zip(other.ranges.iter()) {
+            *r = r.start.max(o.start)..r.end.min(o.end);
+        }
+        let rect = HyperRect { ranges };
+        (!rect.is_empty()).then_some(rect)
+    }
+
+    /// Splits along `axis` into the part below `at` and the part at or above `at`.
+    /// Either side is `None` when it would be empty.
+    pub fn split_at(&self, axis: usize, at: T) -> (Option<Self>, Option<Self>) {
+        let range = &self.ranges[axis];
+        let mut below = self.clone();
+        below.ranges[axis] = range.start..at.min(range.end);
+        let mut above = self.clone();
+        above.ranges[axis] = at.max(range.start)..range.end;
+        (
+            (!below.is_empty()).then_some(below),
+            (!above.is_empty()).then_some(above),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[Range<i32>]) -> RangeSet<i32> {
+        RangeSet::from_ranges(ranges.iter().cloned())
+    }
+
+    #[test]
+    fn merges_overlapping_and_touching_ranges() {
+        let s = set(&[5..8, 0..2, 2..3, 7..10, 12..12]);
+        assert_eq!(s.ranges().cloned().collect::<Vec<_>>(), vec![0..3, 5..10]);
+        assert_eq!(s.len(), 8);
+        assert_eq!(s.min(), Some(0));
+        assert_eq!(s.max(), Some(9));
+        assert!(s.contains(&2));
+        assert!(!s.contains(&3));
+        assert!(s.contains(&9));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[0..10, 20..30]);
+        let b = RangeSet::from(5..25);
+        assert_eq!(a.union(&b), RangeSet::from(0..30));
+        assert_eq!(a.intersection(&b), set(&[5..10, 20..25]));
+        assert_eq!(a.difference(&b), set(&[0..5, 25..30]));
+        assert_eq!(b.difference(&a), RangeSet::from(10..20));
+        assert_eq!(
+            a.difference(&set(&[0..1, 3..4, 8..22])),
+            set(&[1..3, 4..8, 22..30])
+        );
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn split_and_shift() {
+        let a = set(&[0..10, 20..30]);
+        assert_eq!(a.split_at(5), (RangeSet::from(0..5), set(&[5..10, 20..30])));
+        assert_eq!(
+            a.split_at(15),
+            (RangeSet::from(0..10), RangeSet::from(20..30))
+        );
+        assert_eq!(a.shift(0, 100), set(&[100..110, 120..130]));
+        let unsigned: RangeSet<u64> = (50..=97).into();
+        assert_eq!(unsigned.shift(50, 52), RangeSet::from(52..100));
+        assert_eq!(unsigned.shift(50, 0), RangeSet::from(0..48));
+    }
+
+    #[test]
+    fn hyper_rect_split_and_volume() {
+        let rect: HyperRect<u32, 2> = HyperRect::new([1..11, 1..5]);
+        assert_eq!(rect.volume::<u64>(), 40);
+        let (below, above) = rect.split_at(0, 4);
+        assert_eq!(below, Some(HyperRect::new([1..4, 1..5])));
+        assert_eq!(above, Some(HyperRect::new([4..11, 1..5])));
+        assert_eq!(rect.split_at(1, 0), (None, Some(rect.clone())));
+        assert_eq!(
+            rect.intersection(&HyperRect::new([5..20, 0..2])),
+            Some(HyperRect::new([5..11, 1..2]))
+        );
+        assert_eq!(rect.intersection(&HyperRect::new([20..30, 0..2])), None);
+        assert!(rect.contains(&[10, 4]));
+        assert!(!rect.contains(&[11, 4]));
+    }
+}
@@ -0,0 +1,362 @@
+//! Pictures of grids and point sets, for looking at what a simulation is doing.
+//!
+//! [`ascii`] and [`ascii_points`] give text to print while debugging. An
+//! [`Image`] is a frame of RGB pixels that can be written as PPM or PNG, and an
+//! [`Animation`] strings frames together into a looping GIF.
+//!
+//! ```no_run
+//! use aoc_utils::{render::{Animation, Image}, Grid};
+//!
+//! let grid: Grid<char> = "#..\n.#.\n..#".parse().unwrap();
+//! println!("{}", aoc_utils::render::ascii(&grid, |&c| c));
+//!
+//! let palette = |c: &char| if *c == '#' { [255, 255, 255] } else { [0, 0, 0] };
+//! let frame = Image::from_grid(&grid, palette).scaled(8);
+//! frame.write_png("grid.png").unwrap();
+//!
+//! let mut animation = Animation::new(10);
+//! animation.push(frame);
+//! animation.write_gif("grid.gif").unwrap();
+//! ```
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::Grid;
+
+/// A colour as red, green and blue.
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+/// One line of text per row, with `cell_fn` picking the character of each cell.
+pub fn ascii<T>(grid: &Grid<T>, mut cell_fn: impl FnMut(&T) -> char) -> String {
+    grid.rows()
+        .map(|row| row.iter().map(&mut cell_fn).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The bounding box of `points` as text, with `on` where there is a point and
+/// `off` everywhere else. `y` grows downwards, like it does in a [`Grid`].
+pub fn ascii_points<P>(points: impl IntoIterator<Item = P>, on: char, off: char) -> String
+where
+    P: Into<(i64, i64)>,
+{
+    ascii(&point_grid(points), |&set| if set { on } else { off })
+}
+
+/// The bounding box of `points` as a grid of which cells hold a point.
+fn point_grid<P>(points: impl IntoIterator<Item = P>) -> Grid<bool>
+where
+    P: Into<(i64, i64)>,
+{
+    let points: Vec<(i64, i64)> = points.into_iter().map(Into::into).collect();
+    let Some(min_x) = points.iter().map(|p| p.0).min() else {
+        return Grid::new(0, 0, vec![]);
+    };
+    let min_y = points.iter().map(|p| p.1).min().unwrap();
+    let max_x = points.iter().map(|p| p.0).max().unwrap();
+    let max_y = points.iter().map(|p| p.1).max().unwrap();
+
+    let width = (max_x - min_x + 1) as usize;
+    let height = (max_y - min_y + 1) as usize;
+    let mut grid = Grid::filled(width, height, false);
+    for (x, y) in points {
+        grid[((x - min_x) as i32, (y - min_y) as i32)] = true;
+    }
+    grid
+}
+
+/// A frame of RGB pixels in row-major order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn filled(width: usize, height: usize, colour: Rgb) -> Image {
+        Image {
+            width,
+            height,
+            pixels: vec![colour; width * height],
+        }
+    }
+
+    /// One pixel per cell, coloured by `palette`.
+    pub fn from_grid<T>(grid: &Grid<T>, palette: impl FnMut(&T) -> Rgb) -> Image {
+        Image {
+            width: grid.width(),
+            height: grid.height(),
+            pixels: grid.values().map(palette).collect(),
+        }
+    }
+
+    /// The bounding box of `points`, with `on` where there is a point and `off`
+    /// everywhere else.
+    pub fn from_points<P>(points: impl IntoIterator<Item = P>, on: Rgb, off: Rgb) -> Image
+    where
+        P: Into<(i64, i64)>,
+    {
+        Image::from_grid(&point_grid(points), |&set| if set { on } else { off })
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<Rgb> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    /// Colours the pixel at `(x, y)`, ignoring positions outside of the image.
+    pub fn set(&mut self, (x, y): (usize, usize), colour: Rgb) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = colour;
+        }
+    }
+
+    /// Every pixel blown up into a `factor` by `factor` square, since one pixel
+    /// per cell is hard to make out.
+    pub fn scaled(&self, factor: usize) -> Image {
+        let width = self.width * factor;
+        let height = self.height * factor;
+        let pixels = (0..width * height)
+            .map(|i| self.pixels[(i / width / factor) * self.width + (i % width) / factor])
+            .collect();
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// The image as a binary PPM (`P6`), which most viewers open and which needs
+    /// no encoder.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.extend(self.pixels.iter().flatten());
+        ppm
+    }
+
+    pub fn write_ppm(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        out.write_all(&self.to_ppm())?;
+        out.flush()
+    }
+
+    pub fn to_png(&self) -> io::Result<Vec<u8>> {
+        let mut png = vec![];
+        self.encode_png(&mut png)?;
+        Ok(png)
+    }
+
+    pub fn write_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.encode_png(&mut out)?;
+        out.flush()
+    }
+
+    fn encode_png(&self, out: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let data: Vec<u8> = self.pixels.iter().flatten().copied().collect();
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&data))
+            .map_err(io::Error::other)
+    }
+}
+
+/// A sequence of frames played back as a looping GIF.
+///
+/// GIFs hold at most 256 colours, which is plenty for a palette picked per
+/// cell. Frames smaller than the largest one are drawn in its top-left corner.
+#[derive(Debug, Clone, Default)]
+pub struct Animation {
+    frames: Vec<Image>,
+    delay: u16,
+}
+
+impl Animation {
+    /// An empty animation showing every frame for `delay` hundredths of a
+    /// second.
+    pub fn new(delay: u16) -> Animation {
+        Animation {
+            frames: vec![],
+            delay,
+        }
+    }
+
+    pub fn push(&mut self, frame: Image) {
+        self.frames.push(frame);
+    }
+
+    pub fn frames(&self) -> &[Image] {
+        &self.frames
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn to_gif(&self) -> io::Result<Vec<u8>> {
+        let mut gif = vec![];
+        self.encode_gif(&mut gif)?;
+        Ok(gif)
+    }
+
+    pub fn write_gif(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.encode_gif(&mut out)?;
+        out.flush()
+    }
+
+    fn encode_gif(&self, out: impl Write) -> io::Result<()> {
+        let width = self.frames.iter().map(Image::width).max().unwrap_or(0);
+        let height = self.frames.iter().map(Image::height).max().unwrap_or(0);
+        let (width, height) = match (u16::try_from(width), u16::try_from(height)) {
+            (Ok(width), Ok(height)) => (width, height),
+            _ => return Err(invalid_gif(format!("{width}x{height} is too large"))),
+        };
+
+        // Colours are numbered in the order they first show up.
+        let mut indices: HashMap<Rgb, u8> = HashMap::new();
+        let mut palette: Vec<u8> = vec![];
+        let mut index_of = |colour: Rgb| -> io::Result<u8> {
+            if let Some(&index) = indices.get(&colour) {
+                return Ok(index);
+            }
+            let index = u8::try_from(indices.len())
+                .map_err(|_| invalid_gif("more than 256 colours".to_string()))?;
+            indices.insert(colour, index);
+            palette.extend(colour);
+            Ok(index)
+        };
+        let buffers = self
+            .frames
+            .iter()
+            .map(|frame| frame.pixels.iter().map(|&c| index_of(c)).collect())
+            .collect::<io::Result<Vec<Vec<u8>>>>()?;
+        // The encoder expects a palette of at least two colours.
+        palette.resize(palette.len().max(6), 0);
+
+        let mut encoder = gif::Encoder::new(out, width, height, &palette).map_err(gif_error)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(gif_error)?;
+        for (frame, buffer) in self.frames.iter().zip(buffers) {
+            let frame = gif::Frame {
+                width: frame.width as u16,
+                height: frame.height as u16,
+                delay: self.delay,
+                buffer: buffer.into(),
+                ..gif::Frame::default()
+            };
+            encoder.write_frame(&frame).map_err(gif_error)?;
+        }
+        Ok(())
+    }
+}
+
+fn invalid_gif(message: String) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("cannot encode GIF: {message}"),
+    )
+}
+
+fn gif_error(err: gif::EncodingError) -> io::Error {
+    match err {
+        gif::EncodingError::Io(err) => err,
+        err => invalid_gif(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard() -> Grid<bool> {
+        Grid::new(3, 2, vec![true, false, true, false, true, false])
+    }
+
+    #[test]
+    fn draws_grids_and_points_as_text() {
+        assert_eq!(
+            ascii(&checkerboard(), |&b| if b { '#' } else { '.' }),
+            "#.#\n.#."
+        );
+        assert_eq!(
+            ascii_points([(5, -1), (7, 0), (6, 1)], '#', '.'),
+            "#..\n..#\n.#."
+        );
+        assert_eq!(ascii_points(Vec::<(i64, i64)>::new(), '#', '.'), "");
+    }
+
+    #[test]
+    fn colours_and_scales_images() {
+        let image = Image::from_grid(&checkerboard(), |&b| if b { WHITE } else { BLACK });
+        let scaled = image.scaled(2);
+        assert_eq!((scaled.width(), scaled.height()), (6, 4));
+        for y in 0..4 {
+            for x in 0..6 {
+                assert_eq!(scaled.get((x, y)), image.get((x / 2, y / 2)));
+            }
+        }
+        assert_eq!(scaled.get((6, 0)), None);
+    }
+
+    #[test]
+    fn encodes_ppm() {
+        let mut image = Image::filled(2, 1, BLACK);
+        image.set((1, 0), [1, 2, 3]);
+        assert_eq!(image.to_ppm(), b"P6\n2 1\n255\n\0\0\0\x01\x02\x03");
+    }
+
+    #[test]
+    fn encodes_png_and_gif() {
+        let image = Image::from_points([(0, 0), (2, 1)], WHITE, [40, 40, 120]);
+        let png = image.to_png().unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+        let mut animation = Animation::new(5);
+        animation.push(image.clone());
+        animation.push(image.scaled(2));
+        let gif = animation.to_gif().unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+        // The logical screen fits the largest frame.
+        assert_eq!(&gif[6..10], &[6, 0, 4, 0]);
+    }
+
+    #[test]
+    fn refuses_gifs_with_too_many_colours() {
+        let mut image = Image::filled(300, 1, BLACK);
+        for x in 0..300 {
+            image.set((x, 0), [x as u8, (x / 256) as u8, 0]);
+        }
+        let mut animation = Animation::new(5);
+        animation.push(image);
+        let err = animation.to_gif().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(err.to_string(), "cannot encode GIF: more than 256 colours");
+    }
+}
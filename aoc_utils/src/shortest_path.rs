@@ -0,0 +1,257 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+    ops::Add,
+};
+
+use crate::{traverser, TraveralStore};
+
+/// A search state together with the cost to reach it and its priority in the
+/// queue (cost plus heuristic). Ordered so that a [`BinaryHeap`] pops the
+/// lowest priority first.
+struct Scored<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> PartialEq for Scored<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Scored<S, C> {}
+
+impl<S, C: Ord> PartialOrd for Scored<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> Ord for Scored<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<T: Ord> TraveralStore<T> for BinaryHeap<T> {
+    fn next_state(&mut self) -> Option<T> {
+        self.pop()
+    }
+}
+
+/// Cheapest cost from any of `initial_states` to a state satisfying `goal_fn`.
+/// `next_states_fn` returns the reachable states together with the cost of the
+/// step to each of them.
+#[inline]
+pub fn dijkstra<S, C, F, I, G>(
+    initial_states: impl Iterator<Item = S>,
+    next_states_fn: F,
+    goal_fn: G,
+) -> Option<C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+{
+    astar(initial_states, next_states_fn, |_| C::default(), goal_fn)
+}
+
+/// Like [`dijkstra`], but also returns the states along the cheapest path,
+/// starting with the initial state it originated from and ending in the goal.
+#[inline]
+pub fn dijkstra_with_path<S, C, F, I, G>(
+    initial_states: impl Iterator<Item = S>,
+    next_states_fn: F,
+    goal_fn: G,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+{
+    astar_with_path(initial_states, next_states_fn, |_| C::default(), goal_fn)
+}
+
+/// Like [`dijkstra`], guided by `heuristic_fn`. The heuristic must never
+/// overestimate the remaining cost to a goal for the result to be optimal.
+#[inline]
+pub fn astar<S, C, F, I, H, G>(
+    initial_states: impl Iterator<Item = S>,
+    next_states_fn: F,
+    heuristic_fn: H,
+    goal_fn: G,
+) -> Option<C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    H: FnMut(&S) -> C,
+    G: FnMut(&S) -> bool,
+{
+    weighted_search(initial_states, next_states_fn, heuristic_fn, goal_fn, false)
+        .map(|(cost, _, _)| cost)
+}
+
+/// Like [`astar`], but also returns the states along the cheapest path.
+#[inline]
+pub fn astar_with_path<S, C, F, I, H, G>(
+    initial_states: impl Iterator<Item = S>,
+    next_states_fn: F,
+    heuristic_fn: H,
+    goal_fn: G,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    H: FnMut(&S) -> C,
+    G: FnMut(&S) -> bool,
+{
+    let (cost, goal, parents) =
+        weighted_search(initial_states, next_states_fn, heuristic_fn, goal_fn, true)?;
+
+    let mut path = vec![goal];
+    while let Some(parent) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    Some((cost, path))
+}
+
+fn weighted_search<S, C, F, I, H, G>(
+    initial_states: impl Iterator<Item = S>,
+    mut next_states_fn: F,
+    mut heuristic_fn: H,
+    mut goal_fn: G,
+    track_parents: bool,
+) -> Option<(C, S, HashMap<S, S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    H: FnMut(&S) -> C,
+    G: FnMut(&S) -> bool,
+{
+    let mut best_costs: HashMap<S, C> = HashMap::new();
+    let mut parents: HashMap<S, S> = HashMap::new();
+    let mut found = None;
+
+    let heap = initial_states
+        .map(|state| {
+            best_costs.insert(state.clone(), C::default());
+            Scored {
+                priority: heuristic_fn(&state),
+                cost: C::default(),
+                state,
+            }
+        })
+        .collect::<BinaryHeap<_>>();
+
+    traverser(
+        heap,
+        |Scored { cost, state, .. }| {
+            // A cheaper way to this state was queued after this one.
+            if best_costs.get(&state).is_some_and(|best| *best < cost) {
+                return None;
+            }
+            let mut next_states = vec![];
+            for (next, step_cost) in next_states_fn(&state) {
+                let next_cost = cost + step_cost;
+                if best_costs.get(&next).is_some_and(|best| *best <= next_cost) {
+                    continue;
+                }
+                best_costs.insert(next.clone(), next_cost);
+                if track_parents {
+                    parents.insert(next.clone(), state.clone());
+                }
+                next_states.push(Scored {
+                    priority: next_cost + heuristic_fn(&next),
+                    cost: next_cost,
+                    state: next,
+                });
+            }
+            Some(next_states)
+        },
+        |Scored { cost, state, .. }| {
+            if goal_fn(state) {
+                found = Some((*cost, state.clone()));
+                return true;
+            }
+            false
+        },
+    );
+
+    found.map(|(cost, goal)| (cost, goal, parents))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -1-> 1 -1-> 2 -1-> 3
+    // 0 ------5-----------> 3
+    fn edges(n: &u32) -> Vec<(u32, u32)> {
+        match n {
+            0 => vec![(1, 1), (3, 5)],
+            1 => vec![(2, 1)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheaper_longer_path() {
+        assert_eq!(dijkstra([0].into_iter(), edges, |n| *n == 3), Some(3));
+        assert_eq!(
+            dijkstra_with_path([0].into_iter(), edges, |n| *n == 3),
+            Some((3, vec![0, 1, 2, 3]))
+        );
+    }
+
+    #[test]
+    fn dijkstra_unreachable_goal() {
+        assert_eq!(dijkstra([0].into_iter(), edges, |n| *n == 4), None);
+    }
+
+    #[test]
+    fn dijkstra_initial_state_is_goal() {
+        assert_eq!(
+            dijkstra_with_path([2, 0].into_iter(), edges, |n| *n == 0),
+            Some((0, vec![0]))
+        );
+    }
+
+    #[test]
+    fn astar_on_open_grid() {
+        let goal = (5i32, 3i32);
+        let mut expanded = 0;
+        let result = astar_with_path(
+            [(0i32, 0i32)].into_iter(),
+            |&(x, y)| {
+                expanded += 1;
+                [(0, 1), (1, 0), (0, -1), (-1, 0)]
+                    .into_iter()
+                    .map(move |(dx, dy)| ((x + dx, y + dy), 1u32))
+                    .filter(|((x, y), _)| (0..10).contains(x) && (0..10).contains(y))
+            },
+            |&(x, y)| goal.0.abs_diff(x) + goal.1.abs_diff(y),
+            |p| *p == goal,
+        );
+        let (cost, path) = result.unwrap();
+        assert_eq!(cost, 8);
+        assert_eq!(path.len(), 9);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&goal));
+        assert!(expanded < 100);
+    }
+}
@@ -0,0 +1,396 @@
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use crate::Error;
+
+/// One of the two parts of a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    pub fn from_number(number: u8) -> Option<Part> {
+        match number {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// The answer to one part of a puzzle. Most answers are numbers, but some are
+/// strings such as letters drawn on a screen.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    /// Text that is a valid integer becomes a [`Answer::Number`], so that answers
+    /// compare equal however they were produced.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim().parse::<i128>() {
+            Ok(n) => Answer::Number(n),
+            Err(_) => Answer::Text(s.to_string()),
+        })
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        s.parse().unwrap()
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        s.parse().unwrap()
+    }
+}
+
+macro_rules! impl_answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Number(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+/// A puzzle solution, split into parsing the input and solving each part so that
+/// the parsed input can be shared and each step timed on its own.
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+    /// The parts that have been solved. Other parts are never run.
+    const PARTS: &'static [Part] = &Part::BOTH;
+
+    type Parsed<'a>: 'a;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, Error>;
+
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, Error>;
+
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, Error>;
+}
+
+/// Declares the [`Solution`] of a day, along with the `try_process_part1`,
+/// `process_part1`, `try_process_part2` and `process_part2` functions that its
+/// binaries and tests call. Each part takes the parsed input and returns a
+/// `Result` of anything an [`Answer`] can be made from.
+///
+/// ```
+/// use aoc_utils::{parse::{integer, lines, parse_all}, Error};
+///
+/// fn top_three(calories: &[u32]) -> Result<u32, Error> {
+///     let mut calories = calories.to_vec();
+///     calories.sort_unstable_by(|a, b| b.cmp(a));
+///     Ok(calories.iter().take(3).sum())
+/// }
+///
+/// aoc_utils::solution! {
+///     Day01: 2022 day 1;
+///     type Parsed<'a> = Vec<u32>;
+///     parse: |input| parse_all(lines(integer), input);
+///     part1: |calories| calories.iter().copied().max().ok_or_else(|| Error::invalid("no elves"));
+///     part2: |calories| top_three(calories);
+/// }
+///
+/// assert_eq!(try_process_part1("3\n5\n1\n4"), Ok("5".to_string()));
+/// assert_eq!(process_part2("3\n5\n1\n4"), "12");
+/// ```
+///
+/// A day that doesn't have both parts lists the ones it has after its name,
+/// as in `parts: [One];`.
+#[macro_export]
+macro_rules! solution {
+    (
+        $day:ident: $year:literal day $number:literal;
+        $(parts: [$($part:ident),*];)?
+        type Parsed<$lifetime:lifetime> = $parsed:ty;
+        parse: |$input:ident| $parse:expr;
+        part1: |$parsed1:pat_param| $part1:expr;
+        part2: |$parsed2:pat_param| $part2:expr;
+    ) => {
+        pub struct $day;
+
+        impl $crate::Solution for $day {
+            const YEAR: u16 = $year;
+            const DAY: u8 = $number;
+            $(const PARTS: &'static [$crate::Part] = &[$($crate::Part::$part),*];)?
+
+            type Parsed<$lifetime> = $parsed;
+
+            fn parse($input: &str) -> Result<Self::Parsed<'_>, $crate::Error> {
+                $parse
+            }
+
+            fn part1($parsed1: &Self::Parsed<'_>) -> Result<$crate::Answer, $crate::Error> {
+                $part1.map($crate::Answer::from)
+            }
+
+            fn part2($parsed2: &Self::Parsed<'_>) -> Result<$crate::Answer, $crate::Error> {
+                $part2.map($crate::Answer::from)
+            }
+        }
+
+        pub fn try_process_part1(input: &str) -> Result<String, $crate::Error> {
+            let parsed = <$day as $crate::Solution>::parse(input)?;
+            Ok(<$day as $crate::Solution>::part1(&parsed)?.to_string())
+        }
+
+        pub fn process_part1(input: &str) -> String {
+            try_process_part1(input).unwrap_or_else(|err| panic!("{err}"))
+        }
+
+        pub fn try_process_part2(input: &str) -> Result<String, $crate::Error> {
+            let parsed = <$day as $crate::Solution>::parse(input)?;
+            Ok(<$day as $crate::Solution>::part2(&parsed)?.to_string())
+        }
+
+        pub fn process_part2(input: &str) -> String {
+            try_process_part2(input).unwrap_or_else(|err| panic!("{err}"))
+        }
+    };
+}
+
+/// Parsed input of a registered solution, ready to solve either part.
+pub trait ParsedInput {
+    fn solve(&self, part: Part) -> Result<Answer, Error>;
+}
+
+struct Parsed<'a, S: Solution>(S::Parsed<'a>);
+
+impl<'a, S: Solution> ParsedInput for Parsed<'a, S> {
+    fn solve(&self, part: Part) -> Result<Answer, Error> {
+        match part {
+            Part::One => S::part1(&self.0),
+            Part::Two => S::part2(&self.0),
+        }
+    }
+}
+
+type BoxedParsed<'a> = Box<dyn ParsedInput + 'a>;
+
+fn parse_boxed<S: Solution + 'static>(input: &str) -> Result<BoxedParsed<'_>, Error> {
+    Ok(Box::new(Parsed::<S>(S::parse(input)?)))
+}
+
+/// A solution in a [`Registry`].
+#[derive(Clone, Copy)]
+pub struct Entry {
+    year: u16,
+    day: u8,
+    parts: &'static [Part],
+    parse: for<'a> fn(&'a str) -> Result<BoxedParsed<'a>, Error>,
+}
+
+impl Entry {
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    pub fn parts(&self) -> &'static [Part] {
+        self.parts
+    }
+
+    pub fn has_part(&self, part: Part) -> bool {
+        self.parts.contains(&part)
+    }
+
+    pub fn parse<'a>(&self, input: &'a str) -> Result<BoxedParsed<'a>, Error> {
+        (self.parse)(input)
+    }
+
+    /// Parses `input` and solves `part` in one go.
+    pub fn solve(&self, input: &str, part: Part) -> Result<Answer, Error> {
+        self.parse(input)?.solve(part)
+    }
+}
+
+impl fmt::Debug for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Entry")
+            .field("year", &self.year)
+            .field("day", &self.day)
+            .field("parts", &self.parts)
+            .finish()
+    }
+}
+
+/// All known solutions, ordered by year and day.
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+    entries: BTreeMap<(u16, u8), Entry>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `S`, panicking if a solution for the same day was added before.
+    pub fn register<S: Solution + 'static>(&mut self) -> &mut Self {
+        let entry = Entry {
+            year: S::YEAR,
+            day: S::DAY,
+            parts: S::PARTS,
+            parse: parse_boxed::<S>,
+        };
+        let previous = self.entries.insert((S::YEAR, S::DAY), entry);
+        assert!(
+            previous.is_none(),
+            "{} day {} registered twice",
+            S::YEAR,
+            S::DAY
+        );
+        self
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Option<&Entry> {
+        self.entries.get(&(year, day))
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> + '_ {
+        self.entries.values()
+    }
+
+    /// Every solved `(year, day, part)`.
+    pub fn parts(&self) -> impl Iterator<Item = (u16, u8, Part)> + '_ {
+        self.entries()
+            .flat_map(|e| e.parts.iter().map(move |p| (e.year, e.day, *p)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::{integer, lines, parse_all};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const YEAR: u16 = 2000;
+        const DAY: u8 = 2;
+
+        type Parsed<'a> = Vec<u32>;
+
+        fn parse(input: &str) -> Result<Self::Parsed<'_>, Error> {
+            parse_all(lines(integer), input)
+        }
+
+        fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, Error> {
+            Ok(parsed.iter().sum::<u32>().into())
+        }
+
+        fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, Error> {
+            Ok(parsed.iter().map(u32::to_string).collect::<String>().into())
+        }
+    }
+
+    struct Unfinished;
+
+    impl Solution for Unfinished {
+        const YEAR: u16 = 2000;
+        const DAY: u8 = 1;
+        const PARTS: &'static [Part] = &[Part::One];
+
+        type Parsed<'a> = &'a str;
+
+        fn parse(input: &str) -> Result<Self::Parsed<'_>, Error> {
+            Ok(input)
+        }
+
+        fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, Error> {
+            Ok(Answer::Text(parsed.to_uppercase()))
+        }
+
+        fn part2(_: &Self::Parsed<'_>) -> Result<Answer, Error> {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn answers_compare_by_value() {
+        assert_eq!(Answer::from("42"), Answer::from(42u64));
+        assert_eq!(Answer::from("-7".to_string()), Answer::Number(-7));
+        assert_eq!(Answer::from("ABC"), Answer::Text("ABC".to_string()));
+        assert_eq!(Answer::from(usize::MAX).to_string(), usize::MAX.to_string());
+    }
+
+    #[test]
+    fn registry_enumerates_solved_parts() {
+        let mut registry = Registry::new();
+        registry.register::<Sum>().register::<Unfinished>();
+        assert_eq!(
+            registry.parts().collect::<Vec<_>>(),
+            vec![
+                (2000, 1, Part::One),
+                (2000, 2, Part::One),
+                (2000, 2, Part::Two)
+            ]
+        );
+
+        let entry = registry.get(2000, 2).unwrap();
+        let parsed = entry.parse("1\n2\n3").unwrap();
+        assert_eq!(parsed.solve(Part::One), Ok(Answer::Number(6)));
+        assert_eq!(parsed.solve(Part::Two), Ok(Answer::Number(123)));
+        assert_eq!(
+            entry.solve("1\r\n2x\r\n", Part::One),
+            Err(Error::Parse {
+                line: 2,
+                column: 2,
+                message: "unexpected \"x\"".to_string()
+            })
+        );
+        assert_eq!(
+            registry.get(2000, 1).unwrap().solve("abc", Part::One),
+            Ok(Answer::from("ABC"))
+        );
+        assert!(!registry.get(2000, 1).unwrap().has_part(Part::Two));
+        assert!(registry.get(2001, 1).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "registered twice")]
+    fn registering_a_day_twice_panics() {
+        Registry::new().register::<Sum>().register::<Sum>();
+    }
+}
@@ -0,0 +1,212 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+use crate::{traverser, TraveralStore};
+
+/// Everything a [`tracked_bfs`] or [`tracked_dfs`] learned about the states it reached,
+/// keyed by the key function passed to the search.
+#[derive(Debug, Clone)]
+pub struct SearchResult<K, S> {
+    nodes: HashMap<K, (usize, Option<K>)>,
+    terminal_state: Option<S>,
+}
+
+impl<K, S> SearchResult<K, S>
+where
+    K: Clone + Eq + Hash,
+{
+    /// Number of steps from an initial state to `key`, if it was reached. For a
+    /// BFS this is the shortest distance, for a DFS the depth it was discovered at.
+    pub fn depth(&self, key: &K) -> Option<usize> {
+        self.nodes.get(key).map(|(depth, _)| *depth)
+    }
+
+    pub fn distances(&self) -> impl Iterator<Item = (&K, usize)> + '_ {
+        self.nodes.iter().map(|(key, (depth, _))| (key, *depth))
+    }
+
+    pub fn reached(&self) -> impl Iterator<Item = &K> + '_ {
+        self.nodes.keys()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.nodes.contains_key(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Keys from the initial state that led to `key` up to and including `key`.
+    pub fn path_to(&self, key: &K) -> Option<Vec<K>> {
+        let mut path = vec![key.clone()];
+        let mut parent = self.nodes.get(key)?.1.clone();
+        while let Some(p) = parent {
+            parent = self.nodes[&p].1.clone();
+            path.push(p);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// The state that ended the search early, if any.
+    pub fn terminal_state(&self) -> Option<&S> {
+        self.terminal_state.as_ref()
+    }
+}
+
+#[inline]
+fn tracked_traverser<S, K, F, G, H>(
+    mut ts: impl TraveralStore<S>,
+    initial_states: impl Iterator<Item = S>,
+    mut key_fn: H,
+    mut next_states_fn: F,
+    terminal_state_fn: G,
+) -> SearchResult<K, S>
+where
+    K: Clone + Eq + Hash,
+    F: FnMut(S) -> Option<Vec<S>>,
+    G: FnMut(&S) -> bool,
+    H: FnMut(&S) -> K,
+{
+    let mut nodes = HashMap::new();
+    ts.extend(initial_states.filter(|state| nodes.insert(key_fn(state), (0, None)).is_none()));
+
+    let terminal_state = traverser(
+        ts,
+        |state| {
+            let key = key_fn(&state);
+            let depth = nodes[&key].0;
+            let next_states = next_states_fn(state)?
+                .into_iter()
+                .filter(|next| {
+                    let next_key = key_fn(next);
+                    if nodes.contains_key(&next_key) {
+                        return false;
+                    }
+                    nodes.insert(next_key, (depth + 1, Some(key.clone())));
+                    true
+                })
+                .collect::<Vec<_>>();
+            Some(next_states)
+        },
+        terminal_state_fn,
+    );
+    SearchResult {
+        nodes,
+        terminal_state,
+    }
+}
+
+/// Breadth-first search that only visits each key returned by `key_fn` once and
+/// records the distance and predecessor of every reached key.
+#[inline]
+pub fn tracked_bfs<S, K, F, G, H>(
+    initial_states: impl Iterator<Item = S>,
+    key_fn: H,
+    next_states_fn: F,
+    terminal_state_fn: G,
+) -> SearchResult<K, S>
+where
+    K: Clone + Eq + Hash,
+    F: FnMut(S) -> Option<Vec<S>>,
+    G: FnMut(&S) -> bool,
+    H: FnMut(&S) -> K,
+{
+    tracked_traverser(
+        VecDeque::new(),
+        initial_states,
+        key_fn,
+        next_states_fn,
+        terminal_state_fn,
+    )
+}
+
+/// Depth-first counterpart of [`tracked_bfs`].
+#[inline]
+pub fn tracked_dfs<S, K, F, G, H>(
+    initial_states: impl Iterator<Item = S>,
+    key_fn: H,
+    next_states_fn: F,
+    terminal_state_fn: G,
+) -> SearchResult<K, S>
+where
+    K: Clone + Eq + Hash,
+    F: FnMut(S) -> Option<Vec<S>>,
+    G: FnMut(&S) -> bool,
+    H: FnMut(&S) -> K,
+{
+    tracked_traverser(
+        Vec::new(),
+        initial_states,
+        key_fn,
+        next_states_fn,
+        terminal_state_fn,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 - 1 - 2 - 3
+    //  \_______/
+    fn edges(n: u32) -> Option<Vec<u32>> {
+        match n {
+            0 => Some(vec![1, 2]),
+            1 => Some(vec![0, 2]),
+            2 => Some(vec![1, 0, 3]),
+            3 => Some(vec![2]),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn bfs_records_distances_and_parents() {
+        let result = tracked_bfs([0].into_iter(), |n| *n, edges, |_| false);
+        assert_eq!(result.len(), 4);
+        assert_eq!(result.depth(&0), Some(0));
+        assert_eq!(result.depth(&2), Some(1));
+        assert_eq!(result.depth(&3), Some(2));
+        assert_eq!(result.path_to(&3), Some(vec![0, 2, 3]));
+        assert_eq!(result.path_to(&7), None);
+        assert!(result.terminal_state().is_none());
+    }
+
+    #[test]
+    fn bfs_deduplicates_by_key() {
+        let mut invocations = 0;
+        // The second tuple element is ignored by the key, so every node is only expanded once.
+        let result = tracked_bfs(
+            [(0, 'a'), (0, 'b')].into_iter(),
+            |(n, _)| *n,
+            |(n, tag)| {
+                invocations += 1;
+                edges(n).map(|next| next.into_iter().map(|m| (m, tag)).collect())
+            },
+            |_| false,
+        );
+        assert_eq!(invocations, 4);
+        assert_eq!(result.reached().count(), 4);
+    }
+
+    #[test]
+    fn interrupted_bfs_keeps_terminal_state() {
+        let result = tracked_bfs([0].into_iter(), |n| *n, edges, |n| *n == 3);
+        assert_eq!(result.terminal_state(), Some(&3));
+        assert_eq!(result.depth(&3), Some(2));
+    }
+
+    #[test]
+    fn dfs_records_discovery_depth() {
+        let result = tracked_dfs([0].into_iter(), |n| *n, edges, |_| false);
+        assert_eq!(result.len(), 4);
+        assert_eq!(result.path_to(&3), Some(vec![0, 2, 3]));
+    }
+}
@@ -1,7 +1,11 @@
+use aoc_utils::inputs;
 use day_aoc::process_part1;
-use std::fs;
+
+// The date of the puzzle, to be filled in when copying the template.
+const YEAR: u16 = 2022;
+const DAY: u8 = 1;
 
 fn main() {
-    let file = fs::read_to_string("./input.txt").unwrap();
-    println!("{}", process_part1(file.as_str()));
+    let input = inputs::input(YEAR, DAY).unwrap_or_else(|err| panic!("{err}"));
+    println!("{}", process_part1(input));
 }